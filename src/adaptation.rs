@@ -0,0 +1,82 @@
+//! This module contains the strategies to adapt the mutation rate during the execution.
+
+use crate::statistics::GenerationStatistics;
+
+/// Success ratio of the 1/5th success rule.
+const ONE_FIFTH: f64 = 0.2;
+
+/// Strategies to adapt the mutation rate. All of them start from `GeneticAlgorithm::mutation_rate`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MutationAdaptation {
+    /// The mutation rate does not change.
+    Constant,
+    /// The mutation rate decreases linearly from the initial rate to `end` in the last iteration.
+    LinearDecay { end: f32 },
+    /// The mutation rate is multiplied by `decay` every iteration.
+    ExponentialDecay { decay: f32 },
+    /// The 1/5th success rule. If more than 1/5 of the mutations improve the fitness of the gene the mutation rate is
+    /// multiplied by `factor`, and if less it is divided by `factor`.
+    ///
+    /// # Notes
+    ///
+    /// The fitness of every mutated gene is calculated before the mutation to count the successes.
+    OneFifthRule { factor: f32 },
+    /// The mutation rate is `rate` while the mean pairwise distance of the generation is lower or equal than
+    /// `diversity_threshold`, and the initial rate otherwise. It only has effect if the gene defines `Gene::distance`.
+    Hypermutation { diversity_threshold: f64, rate: f32 },
+    /// Every gene mutates with its own rate returned by `Gene::mutation_rate`, or with the initial rate if it does
+    /// not define one. The genes encoding step sizes, returned by `Gene::step_size`, are mutated with
+    /// `Gene::self_adaptive_mutate` instead of the mutation operator, and the mean step size of every generation is
+    /// reported in `GenerationStatistics::step_size`.
+    SelfAdaptive,
+}
+
+impl MutationAdaptation {
+    /// Calculates the mutation rate of the next mutation phase.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_rate` - The mutation rate set in the `GeneticAlgorithm`.
+    /// * `current_rate` - The mutation rate of the last mutation phase.
+    /// * `iteration` - The current iteration.
+    /// * `iterations` - The max iterations.
+    /// * `statistics` - The statistics of the current generation.
+    /// * `success_ratio` - The ratio of the last mutations that improved the fitness, if they were counted.
+    pub(crate) fn next_rate(
+        &self,
+        initial_rate: f32,
+        current_rate: f32,
+        iteration: u32,
+        iterations: u32,
+        statistics: Option<&GenerationStatistics>,
+        success_ratio: Option<f64>,
+    ) -> f32 {
+        let rate = match *self {
+            MutationAdaptation::Constant | MutationAdaptation::SelfAdaptive => initial_rate,
+            MutationAdaptation::LinearDecay { end } => {
+                let progress = iteration as f32 / iterations.saturating_sub(1).max(1) as f32;
+                initial_rate + (end - initial_rate) * progress.min(1.0)
+            }
+            MutationAdaptation::ExponentialDecay { decay } => {
+                initial_rate * decay.powi(iteration.min(i32::MAX as u32) as i32)
+            }
+            MutationAdaptation::OneFifthRule { factor } => match success_ratio {
+                Some(ratio) if ratio > ONE_FIFTH => current_rate * factor,
+                Some(ratio) if ratio < ONE_FIFTH => current_rate / factor,
+                _ => current_rate,
+            },
+            MutationAdaptation::Hypermutation {
+                diversity_threshold,
+                rate,
+            } => {
+                let distance =
+                    statistics.and_then(|statistics| statistics.diversity.mean_pairwise_distance);
+                match distance {
+                    Some(distance) if distance <= diversity_threshold => rate,
+                    _ => initial_rate,
+                }
+            }
+        };
+        rate.clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,563 @@
+//! This module contains the Age-Layered Population Structure (ALPS) driver.
+//!
+//! The population is split in layers, each one evolved by its own `GeneticAlgorithm` of `AgedGene`, with its own
+//! `Selection`. The genes get older every iteration and move to the next layer when they exceed the age limit of
+//! their layer, while new random genes are injected in the bottom layer every `age_gap` iterations. That way the young
+//! genes do not compete with the old ones and the population does not converge prematurely.
+
+use core::fmt;
+use std::error::Error;
+
+use crate::genetic_algorithm::{GeneticAlgorithm, StopCriteria};
+use crate::logger::{Logger, VerbosityLevel};
+use crate::Gene;
+
+/// Default value for the max iterations.
+const MAX_ITERATIONS_DEFAULT: u32 = 100;
+/// Default value for the number of iterations between the injections of new genes.
+const AGE_GAP_DEFAULT: u32 = 10;
+/// Minimum number of genes a layer needs to cross them over.
+const MIN_LAYER_SIZE: usize = 2;
+
+/// How the age limits of the layers grow, in units of `age_gap`. The last layer has no limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AgingScheme {
+    /// 1, 2, 3, 4, 5, ...
+    Linear,
+    /// 1, 2, 4, 9, 16, 25, ...
+    Polynomial,
+    /// 1, 2, 4, 8, 16, ...
+    Exponential,
+}
+
+impl AgingScheme {
+    /// Returns the age limit of a layer in units of `age_gap`.
+    fn factor(&self, layer: usize) -> u32 {
+        let layer = layer as u32;
+        match self {
+            AgingScheme::Linear => layer + 1,
+            AgingScheme::Polynomial if layer < 2 => layer + 1,
+            AgingScheme::Polynomial => layer * layer,
+            AgingScheme::Exponential => 2u32.saturating_pow(layer),
+        }
+    }
+}
+
+/// Wrapper of a gene with an age, the number of iterations its genetic material has been evolving. The children take
+/// the age of their oldest parent and the fitness is only calculated again after a change.
+#[derive(Clone, Debug)]
+pub struct AgedGene<T> {
+    /// The wrapped gene.
+    gene: T,
+    /// The age of the gene.
+    age: u32,
+    /// The fitness of the wrapped gene.
+    fitness: f64,
+    /// If the fitness is up to date.
+    evaluated: bool,
+}
+
+impl<T> AgedGene<T> {
+    /// Creates a new gene of age 0 wrapping `gene`, e.g. in the `GeneticAlgorithm::gene_init` of the layers.
+    pub fn new(gene: T) -> Self {
+        AgedGene {
+            gene,
+            age: 0,
+            fitness: 0.0,
+            evaluated: false,
+        }
+    }
+
+    /// Returns the wrapped gene.
+    pub fn gene(&self) -> &T {
+        &self.gene
+    }
+
+    /// Returns the wrapped gene, consuming the wrapper.
+    pub fn into_gene(self) -> T {
+        self.gene
+    }
+
+    /// Returns the age of the gene.
+    pub fn get_age(&self) -> u32 {
+        self.age
+    }
+}
+
+impl<T: Gene + Clone> Gene for AgedGene<T> {
+    fn init() -> Self {
+        AgedGene::new(T::init())
+    }
+
+    fn calculate_fitness(&mut self) -> f64 {
+        if !self.evaluated {
+            self.fitness = self.gene.calculate_fitness();
+            self.evaluated = true;
+        }
+        self.fitness
+    }
+
+    fn crossover(&self, other: &Self) -> Self {
+        AgedGene {
+            gene: self.gene.crossover(&other.gene),
+            age: self.age.max(other.age),
+            fitness: 0.0,
+            evaluated: false,
+        }
+    }
+
+    fn mutate(&mut self) {
+        self.gene.mutate();
+        self.evaluated = false;
+    }
+
+    fn get_fitness(&self) -> f64 {
+        self.fitness
+    }
+
+    fn distance(&self, other: &Self) -> Option<f64> {
+        self.gene.distance(&other.gene)
+    }
+
+    fn mutation_rate(&self) -> Option<f32> {
+        self.gene.mutation_rate()
+    }
+
+    fn step_size(&self) -> Option<f64> {
+        self.gene.step_size()
+    }
+
+    fn self_adaptive_mutate(&mut self) {
+        self.gene.self_adaptive_mutate();
+        self.evaluated = false;
+    }
+
+    fn constraint_violation(&self) -> f64 {
+        self.gene.constraint_violation()
+    }
+
+    fn repair(&mut self) {
+        self.gene.repair();
+        self.evaluated = false;
+    }
+
+    fn neighbour(&self) -> Self
+    where
+        Self: Clone,
+    {
+        AgedGene {
+            gene: self.gene.neighbour(),
+            age: self.age,
+            fitness: 0.0,
+            evaluated: false,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.gene.is_valid()
+    }
+
+    fn behaviour(&self) -> Option<Vec<f64>> {
+        self.gene.behaviour()
+    }
+}
+
+/// Age-Layered Population Structure driver. Every iteration the genes get one iteration older, the genes older than
+/// the age limit of their layer move to the next one, the bottom layer is replaced by new random genes every `age_gap`
+/// iterations and then every `GeneticAlgorithm` goes to its next iteration.
+pub struct Alps<T: Gene + Clone> {
+    /// The algorithms evolving every layer, from the youngest to the oldest.
+    layers: Vec<GeneticAlgorithm<AgedGene<T>>>,
+    /// Number of iterations between the injections of new genes, the unit of the age limits.
+    age_gap: u32,
+    /// How the age limits of the layers grow.
+    aging_scheme: AgingScheme,
+    /// Num of the max iterations the driver will perform.
+    iterations: u32,
+    /// The current iteration.
+    current_iteration: u32,
+    /// The best gene of any layer overall.
+    best_gene: Option<AgedGene<T>>,
+    /// The fitness value to reach to end the driver.
+    fitness_goal: f64,
+    /// If the driver is running or not.
+    running: bool,
+    /// The stop reason if the driver has stopped.
+    stop_criteria: StopCriteria,
+    /// The logger of this execution.
+    logger: Logger,
+}
+
+impl<T: Gene + Clone> Alps<T> {
+    /// Creates a new `Alps` with default values.
+    /// * `age_gap` = 10
+    /// * `aging_scheme` = AgingScheme::Polynomial
+    /// * `iterations` = 100
+    /// * `fitness_goal` = f64::MAX
+    ///
+    /// # Arguments
+    ///
+    /// * `layers` - The algorithms evolving every layer from the youngest to the oldest, configured with their builder.
+    ///
+    /// # Panics
+    ///
+    /// If there are no layers.
+    ///
+    /// # Notes
+    ///
+    /// The iterations and the stop criteria of the algorithms are ignored, the driver stops them.
+    pub fn new(layers: Vec<GeneticAlgorithm<AgedGene<T>>>) -> Self {
+        if layers.is_empty() {
+            panic!("ALPS needs at least one layer");
+        }
+
+        Alps {
+            layers,
+            age_gap: AGE_GAP_DEFAULT,
+            aging_scheme: AgingScheme::Polynomial,
+            iterations: MAX_ITERATIONS_DEFAULT,
+            current_iteration: 0,
+            best_gene: None,
+            fitness_goal: f64::MAX,
+            running: false,
+            stop_criteria: StopCriteria::Unknown,
+            logger: Logger::default(),
+        }
+    }
+
+    /// Initiate the driver and the algorithms of every layer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the IO error if the logger destination of the driver or of any algorithm could not be opened.
+    pub fn init(mut self) -> Result<Self, Box<dyn Error>> {
+        self.logger.open()?;
+        self.layers = self
+            .layers
+            .into_iter()
+            .map(|layer| layer.init())
+            .collect::<Result<_, _>>()?;
+        self.start();
+        Ok(self)
+    }
+
+    /// Runs the driver by itself without user control.
+    ///
+    /// # Returns
+    ///
+    /// * `(best_gene, stop_criteria)` - The best gene of any layer overall and the stop reason.
+    pub fn run(mut self) -> (T, StopCriteria) {
+        if !self.is_running() {
+            self.start();
+        }
+
+        while self.running {
+            self.next_iteration();
+        }
+
+        let best_gene = self.best_gene.map_or_else(T::init, AgedGene::into_gene);
+        (best_gene, self.stop_criteria)
+    }
+
+    /// Marks the driver as running.
+    fn start(&mut self) {
+        self.running = true;
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!("Alps started with values:\n{}", self).as_str(),
+        );
+        self.logger
+            .log(VerbosityLevel::HIGH, "Alps initiated properly.");
+    }
+
+    /// Goes iteration by iteration in case the user wants to have more control over the lifetime of the driver.
+    pub fn next_iteration(&mut self) {
+        let _generation_span = self.logger.enter_generation(self.current_iteration);
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!(
+                ">>>>>>> Started iteration {} <<<<<<<",
+                self.current_iteration
+            )
+            .as_str(),
+        );
+
+        self.aging_phase();
+        self.migration_phase();
+        self.injection_phase();
+        for layer in self.layers.iter_mut() {
+            layer.next_iteration();
+        }
+
+        self.current_iteration += 1;
+        self.check_stop_criteria();
+    }
+
+    /// Makes every gene one iteration older, evaluates it and updates the best gene.
+    fn aging_phase(&mut self) {
+        let _phase_span = self.logger.enter_phase("aging");
+        let age_limits: Vec<Option<u32>> =
+            (0..self.layers.len()).map(|i| self.age_limit(i)).collect();
+        for (i, layer) in self.layers.iter_mut().enumerate() {
+            for gene in layer.generation_mut().iter_mut() {
+                gene.age += 1;
+                gene.calculate_fitness();
+                if self
+                    .best_gene
+                    .as_ref()
+                    .map_or(true, |best| gene.fitness > best.fitness)
+                {
+                    self.best_gene = Some(gene.clone());
+                }
+            }
+            self.logger.log(
+                VerbosityLevel::MID,
+                format!(
+                    "Layer {i}: {} genes, age limit = {:?}",
+                    layer.generation_mut().len(),
+                    age_limits[i]
+                )
+                .as_str(),
+            );
+        }
+        if let Some(best) = &self.best_gene {
+            self.logger.log_best_fitness(best.fitness);
+        }
+    }
+
+    /// Moves the genes older than the age limit of their layer to the next one, from the top to the bottom. A gene
+    /// enters the next layer if it is not full or if it is better than its worst gene, which it replaces. Otherwise
+    /// it is discarded.
+    ///
+    /// # Notes
+    ///
+    /// A layer left with less than two genes is filled up with the best genes of the layer below, or with new random
+    /// genes if it is the bottom layer, so it can still cross them over.
+    fn migration_phase(&mut self) {
+        let _phase_span = self.logger.enter_phase("migration");
+        let mut migrations = 0;
+        for i in (0..self.layers.len().saturating_sub(1)).rev() {
+            let age_limit = self.age_limit(i).unwrap_or(u32::MAX);
+            let (young, old): (Vec<AgedGene<T>>, Vec<AgedGene<T>>) = self.layers[i]
+                .generation_mut()
+                .iter()
+                .cloned()
+                .partition(|gene| gene.age <= age_limit);
+            if old.is_empty() {
+                continue;
+            }
+
+            let next_size = self.layers[i + 1].get_population_size();
+            let mut next_generation = self.layers[i + 1].generation_mut().to_vec();
+            for gene in old {
+                if next_generation.len() < next_size {
+                    next_generation.push(gene);
+                    migrations += 1;
+                    continue;
+                }
+                let worst = next_generation
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| a.fitness.total_cmp(&b.fitness))
+                    .map(|(idx, worst)| (idx, worst.fitness));
+                if let Some((idx, fitness)) = worst {
+                    if gene.fitness > fitness {
+                        next_generation[idx] = gene;
+                        migrations += 1;
+                    }
+                }
+            }
+            self.layers[i + 1].set_generation(next_generation);
+            self.layers[i].set_generation(young);
+        }
+
+        for i in 0..self.layers.len() {
+            let missing = MIN_LAYER_SIZE.saturating_sub(self.layers[i].generation_mut().len());
+            if missing == 0 {
+                continue;
+            }
+            let mut generation = self.layers[i].generation_mut().to_vec();
+            if i == 0 {
+                let layer = &self.layers[0];
+                generation.extend((0..missing).map(|_| layer.new_gene()));
+            } else {
+                let mut below = self.layers[i - 1].generation_mut().to_vec();
+                below.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+                generation.extend(below.into_iter().take(missing));
+            }
+            self.layers[i].set_generation(generation);
+        }
+
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!("{} genes moved to the next layer.", migrations).as_str(),
+        );
+    }
+
+    /// Replaces the bottom layer with new random genes every `age_gap` iterations.
+    fn injection_phase(&mut self) {
+        if self.current_iteration == 0 || self.current_iteration % self.age_gap != 0 {
+            return;
+        }
+
+        let _phase_span = self.logger.enter_phase("injection");
+        let population_size = self.layers[0].get_population_size();
+        let generation = (0..population_size)
+            .map(|_| self.layers[0].new_gene())
+            .collect();
+        self.layers[0].set_generation(generation);
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!(
+                "{} new genes injected in the bottom layer.",
+                population_size
+            )
+            .as_str(),
+        );
+    }
+
+    /// Checks if the driver should stop or not.
+    fn check_stop_criteria(&mut self) {
+        if self
+            .best_gene
+            .as_ref()
+            .is_some_and(|gene| gene.fitness >= self.fitness_goal)
+        {
+            self.running = false;
+            self.stop_criteria = StopCriteria::FitnessAchieved;
+        } else if self.current_iteration >= self.iterations {
+            self.running = false;
+            self.stop_criteria = StopCriteria::MaxIterations;
+        }
+
+        if !self.running {
+            self.logger.log(
+                VerbosityLevel::LOW,
+                format!("Algorithm must stop because of {:?}", self.stop_criteria).as_str(),
+            );
+        }
+    }
+
+    /// Sets the number of iterations between the injections of new genes, the unit of the age limits.
+    ///
+    /// # Panics
+    ///
+    /// If `age_gap` is 0.
+    pub fn age_gap(mut self, age_gap: u32) -> Self {
+        if age_gap == 0 {
+            panic!("Age gap must be greater than 0");
+        }
+
+        self.age_gap = age_gap;
+        self
+    }
+
+    /// Sets how the age limits of the layers grow.
+    pub fn aging_scheme(mut self, aging_scheme: AgingScheme) -> Self {
+        self.aging_scheme = aging_scheme;
+        self
+    }
+
+    /// Sets the max number of iterations the driver will perform.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        if iterations <= self.current_iteration {
+            panic!("Number of iterations is not greater  than the actual iteration.");
+        }
+
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the fitness to reach to stop the driver.
+    pub fn fitness_goal(mut self, fitness_goal: f64) -> Self {
+        self.fitness_goal = fitness_goal;
+        self
+    }
+
+    /// Sets the logger of this execution.
+    pub fn logger(mut self, logger: Logger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Returns the age limit of a layer, the maximum age of its genes, or `None` for the last layer.
+    pub fn age_limit(&self, layer: usize) -> Option<u32> {
+        if layer + 1 >= self.layers.len() {
+            return None;
+        }
+
+        Some(self.age_gap.saturating_mul(self.aging_scheme.factor(layer)))
+    }
+
+    /// Returns the algorithms evolving every layer, from the youngest to the oldest.
+    pub fn get_layers(&self) -> &[GeneticAlgorithm<AgedGene<T>>] {
+        &self.layers
+    }
+
+    /// Returns the number of iterations between the injections of new genes.
+    pub fn get_age_gap(&self) -> u32 {
+        self.age_gap
+    }
+
+    /// Returns how the age limits of the layers grow.
+    pub fn get_aging_scheme(&self) -> AgingScheme {
+        self.aging_scheme
+    }
+
+    /// Returns the max iterations.
+    pub fn get_iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns the current iteration.
+    pub fn get_current_iteration(&self) -> u32 {
+        self.current_iteration
+    }
+
+    /// Returns the best gene of any layer overall with its age.
+    pub fn get_best_gene(&self) -> Option<&AgedGene<T>> {
+        self.best_gene.as_ref()
+    }
+
+    /// Returns the fitness goal.
+    pub fn get_fitness_goal(&self) -> f64 {
+        self.fitness_goal
+    }
+
+    /// Returns if the driver is running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns the stop reason.
+    pub fn get_stop_criteria(&self) -> StopCriteria {
+        self.stop_criteria
+    }
+
+    /// Returns the logger of this execution.
+    pub fn get_logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// Returns the logger of this execution as mutable, for example to flush it.
+    pub fn get_logger_mut(&mut self) -> &mut Logger {
+        &mut self.logger
+    }
+}
+
+impl<T: Gene + Clone> fmt::Display for Alps<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+             "{{\n\tlayers: {},\n\tage_gap: {},\n\taging_scheme: {:?},\n\titerations: {},\n\tcurrent_iteration: {},\n\tfitness_goal: {:?},\n\tstop_criteria: {:?},\n\tbest_gene_fitness: {:?}\n}}",
+            self.layers.len(),
+            self.age_gap,
+            self.aging_scheme,
+            self.iterations,
+            self.current_iteration,
+            self.fitness_goal,
+            self.stop_criteria,
+            self.best_gene.as_ref().map(|gene| gene.fitness)
+        )
+    }
+}
@@ -0,0 +1,846 @@
+//! This module contains the CMA-ES (covariance matrix adaptation evolution strategy) optimiser for `RealVectorGene`.
+//!
+//! The distribution is sampled in the unit hypercube and mapped to the bounds of the `RealVectorProblem`, so every
+//! dimension has the same scale. The eigendecomposition of the covariance matrix is calculated with the Jacobi
+//! method, without external linear algebra libraries.
+
+use core::fmt;
+use rand::Rng;
+use std::collections::VecDeque;
+use std::error::Error;
+
+use crate::genetic_algorithm::StopCriteria;
+use crate::genotypes::real_vector::standard_normal;
+use crate::genotypes::{RealVectorGene, RealVectorProblem};
+use crate::logger::{Logger, VerbosityLevel};
+use crate::statistics::GenerationStatistics;
+use crate::Gene;
+
+/// Default value for the max generations aka iterations, counting the generations of every restart.
+const MAX_ITERATIONS_DEFAULT: u32 = 1000;
+/// Default value for the initial step size, relative to the range of every dimension.
+const INITIAL_SIGMA_DEFAULT: f64 = 0.3;
+/// Default value for the max number of restarts.
+const MAX_RESTARTS_DEFAULT: u32 = 9;
+/// A run restarts when the step size times the largest standard deviation is lower than this value.
+const TOL_X: f64 = 1e-12;
+/// A run restarts when the range of the best fitnesses of the last generations is lower than this value.
+const TOL_FUN: f64 = 1e-12;
+/// A run restarts when the condition number of the covariance matrix is greater than this value.
+const MAX_CONDITION: f64 = 1e14;
+/// Max number of sweeps of the Jacobi eigenvalue method.
+const JACOBI_SWEEPS: usize = 50;
+
+/// Restart strategies applied when the distribution of a run has converged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CmaRestart {
+    /// No restarts. The optimiser stops with `StopCriteria::Converged`.
+    None,
+    /// IPOP-CMA-ES. Every restart multiplies the population size by `increase`, usually 2.0.
+    Ipop { increase: f64 },
+    /// BIPOP-CMA-ES. It alternates restarts with increasing population sizes and restarts with small populations and
+    /// step sizes, choosing the regime that has used fewer evaluations.
+    Bipop,
+}
+
+/// State of the search distribution of a single run.
+struct Distribution {
+    /// The number of dimensions.
+    n: usize,
+    /// The number of samples of every generation.
+    lambda: usize,
+    /// The recombination weights of the best `weights.len()` samples.
+    weights: Vec<f64>,
+    /// The variance effective selection mass.
+    mueff: f64,
+    /// The learning rate of the evolution path of the covariance.
+    cc: f64,
+    /// The learning rate of the evolution path of the step size.
+    cs: f64,
+    /// The learning rate of the rank-one update.
+    c1: f64,
+    /// The learning rate of the rank-mu update.
+    cmu: f64,
+    /// The damping of the step size.
+    damps: f64,
+    /// The expected norm of a standard normal vector.
+    chi_n: f64,
+    /// The mean.
+    mean: Vec<f64>,
+    /// The step size.
+    sigma: f64,
+    /// The covariance matrix.
+    covariance: Vec<Vec<f64>>,
+    /// The eigenvectors of the covariance matrix, by columns.
+    eigenvectors: Vec<Vec<f64>>,
+    /// The square root of the eigenvalues of the covariance matrix.
+    deviations: Vec<f64>,
+    /// The evolution path of the covariance.
+    pc: Vec<f64>,
+    /// The evolution path of the step size.
+    ps: Vec<f64>,
+    /// The number of generations of the run.
+    generations: u32,
+    /// The best fitness of the last generations.
+    history: VecDeque<f64>,
+}
+
+impl Distribution {
+    /// Creates the distribution of a new run with the default parameters of Hansen's tutorial.
+    fn new(mean: Vec<f64>, sigma: f64, lambda: usize) -> Self {
+        let n = mean.len();
+        let nf = n as f64;
+        let mu = lambda / 2;
+        let raw_weights: Vec<f64> = (1..=mu)
+            .map(|i| (mu as f64 + 0.5).ln() - (i as f64).ln())
+            .collect();
+        let sum: f64 = raw_weights.iter().sum();
+        let weights: Vec<f64> = raw_weights.iter().map(|w| w / sum).collect();
+        let mueff = 1.0 / weights.iter().map(|w| w * w).sum::<f64>();
+
+        let cc = (4.0 + mueff / nf) / (nf + 4.0 + 2.0 * mueff / nf);
+        let cs = (mueff + 2.0) / (nf + mueff + 5.0);
+        let c1 = 2.0 / ((nf + 1.3).powi(2) + mueff);
+        let cmu = (1.0 - c1).min(2.0 * (mueff - 2.0 + 1.0 / mueff) / ((nf + 2.0).powi(2) + mueff));
+        let damps = 1.0 + 2.0 * (((mueff - 1.0) / (nf + 1.0)).sqrt() - 1.0).max(0.0) + cs;
+        let chi_n = nf.sqrt() * (1.0 - 1.0 / (4.0 * nf) + 1.0 / (21.0 * nf * nf));
+
+        Distribution {
+            n,
+            lambda,
+            weights,
+            mueff,
+            cc,
+            cs,
+            c1,
+            cmu,
+            damps,
+            chi_n,
+            mean,
+            sigma,
+            covariance: identity(n),
+            eigenvectors: identity(n),
+            deviations: vec![1.0; n],
+            pc: vec![0.0; n],
+            ps: vec![0.0; n],
+            generations: 0,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Samples a point `mean + sigma * B * D * z`.
+    fn sample<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        let z: Vec<f64> = (0..self.n)
+            .map(|i| self.deviations[i] * standard_normal(rng))
+            .collect();
+        (0..self.n)
+            .map(|i| {
+                self.mean[i]
+                    + self.sigma
+                        * (0..self.n)
+                            .map(|j| self.eigenvectors[i][j] * z[j])
+                            .sum::<f64>()
+            })
+            .collect()
+    }
+
+    /// Updates the distribution with the samples of a generation sorted from the best to the worst.
+    fn update(&mut self, sorted: &[Vec<f64>], best_fitness: f64) {
+        let n = self.n;
+        let old_mean = self.mean.clone();
+        self.mean = (0..n)
+            .map(|i| {
+                self.weights
+                    .iter()
+                    .zip(sorted.iter())
+                    .map(|(w, x)| w * x[i])
+                    .sum()
+            })
+            .collect();
+        let step: Vec<f64> = (0..n)
+            .map(|i| (self.mean[i] - old_mean[i]) / self.sigma)
+            .collect();
+
+        // C^(-1/2) * step = B * D^(-1) * B^T * step.
+        let projected: Vec<f64> = (0..n)
+            .map(|j| {
+                (0..n)
+                    .map(|i| self.eigenvectors[i][j] * step[i])
+                    .sum::<f64>()
+                    / self.deviations[j]
+            })
+            .collect();
+        let whitened: Vec<f64> = (0..n)
+            .map(|i| (0..n).map(|j| self.eigenvectors[i][j] * projected[j]).sum())
+            .collect();
+
+        let ps_factor = (self.cs * (2.0 - self.cs) * self.mueff).sqrt();
+        for (ps, whitened) in self.ps.iter_mut().zip(whitened.iter()) {
+            *ps = (1.0 - self.cs) * *ps + ps_factor * whitened;
+        }
+        self.generations += 1;
+        let ps_norm = norm(&self.ps);
+        let hsig =
+            ps_norm / (1.0 - (1.0 - self.cs).powi(2 * self.generations as i32)).sqrt() / self.chi_n
+                < 1.4 + 2.0 / (n as f64 + 1.0);
+        let hsig = if hsig { 1.0 } else { 0.0 };
+
+        let pc_factor = hsig * (self.cc * (2.0 - self.cc) * self.mueff).sqrt();
+        for (pc, step) in self.pc.iter_mut().zip(step.iter()) {
+            *pc = (1.0 - self.cc) * *pc + pc_factor * step;
+        }
+
+        let deltas: Vec<Vec<f64>> = sorted
+            .iter()
+            .take(self.weights.len())
+            .map(|x| (0..n).map(|i| (x[i] - old_mean[i]) / self.sigma).collect())
+            .collect();
+        let keep = 1.0 - self.c1 - self.cmu + (1.0 - hsig) * self.c1 * self.cc * (2.0 - self.cc);
+        for i in 0..n {
+            for j in 0..=i {
+                let rank_mu: f64 = self
+                    .weights
+                    .iter()
+                    .zip(deltas.iter())
+                    .map(|(w, y)| w * y[i] * y[j])
+                    .sum();
+                let value = keep * self.covariance[i][j]
+                    + self.c1 * self.pc[i] * self.pc[j]
+                    + self.cmu * rank_mu;
+                self.covariance[i][j] = value;
+                self.covariance[j][i] = value;
+            }
+        }
+
+        self.sigma *= ((self.cs / self.damps) * (ps_norm / self.chi_n - 1.0)).exp();
+
+        let (eigenvalues, eigenvectors) = symmetric_eigen(&self.covariance);
+        self.deviations = eigenvalues
+            .iter()
+            .map(|value| value.max(f64::MIN_POSITIVE).sqrt())
+            .collect();
+        self.eigenvectors = eigenvectors;
+
+        self.history.push_back(best_fitness);
+        let window = 10 + (30 * n).div_ceil(self.lambda);
+        while self.history.len() > window {
+            self.history.pop_front();
+        }
+    }
+
+    /// Returns the reason to restart the run, if it has converged.
+    fn converged(&self) -> Option<&'static str> {
+        let max_deviation = self.deviations.iter().cloned().fold(0.0, f64::max);
+        let min_deviation = self
+            .deviations
+            .iter()
+            .cloned()
+            .fold(f64::INFINITY, f64::min);
+        let window = 10 + (30 * self.n).div_ceil(self.lambda);
+        let (min_fitness, max_fitness) = self
+            .history
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), f| {
+                (min.min(*f), max.max(*f))
+            });
+
+        if !self.sigma.is_finite() || self.mean.iter().any(|x| !x.is_finite()) {
+            Some("numerical error")
+        } else if self.sigma * max_deviation < TOL_X {
+            Some("TolX")
+        } else if self.history.len() >= window && max_fitness - min_fitness < TOL_FUN {
+            Some("TolFun")
+        } else if (max_deviation / min_deviation).powi(2) > MAX_CONDITION {
+            Some("ConditionCov")
+        } else {
+            None
+        }
+    }
+}
+
+/// CMA-ES optimiser for a `RealVectorProblem`. Higher fitness is better.
+pub struct CmaEs<P: RealVectorProblem> {
+    /// Num of the max iterations the optimiser will perform, counting the generations of every restart.
+    iterations: u32,
+    /// The current iteration.
+    current_iteration: u32,
+    /// The number of samples of every generation of the first run. By default `4 + 3 * ln(n)`.
+    population_size: usize,
+    /// The initial step size, relative to the range of every dimension.
+    initial_sigma: f64,
+    /// The restart strategy.
+    restart: CmaRestart,
+    /// The max number of restarts.
+    max_restarts: u32,
+    /// The number of restarts performed.
+    restarts: u32,
+    /// The number of fitness evaluations of the runs with large populations in BIPOP.
+    large_evaluations: u64,
+    /// The number of fitness evaluations of the runs with small populations in BIPOP.
+    small_evaluations: u64,
+    /// The number of restarts with large populations in BIPOP.
+    large_restarts: u32,
+    /// If the current run has a small population in BIPOP.
+    small_regime: bool,
+    /// The distribution of the current run.
+    distribution: Distribution,
+    /// The last generation.
+    population: Vec<RealVectorGene<P>>,
+    /// The fitness value to reach to end the optimiser.
+    fitness_goal: f64,
+    /// If the optimiser is running or not.
+    running: bool,
+    /// The best gene overall, `None` until the first generation is evaluated.
+    best_gene: Option<RealVectorGene<P>>,
+    /// The stop reason if the optimiser has stopped.
+    stop_criteria: StopCriteria,
+    /// The logger of this execution.
+    logger: Logger,
+    /// Statistics of every generation.
+    statistics: Vec<GenerationStatistics>,
+    /// If the distance metrics of the diversity are saved in the statistics of every generation.
+    diversity_statistics: bool,
+}
+
+impl<P: RealVectorProblem> CmaEs<P> {
+    /// Creates a new `CmaEs` with default values.
+    /// * `iterations` = 1000
+    /// * `population_size` = 4 + 3 * ln(n)
+    /// * `initial_sigma` = 0.3
+    /// * `restart` = CmaRestart::None
+    /// * `max_restarts` = 9
+    /// * `fitness_goal` = f64::MAX
+    pub fn new() -> Self {
+        let population_size = Self::default_population_size();
+        CmaEs {
+            iterations: MAX_ITERATIONS_DEFAULT,
+            current_iteration: 0,
+            population_size,
+            initial_sigma: INITIAL_SIGMA_DEFAULT,
+            restart: CmaRestart::None,
+            max_restarts: MAX_RESTARTS_DEFAULT,
+            restarts: 0,
+            large_evaluations: 0,
+            small_evaluations: 0,
+            large_restarts: 0,
+            small_regime: false,
+            distribution: Distribution::new(
+                vec![0.5; P::BOUNDS.len()],
+                INITIAL_SIGMA_DEFAULT,
+                population_size,
+            ),
+            population: Vec::new(),
+            fitness_goal: f64::MAX,
+            running: false,
+            best_gene: None,
+            stop_criteria: StopCriteria::Unknown,
+            logger: Logger::default(),
+            statistics: Vec::new(),
+            diversity_statistics: false,
+        }
+    }
+
+    /// Returns the default population size `4 + 3 * ln(n)`.
+    fn default_population_size() -> usize {
+        4 + (3.0 * (P::BOUNDS.len().max(1) as f64).ln()).floor() as usize
+    }
+
+    /// Initiate the optimiser.
+    ///
+    /// # Errors
+    ///
+    /// Returns the IO error if the logger destination could not be opened.
+    pub fn init(mut self) -> Result<Self, Box<dyn Error>> {
+        self.logger.open()?;
+        self.start();
+        Ok(self)
+    }
+
+    /// Runs the optimiser by itself without user control.
+    ///
+    /// # Notes
+    ///
+    /// Logger IO errors do not stop the execution. Use `CmaEs::try_run` to stop on them.
+    pub fn run(mut self) -> (RealVectorGene<P>, StopCriteria) {
+        if !self.is_running() {
+            self.start();
+        }
+
+        while self.running {
+            self.next_iteration();
+        }
+
+        (
+            self.best_gene.unwrap_or_else(RealVectorGene::init),
+            self.stop_criteria,
+        )
+    }
+
+    /// Runs the optimiser by itself without user control, stopping if the logger fails to save.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first IO error produced by the logger.
+    pub fn try_run(mut self) -> Result<(RealVectorGene<P>, StopCriteria), Box<dyn Error>> {
+        if !self.is_running() {
+            self = self.init()?;
+        }
+
+        while self.running {
+            self.next_iteration();
+            if self.logger.get_error().is_some() {
+                self.logger.flush()?;
+            }
+        }
+
+        self.logger.flush()?;
+        Ok((
+            self.best_gene.unwrap_or_else(RealVectorGene::init),
+            self.stop_criteria,
+        ))
+    }
+
+    /// Marks the optimiser as running and creates the distribution of the first run.
+    fn start(&mut self) {
+        self.running = true;
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!("CmaEs started with values:\n{}", self).as_str(),
+        );
+        self.distribution = Distribution::new(
+            random_mean(P::BOUNDS.len()),
+            self.initial_sigma,
+            self.population_size,
+        );
+        self.logger
+            .log(VerbosityLevel::HIGH, "CmaEs initiated properly.");
+    }
+
+    /// Goes iteration by iteration in case the user wants to have more control over the lifetime of the optimiser.
+    ///
+    /// # Returns
+    ///
+    /// `self.population` - The last generation.
+    pub fn next_iteration(&mut self) -> &Vec<RealVectorGene<P>> {
+        let _generation_span = self.logger.enter_generation(self.current_iteration);
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!(
+                ">>>>>>> Started iteration {} <<<<<<<",
+                self.current_iteration
+            )
+            .as_str(),
+        );
+
+        let mut rng = rand::thread_rng();
+        let mut samples: Vec<(Vec<f64>, RealVectorGene<P>)> = (0..self.distribution.lambda)
+            .map(|_| {
+                let mut gene = to_gene::<P>(&self.distribution.sample(&mut rng));
+                gene.calculate_fitness();
+                // The distribution is updated with the samples inside the bounds.
+                (to_unit::<P>(&gene), gene)
+            })
+            .collect();
+        samples.sort_by(|a, b| b.1.get_fitness().total_cmp(&a.1.get_fitness()));
+        if self.small_regime {
+            self.small_evaluations += samples.len() as u64;
+        } else {
+            self.large_evaluations += samples.len() as u64;
+        }
+
+        let best_gene = self.best_gene.get_or_insert_with(|| samples[0].1.clone());
+        if samples[0].1.get_fitness() > best_gene.get_fitness() {
+            *best_gene = samples[0].1.clone();
+        }
+        self.logger.log_best_fitness(best_gene.get_fitness());
+
+        let best_fitness = samples[0].1.get_fitness();
+        let (points, population): (Vec<Vec<f64>>, Vec<RealVectorGene<P>>) =
+            samples.into_iter().unzip();
+        self.distribution.update(&points, best_fitness);
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!(
+                "Step size = {}, population size = {}",
+                self.distribution.sigma, self.distribution.lambda
+            )
+            .as_str(),
+        );
+        self.population = population;
+        self.statistics.push(GenerationStatistics::calculate(
+            self.current_iteration,
+            &self.population,
+            self.diversity_statistics,
+        ));
+
+        self.current_iteration += 1;
+        self.check_stop_criteria();
+
+        &self.population
+    }
+
+    /// Restarts the distribution according to the restart strategy.
+    fn restart(&mut self) {
+        let mut rng = rand::thread_rng();
+        let (lambda, sigma) = match self.restart {
+            CmaRestart::None => return,
+            CmaRestart::Ipop { increase } => (
+                (self.distribution.lambda as f64 * increase).round() as usize,
+                self.initial_sigma,
+            ),
+            CmaRestart::Bipop => {
+                if self.large_restarts > 0 && self.small_evaluations < self.large_evaluations {
+                    let u: f64 = rng.gen_range(0.0..1.0);
+                    let large_lambda =
+                        self.population_size as f64 * 2f64.powi(self.large_restarts as i32);
+                    let ratio = 0.5 * large_lambda / self.population_size as f64;
+                    self.small_regime = true;
+                    (
+                        ((self.population_size as f64 * ratio.powf(u * u)).floor() as usize)
+                            .max(self.population_size),
+                        self.initial_sigma * 10f64.powf(-2.0 * u),
+                    )
+                } else {
+                    self.large_restarts += 1;
+                    self.small_regime = false;
+                    (
+                        self.population_size * 2usize.pow(self.large_restarts),
+                        self.initial_sigma,
+                    )
+                }
+            }
+        };
+
+        self.restarts += 1;
+        self.distribution = Distribution::new(random_mean(P::BOUNDS.len()), sigma, lambda.max(2));
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!(
+                "Restart {} with population size = {} and step size = {}",
+                self.restarts, self.distribution.lambda, sigma
+            )
+            .as_str(),
+        );
+    }
+
+    /// Checks if the optimiser should stop, restarting the distribution if it has converged.
+    fn check_stop_criteria(&mut self) {
+        if self
+            .best_gene
+            .as_ref()
+            .is_some_and(|gene| gene.get_fitness() >= self.fitness_goal)
+        {
+            self.running = false;
+            self.stop_criteria = StopCriteria::FitnessAchieved;
+        } else if self.current_iteration >= self.iterations {
+            self.running = false;
+            self.stop_criteria = StopCriteria::MaxIterations;
+        } else if let Some(reason) = self.distribution.converged() {
+            self.logger.log(
+                VerbosityLevel::MID,
+                format!("Distribution converged because of {reason}").as_str(),
+            );
+            if self.restart == CmaRestart::None || self.restarts >= self.max_restarts {
+                self.running = false;
+                self.stop_criteria = StopCriteria::Converged;
+            } else {
+                self.restart();
+            }
+        }
+
+        if !self.running {
+            self.logger.log(
+                VerbosityLevel::LOW,
+                format!("Algorithm must stop because of {:?}", self.stop_criteria).as_str(),
+            );
+        }
+    }
+
+    /// Sets the max number of iterations the optimiser will perform, counting the generations of every restart.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        if iterations <= self.current_iteration {
+            panic!("Number of iterations is not greater  than the actual iteration.");
+        }
+
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the number of samples of every generation of the first run.
+    ///
+    /// # Panics
+    ///
+    /// If the population size is lower than 2.
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        if population_size < 2 {
+            panic!("Population size lower than 2");
+        }
+
+        self.population_size = population_size;
+        self
+    }
+
+    /// Sets the initial step size, relative to the range of every dimension.
+    pub fn initial_sigma(mut self, initial_sigma: f64) -> Self {
+        if initial_sigma <= 0.0 {
+            panic!("Initial sigma is not greater than 0.0");
+        }
+
+        self.initial_sigma = initial_sigma;
+        self
+    }
+
+    /// Sets the restart strategy.
+    pub fn restart_strategy(mut self, restart: CmaRestart) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    /// Sets the max number of restarts.
+    pub fn max_restarts(mut self, max_restarts: u32) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    /// Sets the fitness goal to stop the optimiser.
+    pub fn fitness_goal(mut self, fitness_goal: f64) -> Self {
+        self.fitness_goal = fitness_goal;
+        self
+    }
+
+    /// Sets if the mean pairwise distance and the unique genomes are saved in the statistics of every generation.
+    ///
+    /// # Notes
+    ///
+    /// They need O(n²) calls to `Gene::distance` every generation, so they are not calculated by default.
+    pub fn diversity_statistics(mut self, diversity_statistics: bool) -> Self {
+        self.diversity_statistics = diversity_statistics;
+        self
+    }
+
+    /// Sets the logger of this execution.
+    pub fn logger(mut self, logger: Logger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Returns the max iterations.
+    pub fn get_iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns the current iteration.
+    pub fn get_current_iteration(&self) -> u32 {
+        self.current_iteration
+    }
+
+    /// Returns the number of samples of every generation of the first run.
+    pub fn get_population_size(&self) -> usize {
+        self.population_size
+    }
+
+    /// Returns the number of samples of every generation of the current run.
+    pub fn get_current_population_size(&self) -> usize {
+        self.distribution.lambda
+    }
+
+    /// Returns the initial step size.
+    pub fn get_initial_sigma(&self) -> f64 {
+        self.initial_sigma
+    }
+
+    /// Returns the step size of the current run, relative to the range of every dimension.
+    pub fn get_sigma(&self) -> f64 {
+        self.distribution.sigma
+    }
+
+    /// Returns the mean of the current run inside the bounds of the problem.
+    pub fn get_mean(&self) -> Vec<f64> {
+        to_gene::<P>(&self.distribution.mean).values().to_vec()
+    }
+
+    /// Returns the restart strategy.
+    pub fn get_restart_strategy(&self) -> CmaRestart {
+        self.restart
+    }
+
+    /// Returns the max number of restarts.
+    pub fn get_max_restarts(&self) -> u32 {
+        self.max_restarts
+    }
+
+    /// Returns the number of restarts performed.
+    pub fn get_restarts(&self) -> u32 {
+        self.restarts
+    }
+
+    /// Returns the last generation.
+    pub fn get_population(&self) -> &[RealVectorGene<P>] {
+        &self.population
+    }
+
+    /// Returns the fitness goal.
+    pub fn get_fitness_goal(&self) -> f64 {
+        self.fitness_goal
+    }
+
+    /// Returns if the distance metrics of the diversity are saved in the statistics.
+    pub fn get_diversity_statistics(&self) -> bool {
+        self.diversity_statistics
+    }
+
+    /// Returns the best gene overall, `None` until the first generation is evaluated.
+    pub fn get_best_gene(&self) -> Option<&RealVectorGene<P>> {
+        self.best_gene.as_ref()
+    }
+
+    /// Returns if the optimiser is running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns the stop reason.
+    pub fn get_stop_criteria(&self) -> StopCriteria {
+        self.stop_criteria
+    }
+
+    /// Returns the statistics of every generation.
+    pub fn get_statistics(&self) -> &[GenerationStatistics] {
+        &self.statistics
+    }
+
+    /// Returns the logger of this execution.
+    pub fn get_logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// Returns the logger of this execution as mutable, for example to flush it.
+    pub fn get_logger_mut(&mut self) -> &mut Logger {
+        &mut self.logger
+    }
+}
+
+/// Default trait implementation for CmaEs.
+impl<P: RealVectorProblem> Default for CmaEs<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Display trait implementation for CmaEs.
+impl<P: RealVectorProblem> fmt::Display for CmaEs<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+             "{{\n\titerations: {},\n\tcurrent_iteration: {},\n\tpopulation_size: {},\n\tinitial_sigma: {},\n\trestart: {:?},\n\trestarts: {},\n\tfitness_goal: {:?},\n\tstop_criteria: {:?},\n\tbest_gene_fitness: {:?}\n}}",
+            self.iterations,
+            self.current_iteration,
+            self.population_size,
+            self.initial_sigma,
+            self.restart,
+            self.restarts,
+            self.fitness_goal,
+            self.stop_criteria,
+            self.best_gene.as_ref().map(|gene| gene.get_fitness())
+        )
+    }
+}
+
+/// Maps a point of the unit hypercube to a gene. The values outside the bounds are brought back with
+/// `P::BOUND_HANDLING`.
+fn to_gene<P: RealVectorProblem>(point: &[f64]) -> RealVectorGene<P> {
+    let values = point
+        .iter()
+        .zip(P::BOUNDS.iter())
+        .map(|(x, (lower, upper))| lower + x * (upper - lower))
+        .collect();
+    RealVectorGene::from_values(values)
+}
+
+/// Maps a gene to the unit hypercube.
+fn to_unit<P: RealVectorProblem>(gene: &RealVectorGene<P>) -> Vec<f64> {
+    gene.values()
+        .iter()
+        .zip(P::BOUNDS.iter())
+        .map(|(value, (lower, upper))| {
+            if upper > lower {
+                (value - lower) / (upper - lower)
+            } else {
+                0.5
+            }
+        })
+        .collect()
+}
+
+/// Returns a random point of the unit hypercube.
+fn random_mean(n: usize) -> Vec<f64> {
+    let mut rng = rand::thread_rng();
+    (0..n).map(|_| rng.gen_range(0.0..=1.0)).collect()
+}
+
+/// Returns the identity matrix of size `n`.
+fn identity(n: usize) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect()
+}
+
+/// Returns the Euclidean norm of a vector.
+fn norm(vector: &[f64]) -> f64 {
+    vector.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+/// Calculates the eigendecomposition of a symmetric matrix with the cyclic Jacobi method.
+///
+/// # Returns
+///
+/// (`eigenvalues`, `eigenvectors`) - The eigenvalues and the matrix with their eigenvectors by columns.
+#[allow(clippy::needless_range_loop)]
+fn symmetric_eigen(matrix: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = matrix.len();
+    let mut a: Vec<Vec<f64>> = matrix.to_vec();
+    let mut v = identity(n);
+
+    for _ in 0..JACOBI_SWEEPS {
+        let off_diagonal: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        let diagonal: f64 = (0..n).map(|i| a[i][i] * a[i][i]).sum();
+        if off_diagonal <= f64::EPSILON * f64::EPSILON * diagonal || off_diagonal == 0.0 {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q] == 0.0 {
+                    continue;
+                }
+
+                // Rotation that zeroes a[p][q].
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for k in 0..n {
+                    let (akp, akq) = (a[k][p], a[k][q]);
+                    a[k][p] = c * akp - s * akq;
+                    a[k][q] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[p][k], a[q][k]);
+                    a[p][k] = c * apk - s * aqk;
+                    a[q][k] = s * apk + c * aqk;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    ((0..n).map(|i| a[i][i]).collect(), v)
+}
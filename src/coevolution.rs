@@ -0,0 +1,618 @@
+//! This module contains the co-evolution driver of two populations, possibly of different `Gene` types.
+//!
+//! Every population is evolved by its own `GeneticAlgorithm` of `CoevolvedGene`, a wrapper whose fitness is assigned by
+//! the driver from the interaction with the other population instead of being calculated by the gene.
+
+use core::fmt;
+use rand::seq::{index, SliceRandom};
+use std::error::Error;
+
+use crate::genetic_algorithm::{GeneticAlgorithm, StopCriteria};
+use crate::logger::{Logger, VerbosityLevel};
+use crate::Gene;
+
+/// Default value for the max iterations.
+const MAX_ITERATIONS_DEFAULT: u32 = 100;
+/// Default value for the number of past best genes kept in the hall of fame of every population.
+const HALL_OF_FAME_SIZE_DEFAULT: usize = 10;
+
+/// The interaction between the genes of both populations. It returns the fitness of the first gene and of the second.
+pub type Interaction<A, B> = Box<dyn Fn(&A, &B) -> (f64, f64)>;
+
+/// How the genes of a population are evaluated against the other population.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoEvolutionMode {
+    /// Cooperative co-evolution. Every gene is evaluated together with the representative of the other population,
+    /// its best gene in the previous iteration, or a random gene of it in the first iteration.
+    Cooperative,
+    /// Competitive co-evolution. Every gene is evaluated against `opponents` genes sampled from the other population
+    /// and `hall_of_fame_opponents` sampled from its hall of fame, getting the mean fitness.
+    Competitive {
+        opponents: usize,
+        hall_of_fame_opponents: usize,
+    },
+}
+
+/// Wrapper of a gene whose fitness is assigned by the `CoEvolution` driver. `Gene::calculate_fitness` returns the
+/// assigned fitness, the crossover and the mutation are the ones of the wrapped gene.
+#[derive(Clone, Debug)]
+pub struct CoevolvedGene<T> {
+    /// The wrapped gene.
+    gene: T,
+    /// The fitness assigned from the interaction with the other population.
+    fitness: f64,
+}
+
+impl<T> CoevolvedGene<T> {
+    /// Returns the wrapped gene.
+    pub fn gene(&self) -> &T {
+        &self.gene
+    }
+
+    /// Returns the wrapped gene, consuming the wrapper.
+    pub fn into_gene(self) -> T {
+        self.gene
+    }
+}
+
+impl<T: Gene> Gene for CoevolvedGene<T> {
+    fn init() -> Self {
+        CoevolvedGene {
+            gene: T::init(),
+            fitness: 0.0,
+        }
+    }
+
+    fn calculate_fitness(&mut self) -> f64 {
+        self.fitness
+    }
+
+    fn crossover(&self, other: &Self) -> Self {
+        CoevolvedGene {
+            gene: self.gene.crossover(&other.gene),
+            fitness: 0.0,
+        }
+    }
+
+    fn mutate(&mut self) {
+        self.gene.mutate();
+    }
+
+    fn get_fitness(&self) -> f64 {
+        self.fitness
+    }
+
+    fn distance(&self, other: &Self) -> Option<f64> {
+        self.gene.distance(&other.gene)
+    }
+
+    fn mutation_rate(&self) -> Option<f32> {
+        self.gene.mutation_rate()
+    }
+
+    fn step_size(&self) -> Option<f64> {
+        self.gene.step_size()
+    }
+
+    fn self_adaptive_mutate(&mut self) {
+        self.gene.self_adaptive_mutate();
+    }
+
+    fn constraint_violation(&self) -> f64 {
+        self.gene.constraint_violation()
+    }
+
+    fn repair(&mut self) {
+        self.gene.repair();
+    }
+
+    fn is_valid(&self) -> bool {
+        self.gene.is_valid()
+    }
+
+    fn behaviour(&self) -> Option<Vec<f64>> {
+        self.gene.behaviour()
+    }
+}
+
+/// Co-evolution driver of two populations. Every iteration the genes of both populations are evaluated with the
+/// `Interaction` according to the `CoEvolutionMode`, and then both `GeneticAlgorithm` go to their next iteration.
+///
+/// # Notes
+///
+/// The driver is limited to two populations. A problem decomposed in more subcomponents has to group several of them
+/// in the gene of one population.
+pub struct CoEvolution<A: Gene + Clone, B: Gene + Clone> {
+    /// The algorithm evolving the first population.
+    population_a: GeneticAlgorithm<CoevolvedGene<A>>,
+    /// The algorithm evolving the second population.
+    population_b: GeneticAlgorithm<CoevolvedGene<B>>,
+    /// The interaction between the genes of both populations.
+    interaction: Interaction<A, B>,
+    /// How the genes are evaluated.
+    mode: CoEvolutionMode,
+    /// Num of the max iterations the driver will perform.
+    iterations: u32,
+    /// The current iteration.
+    current_iteration: u32,
+    /// Number of past best genes kept in the hall of fame of every population.
+    hall_of_fame_size: usize,
+    /// The past best genes of the first population, from the oldest to the newest.
+    hall_of_fame_a: Vec<A>,
+    /// The past best genes of the second population, from the oldest to the newest.
+    hall_of_fame_b: Vec<B>,
+    /// The best gene of the first population in the last evaluation.
+    representative_a: Option<A>,
+    /// The best gene of the second population in the last evaluation.
+    representative_b: Option<B>,
+    /// The best gene of the first population overall.
+    best_gene_a: Option<CoevolvedGene<A>>,
+    /// The best gene of the second population overall.
+    best_gene_b: Option<CoevolvedGene<B>>,
+    /// The fitness value of the first population to reach to end the driver.
+    fitness_goal: f64,
+    /// If the driver is running or not.
+    running: bool,
+    /// The stop reason if the driver has stopped.
+    stop_criteria: StopCriteria,
+    /// The logger of this execution.
+    logger: Logger,
+}
+
+impl<A: Gene + Clone, B: Gene + Clone> CoEvolution<A, B> {
+    /// Creates a new `CoEvolution` with default values.
+    /// * `mode` = CoEvolutionMode::Cooperative
+    /// * `iterations` = 100
+    /// * `hall_of_fame_size` = 10
+    /// * `fitness_goal` = f64::MAX
+    ///
+    /// # Arguments
+    ///
+    /// * `population_a` - The algorithm evolving the first population, configured with its builder.
+    /// * `population_b` - The algorithm evolving the second population, configured with its builder.
+    /// * `interaction` - The interaction between the genes of both populations.
+    ///
+    /// # Notes
+    ///
+    /// The iterations and the stop criteria of both algorithms are ignored, the driver stops them.
+    pub fn new(
+        population_a: GeneticAlgorithm<CoevolvedGene<A>>,
+        population_b: GeneticAlgorithm<CoevolvedGene<B>>,
+        interaction: Interaction<A, B>,
+    ) -> Self {
+        CoEvolution {
+            population_a,
+            population_b,
+            interaction,
+            mode: CoEvolutionMode::Cooperative,
+            iterations: MAX_ITERATIONS_DEFAULT,
+            current_iteration: 0,
+            hall_of_fame_size: HALL_OF_FAME_SIZE_DEFAULT,
+            hall_of_fame_a: Vec::new(),
+            hall_of_fame_b: Vec::new(),
+            representative_a: None,
+            representative_b: None,
+            best_gene_a: None,
+            best_gene_b: None,
+            fitness_goal: f64::MAX,
+            running: false,
+            stop_criteria: StopCriteria::Unknown,
+            logger: Logger::default(),
+        }
+    }
+
+    /// Initiate the driver and both algorithms.
+    ///
+    /// # Errors
+    ///
+    /// Returns the IO error if the logger destination of the driver or of any algorithm could not be opened.
+    pub fn init(mut self) -> Result<Self, Box<dyn Error>> {
+        self.logger.open()?;
+        self.population_a = self.population_a.init()?;
+        self.population_b = self.population_b.init()?;
+        self.start();
+        Ok(self)
+    }
+
+    /// Runs the driver by itself without user control.
+    ///
+    /// # Returns
+    ///
+    /// * `(best_genes, stop_criteria)` - The best gene of every population overall and the stop reason.
+    pub fn run(mut self) -> ((A, B), StopCriteria) {
+        if !self.is_running() {
+            self.start();
+        }
+
+        while self.running {
+            self.next_iteration();
+        }
+
+        let best_gene_a = self
+            .best_gene_a
+            .map_or_else(A::init, CoevolvedGene::into_gene);
+        let best_gene_b = self
+            .best_gene_b
+            .map_or_else(B::init, CoevolvedGene::into_gene);
+        ((best_gene_a, best_gene_b), self.stop_criteria)
+    }
+
+    /// Marks the driver as running and chooses the first representatives randomly.
+    fn start(&mut self) {
+        self.running = true;
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!("CoEvolution started with values:\n{}", self).as_str(),
+        );
+        let mut rng = rand::thread_rng();
+        self.representative_a = self
+            .population_a
+            .generation_mut()
+            .choose(&mut rng)
+            .map(|gene| gene.gene.clone());
+        self.representative_b = self
+            .population_b
+            .generation_mut()
+            .choose(&mut rng)
+            .map(|gene| gene.gene.clone());
+        self.logger
+            .log(VerbosityLevel::HIGH, "CoEvolution initiated properly.");
+    }
+
+    /// Goes iteration by iteration in case the user wants to have more control over the lifetime of the driver.
+    /// If the driver was not initiated, the first representatives are chosen first.
+    pub fn next_iteration(&mut self) {
+        if !self.running && self.current_iteration == 0 {
+            self.start();
+        }
+        let _generation_span = self.logger.enter_generation(self.current_iteration);
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!(
+                ">>>>>>> Started iteration {} <<<<<<<",
+                self.current_iteration
+            )
+            .as_str(),
+        );
+
+        self.evaluation_phase();
+        self.population_a.next_iteration();
+        self.population_b.next_iteration();
+
+        self.current_iteration += 1;
+        self.check_stop_criteria();
+    }
+
+    /// Assigns the fitness of every gene of both populations and updates the representatives, the hall of fame and
+    /// the best genes.
+    fn evaluation_phase(&mut self) {
+        let _phase_span = self.logger.enter_phase("evaluation");
+        let genes_a: Vec<A> = self
+            .population_a
+            .generation_mut()
+            .iter()
+            .map(|gene| gene.gene.clone())
+            .collect();
+        let genes_b: Vec<B> = self
+            .population_b
+            .generation_mut()
+            .iter()
+            .map(|gene| gene.gene.clone())
+            .collect();
+
+        let (fitnesses_a, fitnesses_b) = match self.mode {
+            CoEvolutionMode::Cooperative => {
+                let mut rng = rand::thread_rng();
+                let representative_a = self
+                    .representative_a
+                    .clone()
+                    .or_else(|| genes_a.choose(&mut rng).cloned());
+                let representative_b = self
+                    .representative_b
+                    .clone()
+                    .or_else(|| genes_b.choose(&mut rng).cloned());
+                let fitnesses_a = match &representative_b {
+                    Some(representative) => genes_a
+                        .iter()
+                        .map(|gene| (self.interaction)(gene, representative).0)
+                        .collect(),
+                    None => vec![0.0; genes_a.len()],
+                };
+                let fitnesses_b = match &representative_a {
+                    Some(representative) => genes_b
+                        .iter()
+                        .map(|gene| (self.interaction)(representative, gene).1)
+                        .collect(),
+                    None => vec![0.0; genes_b.len()],
+                };
+                (fitnesses_a, fitnesses_b)
+            }
+            CoEvolutionMode::Competitive {
+                opponents,
+                hall_of_fame_opponents,
+            } => {
+                let fitnesses_a = genes_a
+                    .iter()
+                    .map(|gene| {
+                        let opponents = sample(
+                            &genes_b,
+                            &self.hall_of_fame_b,
+                            opponents,
+                            hall_of_fame_opponents,
+                        );
+                        mean(opponents.map(|opponent| (self.interaction)(gene, opponent).0))
+                    })
+                    .collect();
+                let fitnesses_b = genes_b
+                    .iter()
+                    .map(|gene| {
+                        let opponents = sample(
+                            &genes_a,
+                            &self.hall_of_fame_a,
+                            opponents,
+                            hall_of_fame_opponents,
+                        );
+                        mean(opponents.map(|opponent| (self.interaction)(opponent, gene).1))
+                    })
+                    .collect();
+                (fitnesses_a, fitnesses_b)
+            }
+        };
+
+        let best_a = assign(self.population_a.generation_mut(), &fitnesses_a);
+        let best_b = assign(self.population_b.generation_mut(), &fitnesses_b);
+        if let Some(best) = best_a {
+            self.logger.log(
+                VerbosityLevel::MID,
+                format!("Best fitness of the first population = {}", best.fitness).as_str(),
+            );
+            self.representative_a = Some(best.gene.clone());
+            push_bounded(
+                &mut self.hall_of_fame_a,
+                best.gene.clone(),
+                self.hall_of_fame_size,
+            );
+            if self
+                .best_gene_a
+                .as_ref()
+                .map_or(true, |gene| best.fitness > gene.fitness)
+            {
+                self.best_gene_a = Some(best);
+            }
+        }
+        if let Some(best) = best_b {
+            self.logger.log(
+                VerbosityLevel::MID,
+                format!("Best fitness of the second population = {}", best.fitness).as_str(),
+            );
+            self.representative_b = Some(best.gene.clone());
+            push_bounded(
+                &mut self.hall_of_fame_b,
+                best.gene.clone(),
+                self.hall_of_fame_size,
+            );
+            if self
+                .best_gene_b
+                .as_ref()
+                .map_or(true, |gene| best.fitness > gene.fitness)
+            {
+                self.best_gene_b = Some(best);
+            }
+        }
+        if let Some(best) = &self.best_gene_a {
+            self.logger.log_best_fitness(best.fitness);
+        }
+    }
+
+    /// Checks if the driver should stop or not.
+    fn check_stop_criteria(&mut self) {
+        if self
+            .best_gene_a
+            .as_ref()
+            .is_some_and(|gene| gene.fitness >= self.fitness_goal)
+        {
+            self.running = false;
+            self.stop_criteria = StopCriteria::FitnessAchieved;
+        } else if self.current_iteration >= self.iterations {
+            self.running = false;
+            self.stop_criteria = StopCriteria::MaxIterations;
+        }
+
+        if !self.running {
+            self.logger.log(
+                VerbosityLevel::LOW,
+                format!("Algorithm must stop because of {:?}", self.stop_criteria).as_str(),
+            );
+        }
+    }
+
+    /// Sets how the genes are evaluated.
+    pub fn mode(mut self, mode: CoEvolutionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the max number of iterations the driver will perform.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        if iterations <= self.current_iteration {
+            panic!("Number of iterations is not greater  than the actual iteration.");
+        }
+
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the number of past best genes kept in the hall of fame of every population.
+    pub fn hall_of_fame_size(mut self, hall_of_fame_size: usize) -> Self {
+        self.hall_of_fame_size = hall_of_fame_size;
+        self
+    }
+
+    /// Sets the fitness of the first population to reach to stop the driver.
+    pub fn fitness_goal(mut self, fitness_goal: f64) -> Self {
+        self.fitness_goal = fitness_goal;
+        self
+    }
+
+    /// Sets the logger of this execution.
+    pub fn logger(mut self, logger: Logger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Returns the algorithm evolving the first population.
+    pub fn get_population_a(&self) -> &GeneticAlgorithm<CoevolvedGene<A>> {
+        &self.population_a
+    }
+
+    /// Returns the algorithm evolving the second population.
+    pub fn get_population_b(&self) -> &GeneticAlgorithm<CoevolvedGene<B>> {
+        &self.population_b
+    }
+
+    /// Returns how the genes are evaluated.
+    pub fn get_mode(&self) -> CoEvolutionMode {
+        self.mode
+    }
+
+    /// Returns the max iterations.
+    pub fn get_iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns the current iteration.
+    pub fn get_current_iteration(&self) -> u32 {
+        self.current_iteration
+    }
+
+    /// Returns the number of past best genes kept in the hall of fame of every population.
+    pub fn get_hall_of_fame_size(&self) -> usize {
+        self.hall_of_fame_size
+    }
+
+    /// Returns the hall of fame of the first population, from the oldest to the newest.
+    pub fn get_hall_of_fame_a(&self) -> &[A] {
+        &self.hall_of_fame_a
+    }
+
+    /// Returns the hall of fame of the second population, from the oldest to the newest.
+    pub fn get_hall_of_fame_b(&self) -> &[B] {
+        &self.hall_of_fame_b
+    }
+
+    /// Returns the representatives of both populations, their best genes in the last evaluation.
+    pub fn get_representatives(&self) -> (Option<&A>, Option<&B>) {
+        (
+            self.representative_a.as_ref(),
+            self.representative_b.as_ref(),
+        )
+    }
+
+    /// Returns the best gene of the first population overall with its fitness.
+    pub fn get_best_gene_a(&self) -> Option<&CoevolvedGene<A>> {
+        self.best_gene_a.as_ref()
+    }
+
+    /// Returns the best gene of the second population overall with its fitness.
+    pub fn get_best_gene_b(&self) -> Option<&CoevolvedGene<B>> {
+        self.best_gene_b.as_ref()
+    }
+
+    /// Returns the fitness goal.
+    pub fn get_fitness_goal(&self) -> f64 {
+        self.fitness_goal
+    }
+
+    /// Returns if the driver is running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns the stop reason.
+    pub fn get_stop_criteria(&self) -> StopCriteria {
+        self.stop_criteria
+    }
+
+    /// Returns the logger of this execution.
+    pub fn get_logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// Returns the logger of this execution as mutable, for example to flush it.
+    pub fn get_logger_mut(&mut self) -> &mut Logger {
+        &mut self.logger
+    }
+}
+
+/// Samples the opponents of a gene from the other population and its hall of fame, without repetitions.
+fn sample<'a, T>(
+    population: &'a [T],
+    hall_of_fame: &'a [T],
+    opponents: usize,
+    hall_of_fame_opponents: usize,
+) -> impl Iterator<Item = &'a T> {
+    let mut rng = rand::thread_rng();
+    let from_population =
+        index::sample(&mut rng, population.len(), opponents.min(population.len()));
+    let from_hall_of_fame = index::sample(
+        &mut rng,
+        hall_of_fame.len(),
+        hall_of_fame_opponents.min(hall_of_fame.len()),
+    );
+    from_population
+        .into_iter()
+        .map(move |i| &population[i])
+        .chain(from_hall_of_fame.into_iter().map(move |i| &hall_of_fame[i]))
+}
+
+/// Returns the mean of the values, or 0.0 if there are none.
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+/// Assigns the fitnesses to the genes and returns a copy of the best one.
+fn assign<T: Gene + Clone>(
+    generation: &mut [CoevolvedGene<T>],
+    fitnesses: &[f64],
+) -> Option<CoevolvedGene<T>> {
+    for (gene, fitness) in generation.iter_mut().zip(fitnesses) {
+        gene.fitness = *fitness;
+    }
+
+    generation
+        .iter()
+        .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+        .cloned()
+}
+
+/// Pushes a gene into a hall of fame, removing the oldest genes over `size`.
+fn push_bounded<T>(hall_of_fame: &mut Vec<T>, gene: T, size: usize) {
+    hall_of_fame.push(gene);
+    if hall_of_fame.len() > size {
+        let excess = hall_of_fame.len() - size;
+        hall_of_fame.drain(..excess);
+    }
+}
+
+/// Display trait implementation for CoEvolution.
+impl<A: Gene + Clone, B: Gene + Clone> fmt::Display for CoEvolution<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+             "{{\n\tmode: {:?},\n\titerations: {},\n\tcurrent_iteration: {},\n\thall_of_fame_size: {},\n\tfitness_goal: {:?},\n\tstop_criteria: {:?},\n\tpopulation_a: {},\n\tpopulation_b: {}\n}}",
+            self.mode,
+            self.iterations,
+            self.current_iteration,
+            self.hall_of_fame_size,
+            self.fitness_goal,
+            self.stop_criteria,
+            self.population_a,
+            self.population_b
+        )
+    }
+}
@@ -0,0 +1,177 @@
+//! This module contains the strategies to handle the constraints of the problems.
+//!
+//! All of them rely on `Gene::constraint_violation`. A gene is feasible when its violation is 0.0. Without a
+//! constraint violation every gene is feasible and the constraint handling has no effect.
+
+use rand::Rng;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// The penalty below the worst feasible fitness given to the infeasible genes by `ConstraintHandling::DeathPenalty`.
+/// It is finite so the mean fitness and the fitness-proportional selections keep working.
+pub const DEATH_PENALTY: f64 = 1.0e12;
+
+/// Constraint handling strategies supported by the `GeneticAlgorithm`. They only change the fitness used to select and
+/// compare the genes, the fitness of the genes is never modified.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConstraintHandling {
+    /// The constraints are ignored.
+    None,
+    /// The fitness is `fitness - coefficient * violation`.
+    StaticPenalty { coefficient: f64 },
+    /// The penalty grows with the iterations: `fitness - (coefficient * (iteration + 1))^exponent * violation`.
+    DynamicPenalty { coefficient: f64, exponent: f64 },
+    /// Deb's feasibility rules. A feasible gene is better than an infeasible one, two feasible genes are compared by
+    /// fitness and two infeasible genes by violation. With a tournament selection the genes are compared with
+    /// `ConstraintHandling::compare`, with any other selection the infeasible genes get a fitness lower than the worst
+    /// feasible one.
+    FeasibilityRules,
+    /// The infeasible genes are repaired with `Gene::repair` before being evaluated. The genes still infeasible are
+    /// compared with the feasibility rules.
+    Repair,
+    /// The infeasible genes get `DEATH_PENALTY` less than the worst feasible fitness, so they are almost never chosen
+    /// while there are feasible ones.
+    DeathPenalty,
+}
+
+impl ConstraintHandling {
+    /// Returns `true` if `violation` is the one of a feasible gene.
+    pub fn is_feasible(violation: f64) -> bool {
+        violation <= 0.0
+    }
+
+    /// Returns `true` if the genes are compared with Deb's feasibility rules.
+    pub fn uses_feasibility_rules(&self) -> bool {
+        matches!(
+            self,
+            ConstraintHandling::FeasibilityRules | ConstraintHandling::Repair
+        )
+    }
+
+    /// Compares two genes with Deb's feasibility rules: a feasible gene is better than an infeasible one, then the
+    /// lower violation is better and then the higher fitness.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The fitness and the constraint violation of the first gene.
+    /// * `b` - The fitness and the constraint violation of the second gene.
+    ///
+    /// # Returns
+    ///
+    /// * `ordering` as `Ordering` - `Ordering::Greater` if the first gene is better.
+    pub fn compare(a: (f64, f64), b: (f64, f64)) -> Ordering {
+        let (fitness_a, violation_a) = a;
+        let (fitness_b, violation_b) = b;
+        let violation_a = violation_a.max(0.0);
+        let violation_b = violation_b.max(0.0);
+        violation_b
+            .total_cmp(&violation_a)
+            .then(fitness_a.total_cmp(&fitness_b))
+    }
+
+    /// Selects a gene with a tournament compared with Deb's feasibility rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `fitnesses` - The fitness of every gene.
+    /// * `violations` - The constraint violation of every gene.
+    /// * `size` - The number of genes of the tournament.
+    ///
+    /// # Returns
+    ///
+    /// * `index` as `usize` - The index of the winner of the tournament.
+    ///
+    /// # Panics
+    ///
+    /// If `fitnesses` is empty or `violations` has a different length.
+    pub fn tournament(fitnesses: &[f64], violations: &[f64], size: usize) -> usize {
+        if fitnesses.is_empty() || fitnesses.len() != violations.len() {
+            panic!("Tournament needs the same number of fitnesses and violations");
+        }
+        let mut rng = rand::thread_rng();
+        let size = size.clamp(1, fitnesses.len());
+        let mut set: HashSet<usize> = HashSet::with_capacity(size);
+        while set.len() < size {
+            set.insert(rng.gen_range(0..fitnesses.len()));
+        }
+
+        set.into_iter()
+            .max_by(|a, b| {
+                Self::compare(
+                    (fitnesses[*a], violations[*a]),
+                    (fitnesses[*b], violations[*b]),
+                )
+            })
+            .unwrap()
+    }
+
+    /// Calculates the fitness used to select and compare the genes.
+    ///
+    /// # Arguments
+    ///
+    /// * `fitnesses` - The fitness of every gene.
+    /// * `violations` - The constraint violation of every gene.
+    /// * `iteration` - The current iteration.
+    ///
+    /// # Returns
+    ///
+    /// * `fitnesses` as `Vec<f64>` - The penalized fitness of every gene.
+    pub fn penalized_fitnesses(
+        &self,
+        fitnesses: &[f64],
+        violations: &[f64],
+        iteration: u32,
+    ) -> Vec<f64> {
+        let penalized = |penalty: &dyn Fn(f64, f64) -> f64| -> Vec<f64> {
+            fitnesses
+                .iter()
+                .zip(violations.iter())
+                .map(|(&fitness, &violation)| {
+                    if Self::is_feasible(violation) {
+                        fitness
+                    } else {
+                        penalty(fitness, violation)
+                    }
+                })
+                .collect()
+        };
+
+        match *self {
+            ConstraintHandling::None => fitnesses.to_vec(),
+            ConstraintHandling::StaticPenalty { coefficient } => {
+                penalized(&|fitness, violation| fitness - coefficient * violation)
+            }
+            ConstraintHandling::DynamicPenalty {
+                coefficient,
+                exponent,
+            } => {
+                let weight = (coefficient * (iteration as f64 + 1.0)).powf(exponent);
+                penalized(&|fitness, violation| fitness - weight * violation)
+            }
+            ConstraintHandling::FeasibilityRules | ConstraintHandling::Repair => {
+                // Every infeasible gene is worse than the worst feasible one.
+                let worst_feasible = Self::worst_feasible(fitnesses, violations);
+                penalized(&|_, violation| worst_feasible - violation.min(DEATH_PENALTY))
+            }
+            ConstraintHandling::DeathPenalty => {
+                let worst_feasible = Self::worst_feasible(fitnesses, violations);
+                penalized(&|_, _| worst_feasible - DEATH_PENALTY)
+            }
+        }
+    }
+
+    /// Returns the worst fitness of the feasible genes, or 0.0 if there are none.
+    fn worst_feasible(fitnesses: &[f64], violations: &[f64]) -> f64 {
+        let worst_feasible = fitnesses
+            .iter()
+            .zip(violations.iter())
+            .filter(|(_, &violation)| Self::is_feasible(violation))
+            .map(|(&fitness, _)| fitness)
+            .fold(f64::INFINITY, f64::min);
+        if worst_feasible.is_finite() {
+            worst_feasible
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,621 @@
+//! This module contains the Differential Evolution optimiser for `RealVectorGene`.
+//!
+//! It shares the conventions of the `GeneticAlgorithm`: it is configured with a builder, it stops with a
+//! `StopCriteria`, it logs through its own `Logger` and it saves the `GenerationStatistics` of every generation.
+
+#![allow(clippy::manual_range_contains)]
+
+use core::fmt;
+use rand::seq::index;
+use rand::Rng;
+use std::error::Error;
+
+use crate::genetic_algorithm::StopCriteria;
+use crate::genotypes::real_vector::standard_normal;
+use crate::genotypes::{RealVectorGene, RealVectorProblem};
+use crate::logger::{Logger, VerbosityLevel};
+use crate::statistics::GenerationStatistics;
+use crate::Gene;
+
+/// Default value for the population size.
+const POPULATION_SIZE_DEFAULT: usize = 50;
+/// Default value for the max generations aka iterations.
+const MAX_ITERATIONS_DEFAULT: u32 = 1000;
+/// Default value for the differential weight `F`.
+const WEIGHT_DEFAULT: f64 = 0.5;
+/// Default value for the crossover rate `CR`.
+const CROSSOVER_RATE_DEFAULT: f64 = 0.9;
+/// Minimum population size to choose the target and three different genes.
+const MIN_POPULATION_SIZE: usize = 4;
+/// Scale of the distributions sampling the parameters of every gene in JADE.
+const JADE_SCALE: f64 = 0.1;
+
+/// Strategies to create the mutant vector of every gene. All of them use binomial crossover with the target gene.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DifferentialStrategy {
+    /// DE/rand/1/bin: `x_r0 + F * (x_r1 - x_r2)`.
+    Rand1Bin,
+    /// DE/best/1/bin: `x_best + F * (x_r1 - x_r2)`.
+    Best1Bin,
+    /// DE/current-to-best/1/bin: `x_i + F * (x_best - x_i) + F * (x_r1 - x_r2)`. With `DifferentialAdaptation::Jade`
+    /// `x_best` is chosen randomly among the best `p` genes.
+    CurrentToBest1Bin,
+}
+
+/// Adaptation of the differential weight `F` and the crossover rate `CR`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DifferentialAdaptation {
+    /// `F` and `CR` are constant.
+    Constant,
+    /// JADE adaptation. Every gene samples its own `F` from a Cauchy distribution and `CR` from a normal distribution
+    /// whose locations are moved every generation towards the parameters of the successful trials.
+    ///
+    /// * `c` - The learning rate of the locations, usually 0.1.
+    /// * `p` - The fraction of best genes used by `DifferentialStrategy::CurrentToBest1Bin`, usually 0.05.
+    Jade { c: f64, p: f64 },
+}
+
+/// Differential Evolution optimiser for a `RealVectorProblem`. Higher fitness is better.
+pub struct DifferentialEvolution<P: RealVectorProblem> {
+    /// Size of the population.
+    population_size: usize,
+    /// Num of the max iterations the optimiser will perform.
+    iterations: u32,
+    /// The current iteration.
+    current_iteration: u32,
+    /// The current population.
+    population: Vec<RealVectorGene<P>>,
+    /// The differential weight `F`, or its initial location with JADE.
+    weight: f64,
+    /// The crossover rate `CR`, or its initial location with JADE.
+    crossover_rate: f64,
+    /// The strategy to create the mutant vectors.
+    strategy: DifferentialStrategy,
+    /// The adaptation of `F` and `CR`.
+    adaptation: DifferentialAdaptation,
+    /// The location of the distribution of `F` with JADE.
+    mean_weight: f64,
+    /// The location of the distribution of `CR` with JADE.
+    mean_crossover_rate: f64,
+    /// The fitness value to reach to end the optimiser.
+    fitness_goal: f64,
+    /// The mean pairwise distance under which the population is considered converged.
+    convergence_threshold: Option<f64>,
+    /// If the distance metrics of the diversity are saved in the statistics of every generation.
+    diversity_statistics: bool,
+    /// If the optimiser is running or not.
+    running: bool,
+    /// The best gene overall, `None` until the first population is evaluated.
+    best_gene: Option<RealVectorGene<P>>,
+    /// The stop reason if the optimiser has stopped.
+    stop_criteria: StopCriteria,
+    /// The logger of this execution.
+    logger: Logger,
+    /// Statistics of every generation.
+    statistics: Vec<GenerationStatistics>,
+}
+
+impl<P: RealVectorProblem> DifferentialEvolution<P> {
+    /// Creates a new `DifferentialEvolution` with default values.
+    /// * `population_size` = 50
+    /// * `iterations` = 1000
+    /// * `weight` = 0.5
+    /// * `crossover_rate` = 0.9
+    /// * `strategy` = DifferentialStrategy::Rand1Bin
+    /// * `adaptation` = DifferentialAdaptation::Constant
+    /// * `fitness_goal` = f64::MAX
+    pub fn new() -> Self {
+        DifferentialEvolution {
+            population_size: POPULATION_SIZE_DEFAULT,
+            iterations: MAX_ITERATIONS_DEFAULT,
+            current_iteration: 0,
+            population: (0..POPULATION_SIZE_DEFAULT)
+                .map(|_| RealVectorGene::init())
+                .collect(),
+            weight: WEIGHT_DEFAULT,
+            crossover_rate: CROSSOVER_RATE_DEFAULT,
+            strategy: DifferentialStrategy::Rand1Bin,
+            adaptation: DifferentialAdaptation::Constant,
+            mean_weight: WEIGHT_DEFAULT,
+            mean_crossover_rate: CROSSOVER_RATE_DEFAULT,
+            fitness_goal: f64::MAX,
+            convergence_threshold: None,
+            diversity_statistics: false,
+            running: false,
+            best_gene: None,
+            stop_criteria: StopCriteria::Unknown,
+            logger: Logger::default(),
+            statistics: Vec::new(),
+        }
+    }
+
+    /// Initiate the optimiser.
+    ///
+    /// # Errors
+    ///
+    /// Returns the IO error if the logger destination could not be opened.
+    pub fn init(mut self) -> Result<Self, Box<dyn Error>> {
+        self.logger.open()?;
+        self.start();
+        Ok(self)
+    }
+
+    /// Runs the optimiser by itself without user control.
+    ///
+    /// # Notes
+    ///
+    /// Logger IO errors do not stop the execution. Use `DifferentialEvolution::try_run` to stop on them.
+    pub fn run(mut self) -> (RealVectorGene<P>, StopCriteria) {
+        if !self.is_running() {
+            self.start();
+        }
+
+        while self.running {
+            self.next_iteration();
+        }
+
+        (
+            self.best_gene.unwrap_or_else(RealVectorGene::init),
+            self.stop_criteria,
+        )
+    }
+
+    /// Runs the optimiser by itself without user control, stopping if the logger fails to save.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first IO error produced by the logger.
+    pub fn try_run(mut self) -> Result<(RealVectorGene<P>, StopCriteria), Box<dyn Error>> {
+        if !self.is_running() {
+            self = self.init()?;
+        }
+
+        while self.running {
+            self.next_iteration();
+            if self.logger.get_error().is_some() {
+                self.logger.flush()?;
+            }
+        }
+
+        self.logger.flush()?;
+        Ok((
+            self.best_gene.unwrap_or_else(RealVectorGene::init),
+            self.stop_criteria,
+        ))
+    }
+
+    /// Marks the optimiser as running and evaluates the first population.
+    fn start(&mut self) {
+        self.running = true;
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!("DifferentialEvolution started with values:\n{}", self).as_str(),
+        );
+        self.mean_weight = self.weight;
+        self.mean_crossover_rate = self.crossover_rate;
+        for gene in self.population.iter_mut() {
+            gene.calculate_fitness();
+        }
+        self.best_gene = Some(self.best_of_population().clone());
+        self.logger.log(
+            VerbosityLevel::HIGH,
+            "DifferentialEvolution initiated properly.",
+        );
+    }
+
+    /// Goes iteration by iteration in case the user wants to have more control over the lifetime of the optimiser.
+    /// If the optimiser was not initiated, the first population is evaluated first.
+    ///
+    /// # Returns
+    ///
+    /// `self.population` - The new population.
+    pub fn next_iteration(&mut self) -> &Vec<RealVectorGene<P>> {
+        if !self.running && self.current_iteration == 0 {
+            self.start();
+        }
+        let _generation_span = self.logger.enter_generation(self.current_iteration);
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!(
+                ">>>>>>> Started iteration {} <<<<<<<",
+                self.current_iteration
+            )
+            .as_str(),
+        );
+
+        let mut rng = rand::thread_rng();
+        let best_indexes = self.best_indexes();
+        let mut successful_weights: Vec<f64> = Vec::new();
+        let mut successful_crossover_rates: Vec<f64> = Vec::new();
+        let mut new_population = Vec::with_capacity(self.population.len());
+        for i in 0..self.population.len() {
+            let (weight, crossover_rate) = self.sample_parameters(&mut rng);
+            let best = best_indexes[rng.gen_range(0..best_indexes.len())];
+            let mut trial = self.trial(i, best, weight, crossover_rate, &mut rng);
+            trial.calculate_fitness();
+
+            if trial.get_fitness() >= self.population[i].get_fitness() {
+                if trial.get_fitness() > self.population[i].get_fitness() {
+                    successful_weights.push(weight);
+                    successful_crossover_rates.push(crossover_rate);
+                }
+                new_population.push(trial);
+            } else {
+                new_population.push(self.population[i].clone());
+            }
+        }
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!("{} trials improved their target.", successful_weights.len()).as_str(),
+        );
+        self.population = new_population;
+        self.adapt_parameters(&successful_weights, &successful_crossover_rates);
+
+        let best_of_population = self.best_of_population().clone();
+        let best_gene = self
+            .best_gene
+            .get_or_insert_with(|| best_of_population.clone());
+        if best_of_population.get_fitness() > best_gene.get_fitness() {
+            *best_gene = best_of_population;
+        }
+        self.logger.log_best_fitness(best_gene.get_fitness());
+        self.statistics.push(GenerationStatistics::calculate(
+            self.current_iteration,
+            &self.population,
+            self.diversity_statistics || self.convergence_threshold.is_some(),
+        ));
+
+        self.current_iteration += 1;
+        self.check_stop_criteria();
+
+        &self.population
+    }
+
+    /// Returns the parameters `F` and `CR` of a trial.
+    fn sample_parameters<R: Rng>(&self, rng: &mut R) -> (f64, f64) {
+        match self.adaptation {
+            DifferentialAdaptation::Constant => (self.weight, self.crossover_rate),
+            DifferentialAdaptation::Jade { .. } => {
+                let crossover_rate =
+                    (self.mean_crossover_rate + JADE_SCALE * standard_normal(rng)).clamp(0.0, 1.0);
+                let mut weight = 0.0;
+                while weight <= 0.0 {
+                    let u: f64 = rng.gen_range(0.0..1.0);
+                    weight =
+                        self.mean_weight + JADE_SCALE * (std::f64::consts::PI * (u - 0.5)).tan();
+                }
+                (weight.min(1.0), crossover_rate)
+            }
+        }
+    }
+
+    /// Moves the locations of the JADE distributions towards the parameters of the successful trials.
+    fn adapt_parameters(&mut self, weights: &[f64], crossover_rates: &[f64]) {
+        let DifferentialAdaptation::Jade { c, .. } = self.adaptation else {
+            return;
+        };
+        if weights.is_empty() {
+            return;
+        }
+
+        let mean_crossover_rate =
+            crossover_rates.iter().sum::<f64>() / crossover_rates.len() as f64;
+        let lehmer_mean_weight =
+            weights.iter().map(|w| w * w).sum::<f64>() / weights.iter().sum::<f64>();
+        self.mean_crossover_rate = (1.0 - c) * self.mean_crossover_rate + c * mean_crossover_rate;
+        self.mean_weight = (1.0 - c) * self.mean_weight + c * lehmer_mean_weight;
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!(
+                "JADE locations: F = {}, CR = {}",
+                self.mean_weight, self.mean_crossover_rate
+            )
+            .as_str(),
+        );
+    }
+
+    /// Creates the trial gene of the target `i` with the mutant vector and binomial crossover.
+    fn trial<R: Rng>(
+        &self,
+        i: usize,
+        best: usize,
+        weight: f64,
+        crossover_rate: f64,
+        rng: &mut R,
+    ) -> RealVectorGene<P> {
+        let n = self.population.len();
+        // Three different genes, all of them different from the target.
+        let r: Vec<usize> = index::sample(rng, n - 1, 3)
+            .into_iter()
+            .map(|r| if r >= i { r + 1 } else { r })
+            .collect();
+        let value = |gene: usize, d: usize| self.population[gene].values()[d];
+
+        let dimensions = P::BOUNDS.len();
+        let forced = rng.gen_range(0..dimensions.max(1));
+        let values = (0..dimensions)
+            .map(|d| {
+                if d != forced && rng.gen_range(0.0..1.0) >= crossover_rate {
+                    return value(i, d);
+                }
+
+                let difference = weight * (value(r[1], d) - value(r[2], d));
+                match self.strategy {
+                    DifferentialStrategy::Rand1Bin => value(r[0], d) + difference,
+                    DifferentialStrategy::Best1Bin => value(best, d) + difference,
+                    DifferentialStrategy::CurrentToBest1Bin => {
+                        value(i, d) + weight * (value(best, d) - value(i, d)) + difference
+                    }
+                }
+            })
+            .collect();
+        RealVectorGene::from_values(values)
+    }
+
+    /// Returns the indexes of the genes that can be used as best gene in the mutant vectors.
+    fn best_indexes(&self) -> Vec<usize> {
+        let mut indexes: Vec<usize> = (0..self.population.len()).collect();
+        indexes.sort_by(|a, b| {
+            self.population[*b]
+                .get_fitness()
+                .total_cmp(&self.population[*a].get_fitness())
+        });
+        let count = match (self.strategy, self.adaptation) {
+            (DifferentialStrategy::CurrentToBest1Bin, DifferentialAdaptation::Jade { p, .. }) => {
+                ((p * indexes.len() as f64).round() as usize).clamp(1, indexes.len())
+            }
+            _ => 1,
+        };
+        indexes.truncate(count);
+        indexes
+    }
+
+    /// Returns the best gene of the current population.
+    fn best_of_population(&self) -> &RealVectorGene<P> {
+        self.population
+            .iter()
+            .max_by(|a, b| a.get_fitness().total_cmp(&b.get_fitness()))
+            .expect("Empty population")
+    }
+
+    /// Checks if the optimiser should stop or not.
+    fn check_stop_criteria(&mut self) {
+        let mean_pairwise_distance = self
+            .statistics
+            .last()
+            .and_then(|statistics| statistics.diversity.mean_pairwise_distance);
+
+        if self
+            .best_gene
+            .as_ref()
+            .is_some_and(|gene| gene.get_fitness() >= self.fitness_goal)
+        {
+            self.running = false;
+            self.stop_criteria = StopCriteria::FitnessAchieved;
+        } else if let (Some(threshold), Some(distance)) =
+            (self.convergence_threshold, mean_pairwise_distance)
+        {
+            if distance <= threshold {
+                self.running = false;
+                self.stop_criteria = StopCriteria::Converged;
+            }
+        }
+
+        if self.running && self.current_iteration >= self.iterations {
+            self.running = false;
+            self.stop_criteria = StopCriteria::MaxIterations;
+        }
+
+        if !self.running {
+            self.logger.log(
+                VerbosityLevel::LOW,
+                format!("Algorithm must stop because of {:?}", self.stop_criteria).as_str(),
+            );
+        }
+    }
+
+    /// Sets the population size.
+    ///
+    /// # Panics
+    ///
+    /// If the population size is lower than 4.
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        if population_size < MIN_POPULATION_SIZE {
+            panic!("Population size lower than {MIN_POPULATION_SIZE}");
+        }
+
+        self.population
+            .resize_with(population_size, RealVectorGene::init);
+        self.population_size = population_size;
+        self
+    }
+
+    /// Sets the max number of iterations the optimiser will perform.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        if iterations <= self.current_iteration {
+            panic!("Number of iterations is not greater  than the actual iteration.");
+        }
+
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the differential weight `F`. With JADE it is the initial location of its distribution.
+    pub fn weight(mut self, weight: f64) -> Self {
+        if weight <= 0.0 || weight > 2.0 {
+            panic!("Differential weight not in rage between 0.0 and 2.0");
+        }
+
+        self.weight = weight;
+        self
+    }
+
+    /// Sets the crossover rate `CR`. With JADE it is the initial location of its distribution.
+    pub fn crossover_rate(mut self, crossover_rate: f64) -> Self {
+        if crossover_rate > 1.0 || crossover_rate < 0.0 {
+            panic!("Crossover rate not in rage between 0.0 and 1.0");
+        }
+
+        self.crossover_rate = crossover_rate;
+        self
+    }
+
+    /// Sets the strategy to create the mutant vectors.
+    pub fn strategy(mut self, strategy: DifferentialStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the adaptation of `F` and `CR`.
+    pub fn adaptation(mut self, adaptation: DifferentialAdaptation) -> Self {
+        self.adaptation = adaptation;
+        self
+    }
+
+    /// Sets the fitness goal to stop the optimiser.
+    pub fn fitness_goal(mut self, fitness_goal: f64) -> Self {
+        self.fitness_goal = fitness_goal;
+        self
+    }
+
+    /// Sets the mean pairwise distance under which the population is considered converged, stopping the optimiser
+    /// with `StopCriteria::Converged`.
+    pub fn convergence_threshold(mut self, convergence_threshold: f64) -> Self {
+        self.convergence_threshold = Some(convergence_threshold);
+        self
+    }
+
+    /// Sets if the mean pairwise distance and the unique genomes are saved in the statistics of every generation.
+    ///
+    /// # Notes
+    ///
+    /// They need O(n²) calls to `Gene::distance` every generation, so they are only calculated by default when the
+    /// convergence threshold uses them.
+    pub fn diversity_statistics(mut self, diversity_statistics: bool) -> Self {
+        self.diversity_statistics = diversity_statistics;
+        self
+    }
+
+    /// Sets the logger of this execution.
+    pub fn logger(mut self, logger: Logger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Returns the population size.
+    pub fn get_population_size(&self) -> usize {
+        self.population_size
+    }
+
+    /// Returns the max iterations.
+    pub fn get_iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns the current iteration.
+    pub fn get_current_iteration(&self) -> u32 {
+        self.current_iteration
+    }
+
+    /// Returns the current population.
+    pub fn get_population(&self) -> &[RealVectorGene<P>] {
+        &self.population
+    }
+
+    /// Returns the differential weight `F`.
+    pub fn get_weight(&self) -> f64 {
+        self.weight
+    }
+
+    /// Returns the crossover rate `CR`.
+    pub fn get_crossover_rate(&self) -> f64 {
+        self.crossover_rate
+    }
+
+    /// Returns the current locations of the distributions of `F` and `CR`. Without JADE they are `F` and `CR`.
+    pub fn get_parameter_means(&self) -> (f64, f64) {
+        (self.mean_weight, self.mean_crossover_rate)
+    }
+
+    /// Returns the strategy.
+    pub fn get_strategy(&self) -> DifferentialStrategy {
+        self.strategy
+    }
+
+    /// Returns the adaptation of `F` and `CR`.
+    pub fn get_adaptation(&self) -> DifferentialAdaptation {
+        self.adaptation
+    }
+
+    /// Returns the fitness goal.
+    pub fn get_fitness_goal(&self) -> f64 {
+        self.fitness_goal
+    }
+
+    /// Returns the convergence threshold.
+    pub fn get_convergence_threshold(&self) -> Option<f64> {
+        self.convergence_threshold
+    }
+
+    /// Returns if the distance metrics of the diversity are saved in the statistics.
+    pub fn get_diversity_statistics(&self) -> bool {
+        self.diversity_statistics
+    }
+
+    /// Returns the best gene overall, `None` until the first population is evaluated.
+    pub fn get_best_gene(&self) -> Option<&RealVectorGene<P>> {
+        self.best_gene.as_ref()
+    }
+
+    /// Returns if the optimiser is running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns the stop reason.
+    pub fn get_stop_criteria(&self) -> StopCriteria {
+        self.stop_criteria
+    }
+
+    /// Returns the statistics of every generation.
+    pub fn get_statistics(&self) -> &[GenerationStatistics] {
+        &self.statistics
+    }
+
+    /// Returns the logger of this execution.
+    pub fn get_logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// Returns the logger of this execution as mutable, for example to flush it.
+    pub fn get_logger_mut(&mut self) -> &mut Logger {
+        &mut self.logger
+    }
+}
+
+/// Default trait implementation for DifferentialEvolution.
+impl<P: RealVectorProblem> Default for DifferentialEvolution<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Display trait implementation for DifferentialEvolution.
+impl<P: RealVectorProblem> fmt::Display for DifferentialEvolution<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+             "{{\n\tpopulation_size: {},\n\titerations: {},\n\tcurrent_iteration: {},\n\tweight: {},\n\tcrossover_rate: {},\n\tstrategy: {:?},\n\tadaptation: {:?},\n\tfitness_goal: {:?},\n\tstop_criteria: {:?},\n\tbest_gene_fitness: {:?}\n}}",
+            self.population_size,
+            self.iterations,
+            self.current_iteration,
+            self.weight,
+            self.crossover_rate,
+            self.strategy,
+            self.adaptation,
+            self.fitness_goal,
+            self.stop_criteria,
+            self.best_gene.as_ref().map(|gene| gene.get_fitness())
+        )
+    }
+}
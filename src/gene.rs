@@ -36,4 +36,102 @@ pub trait Gene {
     ///
     /// * `fitness_value` as `f64` - The fitness value of the gene.
     fn get_fitness(&self) -> f64;
+
+    /// Returns the distance between this gene and `other` in the genotype space. It is used to calculate the diversity
+    /// of a generation.
+    ///
+    /// By default the genes do not define a distance, so the distance based metrics are not calculated.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` as `Gene` - The other gene to measure the distance to.
+    ///
+    /// # Returns
+    ///
+    /// * `distance` as `Option<f64>` - The distance, being 0.0 for identical genomes, or `None` if it is not defined.
+    fn distance(&self, _other: &Self) -> Option<f64> {
+        None
+    }
+
+    /// Returns the mutation rate encoded in the gene. It is used with `MutationAdaptation::SelfAdaptive`.
+    ///
+    /// By default the genes do not encode a mutation rate, so the rate of the `GeneticAlgorithm` is used.
+    ///
+    /// # Returns
+    ///
+    /// * `mutation_rate` as `Option<f32>` - The probability of mutating this gene, or `None` if it is not defined.
+    fn mutation_rate(&self) -> Option<f32> {
+        None
+    }
+
+    /// Returns the mean step size encoded in the gene. It is used with `MutationAdaptation::SelfAdaptive`.
+    ///
+    /// By default the genes do not encode step sizes, so they are mutated with the mutation operator of the
+    /// `GeneticAlgorithm`.
+    ///
+    /// # Returns
+    ///
+    /// * `step_size` as `Option<f64>` - The mean of the step sizes encoded in the gene, or `None` if it is not defined.
+    fn step_size(&self) -> Option<f64> {
+        None
+    }
+
+    /// Mutates the gene with the step sizes encoded in it, adapting them first. It is used with
+    /// `MutationAdaptation::SelfAdaptive` for the genes that define `Gene::step_size`.
+    ///
+    /// By default it is the same as `Gene::mutate`.
+    fn self_adaptive_mutate(&mut self) {
+        self.mutate();
+    }
+
+    /// Returns how much the gene violates the constraints of the problem. It is used with `ConstraintHandling`.
+    ///
+    /// By default the genes do not define constraints, so every gene is feasible.
+    ///
+    /// # Returns
+    ///
+    /// * `violation` as `f64` - 0.0 if the gene is feasible, otherwise a positive value growing with the violation.
+    fn constraint_violation(&self) -> f64 {
+        0.0
+    }
+
+    /// Repairs the gene to make it feasible. It is used with `ConstraintHandling::Repair`.
+    ///
+    /// By default the genes are not repaired.
+    fn repair(&mut self) {}
+
+    /// Returns if the gene decodes to a solution. The invalid genes of every generation are counted in
+    /// `GenerationStatistics::invalid_genes`.
+    ///
+    /// By default every gene is valid.
+    fn is_valid(&self) -> bool {
+        true
+    }
+
+    /// Returns the behaviour descriptor of the gene. It is used by `NoveltySearch` and `MapElites`.
+    ///
+    /// By default the genes do not define a behaviour.
+    ///
+    /// # Returns
+    ///
+    /// * `behaviour` as `Option<Vec<f64>>` - The point of the gene in the behaviour space, or `None` if undefined.
+    fn behaviour(&self) -> Option<Vec<f64>> {
+        None
+    }
+
+    /// Returns a neighbour of the gene. It is used by the built-in `LocalSearch` implementations.
+    ///
+    /// By default the neighbour is a mutated copy of the gene.
+    ///
+    /// # Returns
+    ///
+    /// * `gene` as `Gene` - The neighbour, without its fitness calculated.
+    fn neighbour(&self) -> Self
+    where
+        Self: Clone,
+    {
+        let mut neighbour = self.clone();
+        neighbour.mutate();
+        neighbour
+    }
 }
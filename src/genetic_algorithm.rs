@@ -4,11 +4,21 @@
 #![allow(clippy::manual_range_contains)]
 
 use core::fmt;
+use rand::seq::SliceRandom;
 use rand::Rng;
 use std::error::Error;
 
-use crate::logger;
+use crate::adaptation::MutationAdaptation;
+use crate::constraints::ConstraintHandling;
+use crate::hall_of_fame::HallOfFame;
+use crate::local_search::{LocalSearch, LocalSearchScope, LocalSearchWriteBack};
+use crate::logger::{Logger, VerbosityLevel};
+use crate::niching::{self, Niching, Species};
+use crate::operators::{CrossoverOperator, GeneCrossover, GeneMutation, MutationOperator};
+use crate::quality_diversity::{self, NoveltySearch};
+use crate::restart::{Reinitialisation, Restart, RestartTrigger};
 use crate::selection::*;
+use crate::statistics::GenerationStatistics;
 use crate::Gene;
 
 /// Default value for our population size.
@@ -19,17 +29,22 @@ const MAX_ITERATIONS_DEFAULT: u32 = 1000;
 const MUTATION_RATE_DEFAULT: f32 = 0.05;
 /// Default value percentage of individuals to survive to the next generation.
 const SELECTION_RATE_DEFAULT: f32 = 0.90;
+/// Default value for crossover probability to cross over the selected parents.
+const CROSSOVER_RATE_DEFAULT: f32 = 1.0;
+/// Number of times a parent is selected again to avoid repeated parents in a crossover.
+const PARENT_SELECTION_ATTEMPTS: usize = 10;
 
 /// Reasons to stop the algorithm.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum StopCriteria {
     MaxIterations,
     FitnessAchieved,
+    Converged,
     Unknown,
 }
 
 /// Struct for our genetic algorithm handler.
-pub struct GeneticAlgorithm<T: Gene + Copy> {
+pub struct GeneticAlgorithm<T: Gene + Clone> {
     /// Size of the population, wich means the amount of `Gene`'s our generation can handle.
     population_size: usize,
     /// Num of the max iterations our algorithm will perform.
@@ -42,6 +57,13 @@ pub struct GeneticAlgorithm<T: Gene + Copy> {
     generation_historic: Vec<Vec<T>>,
     /// The mutation percentage.
     mutation_rate: f32,
+    /// The strategy to adapt the mutation rate.
+    mutation_adaptation: MutationAdaptation,
+    /// The mutation rate of the current generation.
+    current_mutation_rate: f32,
+    /// The index and the fitness before the mutation of the genes mutated in the last mutation phase, to count the
+    /// successful mutations.
+    mutated_genes: Vec<(usize, f64)>,
     /// The percentage of individuals to survive to the next generation.
     ///
     /// # Example
@@ -49,52 +71,140 @@ pub struct GeneticAlgorithm<T: Gene + Copy> {
     selection_rate: f32,
     /// The selection algorithm to perform the Selection::select.
     selection_algorithm: Box<dyn Selection>,
+    /// The probability to cross over the selected parents. Otherwise the children are copies of the parents.
+    crossover_rate: f32,
+    /// The crossover operator to generate the new genes.
+    crossover_operator: Box<dyn CrossoverOperator<T>>,
+    /// The mutation operator to mutate the genes.
+    mutation_operator: Box<dyn MutationOperator<T>>,
+    /// Creates the new genes instead of `Gene::init`.
+    gene_init: Option<Box<dyn Fn() -> T>>,
     /// The fitness value to reach to end the algorithm.
     fitness_goal: f64,
     /// If the algorithm is running or not
     running: bool,
-    /// The best gene overall.
-    best_gene: T,
+    /// The best evaluated gene overall, `None` before the first fitness calculation.
+    best_gene: Option<T>,
     /// The stop reason if the algorithm has stopped.
     stop_criteria: StopCriteria,
+    /// The logger of this execution.
+    logger: Logger,
+    /// Statistics of every evaluated generation.
+    statistics: Vec<GenerationStatistics>,
+    /// The mean pairwise distance under which the generation is considered converged.
+    convergence_threshold: Option<f64>,
+    /// If the distance metrics of the diversity are saved in the statistics of every generation.
+    diversity_statistics: bool,
+    /// The niching mode to keep several optima.
+    niching: Niching,
+    /// The best gene of every niche found.
+    niche_best_genes: Vec<T>,
+    /// The species of `Niching::Speciation`.
+    species: Vec<Species<T>>,
+    /// The identifier of the next species created.
+    next_species_id: usize,
+    /// The current compatibility threshold of `Niching::Speciation`.
+    species_threshold: f64,
+    /// The strategy to handle the constraints.
+    constraint_handling: ConstraintHandling,
+    /// The best feasible gene overall.
+    best_feasible_gene: Option<T>,
+    /// The best distinct genes overall.
+    hall_of_fame: Option<HallOfFame<T>>,
+    /// The local searcher that refines the offspring, making the algorithm memetic.
+    local_search: Option<Box<dyn LocalSearch<T>>>,
+    /// The offspring the local search is applied to.
+    local_search_scope: LocalSearchScope,
+    /// How the result of the local search is written back.
+    local_search_write_back: LocalSearchWriteBack,
+    /// The fitness found by the Baldwinian local search for the genes of the generation, by index.
+    learned_fitnesses: Vec<Option<f64>>,
+    /// If the genes of the generation were already repaired and evaluated before the fitness phase, by index.
+    evaluated_genes: Vec<bool>,
+    /// The novelty search mode, selecting the genes by novelty instead of fitness.
+    novelty_search: Option<NoveltySearch>,
+    /// The archive of novel behaviours.
+    novelty_archive: Vec<Vec<f64>>,
+    /// The novelty of the genes of the generation, by index.
+    novelty_scores: Vec<f64>,
+    /// The restart strategy to escape from stagnation.
+    restart: Option<Restart>,
+    /// The number of restarts performed.
+    restarts: u32,
+    /// The best fitness calculated, to detect the stagnation.
+    stagnation_best_fitness: f64,
+    /// The last iteration the best fitness improved or the algorithm restarted.
+    last_improvement: u32,
 }
 
-impl<T: Gene + Copy> GeneticAlgorithm<T> {
+impl<T: Gene + Clone> GeneticAlgorithm<T> {
     /// Creates a new `GeneticAlgorithm` with default values.
     /// * `population_size` = 100
     /// * `iterations` = 1000
     /// * `mutation_rate` = 0.05
+    /// * `mutation_adaptation` = MutationAdaptation::Constant
+    /// * `constraint_handling` = ConstraintHandling::None
+    /// * `local_search` = None
+    /// * `local_search_scope` = LocalSearchScope::All
+    /// * `local_search_write_back` = LocalSearchWriteBack::Lamarckian
+    /// * `novelty_search` = None
+    /// * `hall_of_fame` = None
+    /// * `restart` = None
     /// * `selection_rate` = 0.90
     /// * `selection_algorithm` = SelectionAlgorithms::Tournament(2)
+    /// * `crossover_rate` = 1.0
+    /// * `crossover_operator` = GeneCrossover
+    /// * `mutation_operator` = GeneMutation
+    /// * `gene_init` = None
     /// * `fitness_goal` = f64::MAX
     pub fn new() -> Self {
         let generation = (0..POPULATION_SIZE_DEFAULT).map(|_| T::init()).collect();
         let generation_historic = vec![Vec::clone(&generation)];
-        let return_value = GeneticAlgorithm {
+        GeneticAlgorithm {
             population_size: POPULATION_SIZE_DEFAULT,
             iterations: MAX_ITERATIONS_DEFAULT,
             current_iteration: 0,
             generation,
             generation_historic,
             mutation_rate: MUTATION_RATE_DEFAULT,
+            mutation_adaptation: MutationAdaptation::Constant,
+            current_mutation_rate: MUTATION_RATE_DEFAULT,
+            mutated_genes: Vec::new(),
             selection_rate: SELECTION_RATE_DEFAULT,
             selection_algorithm: Box::new(SelectionAlgorithms::Tournament(2)),
+            crossover_rate: CROSSOVER_RATE_DEFAULT,
+            crossover_operator: Box::new(GeneCrossover),
+            mutation_operator: Box::new(GeneMutation),
+            gene_init: None,
             fitness_goal: f64::MAX,
             running: false,
-            best_gene: T::init(),
+            best_gene: None,
             stop_criteria: StopCriteria::Unknown,
-        };
-
-        logger::LOG(
-            logger::VerbosityLevel::LOW,
-            format!(
-                "GeneticAlgorithm created with default values:\n{}",
-                return_value
-            )
-            .as_str(),
-        );
-
-        return_value
+            logger: Logger::default(),
+            statistics: Vec::new(),
+            convergence_threshold: None,
+            diversity_statistics: false,
+            niching: Niching::None,
+            niche_best_genes: Vec::new(),
+            species: Vec::new(),
+            next_species_id: 0,
+            species_threshold: 0.0,
+            constraint_handling: ConstraintHandling::None,
+            best_feasible_gene: None,
+            hall_of_fame: None,
+            local_search: None,
+            local_search_scope: LocalSearchScope::All,
+            local_search_write_back: LocalSearchWriteBack::Lamarckian,
+            learned_fitnesses: Vec::new(),
+            evaluated_genes: Vec::new(),
+            novelty_search: None,
+            novelty_archive: Vec::new(),
+            novelty_scores: Vec::new(),
+            restart: None,
+            restarts: 0,
+            stagnation_best_fitness: f64::MIN,
+            last_improvement: 0,
+        }
     }
 
     /// Creates a new `GeneticAlgorithm` with specific values.
@@ -125,54 +235,156 @@ impl<T: Gene + Copy> GeneticAlgorithm<T> {
 
         let generation = (0..population_size).map(|_| T::init()).collect();
         let generation_historic = vec![vec![]];
-        let return_value = GeneticAlgorithm {
+        GeneticAlgorithm {
             population_size,
             iterations,
             current_iteration: 0,
             generation,
             generation_historic,
             mutation_rate,
+            mutation_adaptation: MutationAdaptation::Constant,
+            current_mutation_rate: mutation_rate,
+            mutated_genes: Vec::new(),
             selection_rate,
             selection_algorithm,
+            crossover_rate: CROSSOVER_RATE_DEFAULT,
+            crossover_operator: Box::new(GeneCrossover),
+            mutation_operator: Box::new(GeneMutation),
+            gene_init: None,
             fitness_goal,
             running: false,
-            best_gene: T::init(),
+            best_gene: None,
             stop_criteria: StopCriteria::Unknown,
-        };
-
-        logger::LOG(
-            logger::VerbosityLevel::LOW,
-            format!("GeneticAlgorithm created with values:\n{}", return_value).as_str(),
-        );
-
-        return_value
+            logger: Logger::default(),
+            statistics: Vec::new(),
+            convergence_threshold: None,
+            diversity_statistics: false,
+            niching: Niching::None,
+            niche_best_genes: Vec::new(),
+            species: Vec::new(),
+            next_species_id: 0,
+            species_threshold: 0.0,
+            constraint_handling: ConstraintHandling::None,
+            best_feasible_gene: None,
+            hall_of_fame: None,
+            local_search: None,
+            local_search_scope: LocalSearchScope::All,
+            local_search_write_back: LocalSearchWriteBack::Lamarckian,
+            learned_fitnesses: Vec::new(),
+            evaluated_genes: Vec::new(),
+            novelty_search: None,
+            novelty_archive: Vec::new(),
+            novelty_scores: Vec::new(),
+            restart: None,
+            restarts: 0,
+            stagnation_best_fitness: f64::MIN,
+            last_improvement: 0,
+        }
     }
 
-    // TODO: Implement error handling when the algorithm will not be able to init.
-    /// Initiate the algorithm.    
+    /// Initiate the algorithm.
+    ///
+    /// # Errors
+    ///
+    /// Returns the IO error if the logger destination could not be opened.
     pub fn init(mut self) -> Result<Self, Box<dyn Error>> {
-        self.running = true;
-        self.save_generation();
-        logger::LOG(
-            logger::VerbosityLevel::HIGH,
-            "Algorithm initiated properlly.",
-        );
+        self.logger.open()?;
+        self.start();
         Ok(self)
     }
 
     /// Runs the algorithm by itself without user control.
+    ///
+    /// # Notes
+    ///
+    /// Logger IO errors do not stop the execution. Use `GeneticAlgorithm::try_run` to stop on them.
     pub fn run(mut self) -> (T, StopCriteria) {
         if !self.is_running() {
-            self = self.init().unwrap();
+            self.start();
         }
 
-        logger::LOG(logger::VerbosityLevel::HIGH, "Algorithm run started.");
+        self.logger
+            .log(VerbosityLevel::HIGH, "Algorithm run started.");
 
         while self.running {
             self.next_iteration();
         }
 
-        (self.best_gene, self.stop_criteria)
+        (self.best_gene.unwrap_or_else(T::init), self.stop_criteria)
+    }
+
+    /// Runs the algorithm by itself without user control and returns the best gene of every niche.
+    ///
+    /// # Returns
+    ///
+    /// (`niche_best_genes`, `stop_criteria`) - The best genes sorted from the best to the worst and the stop reason.
+    pub fn run_niches(mut self) -> (Vec<T>, StopCriteria) {
+        if !self.is_running() {
+            self.start();
+        }
+
+        while self.running {
+            self.next_iteration();
+        }
+
+        (self.get_niche_best_genes(), self.stop_criteria)
+    }
+
+    /// Runs the algorithm by itself without user control and returns the genes of the hall of fame.
+    ///
+    /// # Returns
+    ///
+    /// (`hall_of_fame`, `stop_criteria`) - The best distinct genes sorted from the best to the worst and the stop
+    /// reason. Only the best gene if there is no hall of fame.
+    pub fn run_hall_of_fame(mut self) -> (Vec<T>, StopCriteria) {
+        if !self.is_running() {
+            self.start();
+        }
+
+        while self.running {
+            self.next_iteration();
+        }
+
+        match self.hall_of_fame {
+            Some(hall_of_fame) => (hall_of_fame.get_genes().to_vec(), self.stop_criteria),
+            None => (self.best_gene.into_iter().collect(), self.stop_criteria),
+        }
+    }
+
+    /// Runs the algorithm by itself without user control, stopping if the logger fails to save.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first IO error produced by the logger.
+    pub fn try_run(mut self) -> Result<(T, StopCriteria), Box<dyn Error>> {
+        if !self.is_running() {
+            self = self.init()?;
+        }
+
+        self.logger
+            .log(VerbosityLevel::HIGH, "Algorithm run started.");
+
+        while self.running {
+            self.next_iteration();
+            if self.logger.get_error().is_some() {
+                self.logger.flush()?;
+            }
+        }
+
+        self.logger.flush()?;
+        Ok((self.best_gene.unwrap_or_else(T::init), self.stop_criteria))
+    }
+
+    /// Marks the algorithm as running and saves the first generation.
+    fn start(&mut self) {
+        self.running = true;
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!("GeneticAlgorithm started with values:\n{}", self).as_str(),
+        );
+        self.save_generation();
+        self.logger
+            .log(VerbosityLevel::HIGH, "Algorithm initiated properlly.");
     }
 
     /// Goes iteration by iteration in case the user wants to have more control over the lifetime of the algorithm.
@@ -181,8 +393,9 @@ impl<T: Gene + Copy> GeneticAlgorithm<T> {
     ///
     /// `self.generation` - The new generation.
     pub fn next_iteration(&mut self) -> &Vec<T> {
-        logger::LOG(
-            logger::VerbosityLevel::LOW,
+        let _generation_span = self.logger.enter_generation(self.current_iteration);
+        self.logger.log(
+            VerbosityLevel::LOW,
             format!(
                 ">>>>>>> Started iteration {} <<<<<<<",
                 self.current_iteration
@@ -190,127 +403,946 @@ impl<T: Gene + Copy> GeneticAlgorithm<T> {
             .as_str(),
         );
 
-        logger::LOG(
-            logger::VerbosityLevel::HIGH,
-            ">> Fitness calculation phase.",
-        );
-        // Calculate fitness.
+        self.fitness_phase();
+        let new_generation = match self.niching {
+            Niching::DeterministicCrowding { .. } => {
+                let mut new_generation = self.crowding_phase();
+                self.local_search_phase(&mut new_generation, 0);
+                new_generation
+            }
+            Niching::Speciation { .. } => {
+                let (mut new_generation, num_survivors) = self.speciation_phase();
+                self.mutation_phase(&mut new_generation);
+                self.local_search_phase(&mut new_generation, num_survivors);
+                new_generation
+            }
+            _ => {
+                let mut new_generation = self.selection_phase();
+                let num_survivors = new_generation.len();
+                self.crossover_phase(&mut new_generation);
+                self.mutation_phase(&mut new_generation);
+                self.local_search_phase(&mut new_generation, num_survivors);
+                new_generation
+            }
+        };
+
+        // Save generation_historic
+        self.generation = new_generation;
+        self.save_generation();
+
+        self.current_iteration += 1;
+
+        // Check stop criteria
+        self.check_stop_criteria();
+
+        &self.generation
+    }
+
+    /// Calculates the fitness of every gene in the current generation, except the genes already evaluated by the
+    /// crowding or the local search phase.
+    fn fitness_phase(&mut self) {
+        let _phase_span = self.logger.enter_phase("fitness");
+        self.logger
+            .log(VerbosityLevel::HIGH, ">> Fitness calculation phase.");
+        let repair = self.constraint_handling == ConstraintHandling::Repair;
+        let evaluated_genes = std::mem::take(&mut self.evaluated_genes);
         for (i, gene) in self.generation.iter_mut().enumerate() {
-            gene.calculate_fitness();
-            logger::LOG(
-                logger::VerbosityLevel::MID,
+            if !evaluated_genes.get(i).copied().unwrap_or(false) {
+                if repair && !ConstraintHandling::is_feasible(gene.constraint_violation()) {
+                    gene.repair();
+                }
+                gene.calculate_fitness();
+            }
+            self.logger.log(
+                VerbosityLevel::MID,
                 format!("Gene {i} = {:?}", gene.get_fitness()).as_str(),
             );
         }
 
-        logger::LOG(logger::VerbosityLevel::HIGH, ">> Selection phase.");
-        // Selection.
+        let success_ratio = self.mutation_success_ratio();
+        let mut statistics = GenerationStatistics::calculate(
+            self.current_iteration,
+            &self.generation,
+            self.distances_needed(),
+        );
+        self.current_mutation_rate = self.mutation_adaptation.next_rate(
+            self.mutation_rate,
+            self.current_mutation_rate,
+            self.current_iteration,
+            self.iterations,
+            Some(&statistics),
+            success_ratio,
+        );
+        statistics.mutation_rate = match self.mutation_adaptation {
+            MutationAdaptation::SelfAdaptive if !self.generation.is_empty() => {
+                self.generation
+                    .iter()
+                    .map(|gene| self.gene_mutation_rate(gene))
+                    .sum::<f32>()
+                    / self.generation.len() as f32
+            }
+            _ => self.current_mutation_rate,
+        };
+        if self.mutation_adaptation == MutationAdaptation::SelfAdaptive {
+            statistics.step_size = self
+                .generation
+                .iter()
+                .map(|gene| gene.step_size())
+                .sum::<Option<f64>>()
+                .map(|total| total / self.generation.len().max(1) as f64);
+        }
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!(
+                "Diversity = {:?}, mutation rate = {}, invalid genes = {}",
+                statistics.diversity, statistics.mutation_rate, statistics.invalid_genes
+            )
+            .as_str(),
+        );
+        self.statistics.push(statistics);
+        self.update_best_genes();
+
+        if let Some(hall_of_fame) = self.hall_of_fame.as_mut() {
+            let added = hall_of_fame.update(&self.generation);
+            self.logger.log(
+                VerbosityLevel::MID,
+                format!("{} genes added to the hall of fame.", added).as_str(),
+            );
+        }
+
+        self.restart_phase();
+
+        if let Some(novelty_search) = self.novelty_search {
+            let behaviours: Vec<Vec<f64>> = self
+                .generation
+                .iter()
+                .map(|gene| gene.behaviour().unwrap_or_default())
+                .collect();
+            self.novelty_scores = quality_diversity::novelty_scores(
+                &behaviours,
+                &self.novelty_archive,
+                novelty_search.k,
+            );
+            for (behaviour, novelty) in behaviours.into_iter().zip(self.novelty_scores.iter()) {
+                if *novelty > novelty_search.archive_threshold && !behaviour.is_empty() {
+                    self.novelty_archive.push(behaviour);
+                }
+            }
+            self.logger.log(
+                VerbosityLevel::MID,
+                format!("Novelty archive size = {}", self.novelty_archive.len()).as_str(),
+            );
+        }
+    }
+
+    /// Re-initialises the generation if the restart strategy is triggered, keeping the best genes and growing the
+    /// population size. The new genes are evaluated and the last statistics are marked as restarted.
+    fn restart_phase(&mut self) {
+        let Some(restart) = self.restart else {
+            return;
+        };
+        let best_fitness = self
+            .statistics
+            .last()
+            .map_or(f64::MIN, |statistics| statistics.best_fitness);
+        if best_fitness > self.stagnation_best_fitness {
+            self.stagnation_best_fitness = best_fitness;
+            self.last_improvement = self.current_iteration;
+        }
+        if restart
+            .max_restarts
+            .is_some_and(|max_restarts| self.restarts >= max_restarts)
+        {
+            return;
+        }
+        let triggered = match restart.trigger {
+            RestartTrigger::Stagnation { iterations } => {
+                self.current_iteration - self.last_improvement >= iterations
+            }
+            RestartTrigger::LowDiversity { threshold } => self
+                .statistics
+                .last()
+                .and_then(|statistics| statistics.diversity.mean_pairwise_distance)
+                .is_some_and(|distance| distance < threshold),
+        };
+        if !triggered {
+            return;
+        }
+
+        let _phase_span = self.logger.enter_phase("restart");
+        self.restarts += 1;
+        self.last_improvement = self.current_iteration;
+        self.population_size =
+            (self.population_size as f64 * restart.population_growth).round() as usize;
+        let kept = restart.kept(self.generation.len());
+        let mut generation = std::mem::take(&mut self.generation);
+        generation.sort_by(|a, b| b.get_fitness().total_cmp(&a.get_fitness()));
+        generation.truncate(kept);
+        let repair = self.constraint_handling == ConstraintHandling::Repair;
+        while generation.len() < self.population_size {
+            let mut gene = self.new_gene();
+            if repair && !ConstraintHandling::is_feasible(gene.constraint_violation()) {
+                gene.repair();
+            }
+            gene.calculate_fitness();
+            generation.push(gene);
+        }
+        self.generation = generation;
+        self.learned_fitnesses.clear();
+        self.evaluated_genes.clear();
+        self.novelty_scores.clear();
+        if let Some(statistics) = self.statistics.last_mut() {
+            statistics.restarted = true;
+        }
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!(
+                "Restart {} triggered by {:?}: {} genes kept, population size = {}",
+                self.restarts, restart.trigger, kept, self.population_size
+            )
+            .as_str(),
+        );
+    }
+
+    /// Returns if the distance metrics of the diversity are calculated, because they are requested or needed to
+    /// stop, adapt the mutation rate or restart.
+    fn distances_needed(&self) -> bool {
+        self.diversity_statistics
+            || self.convergence_threshold.is_some()
+            || matches!(
+                self.mutation_adaptation,
+                MutationAdaptation::Hypermutation { .. }
+            )
+            || self.restart.is_some_and(|restart| {
+                matches!(restart.trigger, RestartTrigger::LowDiversity { .. })
+            })
+    }
+
+    /// Returns the ratio of the genes mutated in the last mutation phase whose fitness improved, or `None` if they
+    /// were not counted.
+    fn mutation_success_ratio(&mut self) -> Option<f64> {
+        if self.mutated_genes.is_empty() {
+            return None;
+        }
+
+        let successes = self
+            .mutated_genes
+            .iter()
+            .filter(|(idx, fitness)| {
+                self.generation
+                    .get(*idx)
+                    .is_some_and(|gene| gene.get_fitness() > *fitness)
+            })
+            .count();
+        let ratio = successes as f64 / self.mutated_genes.len() as f64;
+        self.mutated_genes.clear();
+        Some(ratio)
+    }
+
+    /// Returns the probability of mutating `gene`.
+    fn gene_mutation_rate(&self, gene: &T) -> f32 {
+        match self.mutation_adaptation {
+            MutationAdaptation::SelfAdaptive => {
+                gene.mutation_rate().unwrap_or(self.current_mutation_rate)
+            }
+            _ => self.current_mutation_rate,
+        }
+    }
+
+    /// Mutates `gene` with its encoded step sizes under `MutationAdaptation::SelfAdaptive`, or with the mutation
+    /// operator otherwise.
+    fn mutate_gene(&self, gene: &mut T) {
+        if self.mutation_adaptation == MutationAdaptation::SelfAdaptive
+            && gene.step_size().is_some()
+        {
+            gene.self_adaptive_mutate();
+        } else {
+            self.mutation_operator.mutate(gene);
+        }
+    }
+
+    /// Selects the genes of the current generation that survive to the next one.
+    ///
+    /// # Returns
+    ///
+    /// `new_generation` - The survivors.
+    fn selection_phase(&mut self) -> Vec<T> {
+        let _phase_span = self.logger.enter_phase("selection");
+        self.logger.log(VerbosityLevel::HIGH, ">> Selection phase.");
         let mut new_generation: Vec<T> = Vec::with_capacity(self.population_size);
         let num_survivors: usize = (self.generation.len() as f32 * self.selection_rate) as usize;
-        let mut new_genes_num: usize = 0;
 
-        logger::LOG(
-            logger::VerbosityLevel::MID,
+        self.logger.log(
+            VerbosityLevel::MID,
             format!("Number of survivors = {:?}", num_survivors).as_str(),
         );
-        while new_genes_num < num_survivors {
-            let gene_idx: usize = self.selection_algorithm.select(
-                &self
-                    .generation
-                    .iter()
-                    .map(|gene| gene.get_fitness())
-                    .collect(),
-            );
-            new_generation.push(self.generation[gene_idx]);
-            self.generation.remove(gene_idx);
-            new_genes_num += 1;
+        let fitnesses = self.selection_fitnesses(&self.generation);
+        let fitnesses: Vec<f64> = match self.niching {
+            Niching::FitnessSharing { sigma, alpha } => niching::share(
+                &fitnesses,
+                &niching::niche_counts(&self.generation, sigma, alpha),
+            ),
+            _ => fitnesses,
+        };
+        let mut fitnesses = self.penalized_fitnesses(&self.generation, &fitnesses);
+        let mut violations = self.tournament_violations(&self.generation);
+        let mut learned_fitnesses = std::mem::take(&mut self.learned_fitnesses);
+        let track_learned = !learned_fitnesses.is_empty();
+        learned_fitnesses.resize(self.generation.len(), None);
+        let mut novelty_scores = std::mem::take(&mut self.novelty_scores);
+        let track_novelty = !novelty_scores.is_empty();
+        novelty_scores.resize(self.generation.len(), 0.0);
+        while new_generation.len() < num_survivors {
+            let gene_idx: usize = self.select(&fitnesses, violations.as_deref());
+            new_generation.push(self.generation.remove(gene_idx));
+            fitnesses.remove(gene_idx);
+            if let Some(violations) = violations.as_mut() {
+                violations.remove(gene_idx);
+            }
+            if track_learned {
+                self.learned_fitnesses
+                    .push(learned_fitnesses.remove(gene_idx));
+            }
+            if track_novelty {
+                self.novelty_scores.push(novelty_scores.remove(gene_idx));
+            }
         }
 
-        logger::LOG(logger::VerbosityLevel::HIGH, ">> Crossover phase.");
-        // Crossover
+        new_generation
+    }
+
+    /// Replaces the generation with deterministic crowding. The parents are paired randomly, every pair generates two
+    /// children and each child competes against its closest parent.
+    ///
+    /// # Returns
+    ///
+    /// `new_generation` - The winners of every competition.
+    fn crowding_phase(&mut self) -> Vec<T> {
+        let _phase_span = self.logger.enter_phase("crowding");
+        self.logger
+            .log(VerbosityLevel::HIGH, ">> Deterministic crowding phase.");
         let mut rng = rand::thread_rng();
-        while new_genes_num < self.population_size {
-            let gen1_idx = rng.gen_range(0..new_generation.len());
-            let mut gen2_idx = gen1_idx;
-            while gen2_idx == gen1_idx {
-                gen2_idx = rng.gen_range(0..new_generation.len());
-            }
-            let gen1 = new_generation[gen1_idx];
-            let crossover_gen = gen1.crossover(&new_generation[gen2_idx]);
-            new_generation.push(crossover_gen);
-            new_genes_num += 1;
-            logger::LOG(
-                logger::VerbosityLevel::MID,
-                format!(
-                    "Crossover between gen1({:?}) and gen2({:?})",
-                    gen1.get_fitness(),
-                    new_generation[gen2_idx].get_fitness()
-                )
-                .as_str(),
-            );
+        let learned_fitnesses = std::mem::take(&mut self.learned_fitnesses);
+        let mut parents: Vec<(T, Option<f64>)> = std::mem::take(&mut self.generation)
+            .into_iter()
+            .enumerate()
+            .map(|(i, gene)| (gene, learned_fitnesses.get(i).copied().flatten()))
+            .collect();
+        parents.shuffle(&mut rng);
+
+        let mut new_generation: Vec<T> = Vec::with_capacity(parents.len());
+        let mut replacements = 0;
+        for pair in parents.chunks(2) {
+            let [(parent1, learned1), (parent2, learned2)] = match pair {
+                [parent1, parent2] => [parent1.clone(), parent2.clone()],
+                _ => {
+                    for (parent, learned) in pair {
+                        new_generation.push(parent.clone());
+                        self.learned_fitnesses.push(*learned);
+                    }
+                    continue;
+                }
+            };
+
+            let mut children = if rng.gen_range(0.0..1.0) < self.crossover_rate {
+                [
+                    self.crossover_operator.crossover(&parent1, &parent2),
+                    self.crossover_operator.crossover(&parent2, &parent1),
+                ]
+            } else {
+                [parent1.clone(), parent2.clone()]
+            };
+            for child in children.iter_mut() {
+                if rng.gen_range(0.0..1.0) < self.gene_mutation_rate(child) {
+                    self.mutate_gene(child);
+                }
+                if self.constraint_handling == ConstraintHandling::Repair
+                    && !ConstraintHandling::is_feasible(child.constraint_violation())
+                {
+                    child.repair();
+                }
+                child.calculate_fitness();
+            }
+
+            let distance = |a: &T, b: &T| a.distance(b).unwrap_or(0.0);
+            if distance(&parent1, &children[0]) + distance(&parent2, &children[1])
+                > distance(&parent1, &children[1]) + distance(&parent2, &children[0])
+            {
+                children.swap(0, 1);
+            }
+
+            for ((parent, learned), child) in [(parent1, learned1), (parent2, learned2)]
+                .into_iter()
+                .zip(children)
+            {
+                let fitnesses = self.penalized_fitnesses(
+                    [&child, &parent],
+                    &[child.get_fitness(), learned.unwrap_or(parent.get_fitness())],
+                );
+                let child_wins = if self.constraint_handling.uses_feasibility_rules() {
+                    ConstraintHandling::compare(
+                        (fitnesses[0], child.constraint_violation()),
+                        (fitnesses[1], parent.constraint_violation()),
+                    )
+                    .is_gt()
+                } else {
+                    fitnesses[0] > fitnesses[1]
+                };
+                if child_wins {
+                    new_generation.push(child);
+                    self.learned_fitnesses.push(None);
+                    replacements += 1;
+                } else {
+                    new_generation.push(parent);
+                    self.learned_fitnesses.push(learned);
+                }
+            }
         }
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!("{} parents replaced by their children.", replacements).as_str(),
+        );
 
-        logger::LOG(logger::VerbosityLevel::HIGH, ">> Mutation phase.");
-        // Mutation
-        let mut num_of_mutations = 0;
-        for gen in new_generation.iter_mut() {
-            if rng.gen_range(0.0..1.0) < self.mutation_rate {
-                gen.mutate();
-                num_of_mutations += 1;
+        // Both the children and the parents were evaluated to compare them.
+        self.evaluated_genes = vec![true; new_generation.len()];
+        new_generation
+    }
+
+    /// Replaces the generation with NEAT-style speciation. The genes are grouped in species, the stagnant species are
+    /// culled and every species produces a number of genes proportional to its shared fitness: the survivors chosen
+    /// with the selection algorithm according to the selection rate and the children of its members.
+    ///
+    /// # Returns
+    ///
+    /// `(new_generation, num_survivors)` - The new generation, with the survivors of every species first.
+    fn speciation_phase(&mut self) -> (Vec<T>, usize) {
+        let _phase_span = self.logger.enter_phase("speciation");
+        self.logger
+            .log(VerbosityLevel::HIGH, ">> Speciation phase.");
+        let (target_species, threshold_step, stagnation) = match self.niching {
+            Niching::Speciation {
+                target_species,
+                threshold_step,
+                stagnation,
+                ..
+            } => (target_species, threshold_step, stagnation),
+            _ => return (std::mem::take(&mut self.generation), 0),
+        };
+        let mut rng = rand::thread_rng();
+        let fitnesses = self.selection_fitnesses(&self.generation);
+        let fitnesses = self.penalized_fitnesses(&self.generation, &fitnesses);
+        let violations = self.tournament_violations(&self.generation);
+
+        // Every gene joins the first species whose representative is compatible.
+        let mut species = std::mem::take(&mut self.species);
+        for s in species.iter_mut() {
+            s.members.clear();
+        }
+        for (i, gene) in self.generation.iter().enumerate() {
+            let compatible = species
+                .iter_mut()
+                .find(|s| gene.distance(&s.representative).unwrap_or(0.0) < self.species_threshold);
+            match compatible {
+                Some(s) => s.members.push(i),
+                None => {
+                    species.push(Species::new(
+                        self.next_species_id,
+                        gene.clone(),
+                        i,
+                        self.current_iteration,
+                    ));
+                    self.next_species_id += 1;
+                }
             }
         }
-        logger::LOG(
-            logger::VerbosityLevel::MID,
-            format!("{} mutations performed.", num_of_mutations).as_str(),
+        species.retain(|s| !s.members.is_empty());
+
+        let best_idx = (0..fitnesses.len())
+            .max_by(|a, b| fitnesses[*a].total_cmp(&fitnesses[*b]))
+            .unwrap_or(0);
+        for s in species.iter_mut() {
+            let best_fitness = s
+                .members
+                .iter()
+                .map(|i| fitnesses[*i])
+                .fold(f64::MIN, f64::max);
+            if best_fitness > s.best_fitness {
+                s.best_fitness = best_fitness;
+                s.last_improvement = self.current_iteration;
+            }
+            let representative = s.members[rng.gen_range(0..s.members.len())];
+            s.representative = self.generation[representative].clone();
+        }
+
+        // The stagnant species are culled, except the one with the best gene.
+        let min_fitness = fitnesses.iter().copied().fold(f64::INFINITY, f64::min);
+        let shared_fitnesses: Vec<f64> = species
+            .iter()
+            .map(|s| {
+                let stagnant = stagnation > 0
+                    && self.current_iteration - s.last_improvement >= stagnation
+                    && !s.members.contains(&best_idx);
+                if stagnant {
+                    0.0
+                } else {
+                    // Adjusted fitness shifted to be positive, plus a small amount so no species is culled by it.
+                    s.members
+                        .iter()
+                        .map(|i| fitnesses[*i] - min_fitness)
+                        .sum::<f64>()
+                        / s.members.len() as f64
+                        + f64::EPSILON
+                }
+            })
+            .collect();
+        let offspring = niching::allocate(&shared_fitnesses, self.population_size);
+
+        let mut survivors: Vec<T> = Vec::with_capacity(self.population_size);
+        let mut survivor_indexes: Vec<usize> = Vec::with_capacity(self.population_size);
+        let mut children: Vec<T> = Vec::with_capacity(self.population_size);
+        let num_parents = self.crossover_operator.parents();
+        for (s, num_offspring) in species.iter_mut().zip(offspring) {
+            s.offspring = num_offspring;
+            let mut members = s.members.clone();
+            let mut member_fitnesses: Vec<f64> = members.iter().map(|i| fitnesses[*i]).collect();
+            let mut member_violations: Option<Vec<f64>> = violations
+                .as_ref()
+                .map(|violations| members.iter().map(|i| violations[*i]).collect());
+            let num_survivors =
+                ((num_offspring as f32 * self.selection_rate) as usize).min(members.len());
+            for _ in 0..num_survivors {
+                let idx = self.select(&member_fitnesses, member_violations.as_deref());
+                member_fitnesses.remove(idx);
+                if let Some(member_violations) = member_violations.as_mut() {
+                    member_violations.remove(idx);
+                }
+                let gene_idx = members.remove(idx);
+                survivors.push(self.generation[gene_idx].clone());
+                survivor_indexes.push(gene_idx);
+            }
+
+            let parent_fitnesses: Vec<f64> = s.members.iter().map(|i| fitnesses[*i]).collect();
+            let parent_violations: Option<Vec<f64>> = violations
+                .as_ref()
+                .map(|violations| s.members.iter().map(|i| violations[*i]).collect());
+            let mut species_children: Vec<T> = Vec::new();
+            while species_children.len() < num_offspring - num_survivors {
+                let parents: Vec<&T> = self
+                    .select_parents(&parent_fitnesses, parent_violations.as_deref(), num_parents)
+                    .into_iter()
+                    .map(|idx| &self.generation[s.members[idx]])
+                    .collect();
+                if rng.gen_range(0.0..1.0) < self.crossover_rate {
+                    let new_children = self.crossover_operator.crossover_parents(&parents);
+                    if new_children.is_empty() {
+                        panic!("Crossover operator produced no children");
+                    }
+                    species_children.extend(new_children);
+                } else {
+                    species_children.extend(parents.into_iter().cloned());
+                }
+            }
+            species_children.truncate(num_offspring - num_survivors);
+            children.extend(species_children);
+        }
+
+        // Adjusts the threshold towards the target number of species.
+        if target_species > 0 {
+            if species.len() > target_species {
+                self.species_threshold += threshold_step;
+            } else if species.len() < target_species {
+                self.species_threshold =
+                    (self.species_threshold - threshold_step).max(threshold_step);
+            }
+        }
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!(
+                "{} species, compatibility threshold = {}",
+                species.len(),
+                self.species_threshold
+            )
+            .as_str(),
         );
+        self.species = species;
 
-        // Save generation_historic & best_gene
-        self.generation = new_generation;
-        self.save_generation();
+        if !self.learned_fitnesses.is_empty() {
+            let learned_fitnesses = std::mem::take(&mut self.learned_fitnesses);
+            self.learned_fitnesses = survivor_indexes
+                .iter()
+                .map(|i| learned_fitnesses.get(*i).copied().flatten())
+                .collect();
+        }
+        if !self.novelty_scores.is_empty() {
+            let novelty_scores = std::mem::take(&mut self.novelty_scores);
+            self.novelty_scores = survivor_indexes
+                .iter()
+                .map(|i| novelty_scores.get(*i).copied().unwrap_or(0.0))
+                .collect();
+        }
 
-        self.current_iteration += 1;
+        let num_survivors = survivors.len();
+        survivors.extend(children);
+        (survivors, num_survivors)
+    }
 
-        // Check stop criteria
-        self.check_stop_criteria();
+    /// Fills the new generation up to the population size crossing over its genes. The parents are chosen with the
+    /// selection algorithm and crossed over according to the crossover rate.
+    fn crossover_phase(&mut self, new_generation: &mut Vec<T>) {
+        let _phase_span = self.logger.enter_phase("crossover");
+        self.logger.log(VerbosityLevel::HIGH, ">> Crossover phase.");
+        let mut rng = rand::thread_rng();
+        let fitnesses = self.selection_fitnesses(new_generation);
+        let fitnesses = self.penalized_fitnesses(new_generation.iter(), &fitnesses);
+        let violations = self.tournament_violations(new_generation.iter());
+        let num_parents = self.crossover_operator.parents();
+        let num_children = self.population_size.saturating_sub(new_generation.len());
+        let mut children: Vec<T> = Vec::with_capacity(num_children);
 
-        &self.generation
+        while children.len() < num_children {
+            let parents: Vec<&T> = self
+                .select_parents(&fitnesses, violations.as_deref(), num_parents)
+                .into_iter()
+                .map(|idx| &new_generation[idx])
+                .collect();
+            let parents_fitness: Vec<f64> = parents.iter().map(|gene| gene.get_fitness()).collect();
+
+            if rng.gen_range(0.0..1.0) < self.crossover_rate {
+                let new_children = self.crossover_operator.crossover_parents(&parents);
+                if new_children.is_empty() {
+                    panic!("Crossover operator produced no children");
+                }
+                self.logger.log(
+                    VerbosityLevel::MID,
+                    format!(
+                        "Crossover between parents {:?} produced {} children",
+                        parents_fitness,
+                        new_children.len()
+                    )
+                    .as_str(),
+                );
+                children.extend(new_children);
+            } else {
+                self.logger.log(
+                    VerbosityLevel::MID,
+                    format!("Parents {:?} copied without crossover", parents_fitness).as_str(),
+                );
+                children.extend(parents.into_iter().cloned());
+            }
+        }
+
+        children.truncate(num_children);
+        new_generation.extend(children);
+    }
+
+    /// Refines the offspring of the new generation with the local search, if there is one. The offspring are repaired
+    /// and evaluated before the search, so the next fitness phase does not evaluate them again.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_generation` - The new generation.
+    /// * `offspring_start` - The index of the first offspring in `new_generation`.
+    fn local_search_phase(&mut self, new_generation: &mut [T], offspring_start: usize) {
+        let local_search = match &self.local_search {
+            Some(local_search) => local_search,
+            None => return,
+        };
+        let _phase_span = self.logger.enter_phase("local search");
+        self.logger
+            .log(VerbosityLevel::HIGH, ">> Local search phase.");
+        let mut rng = rand::thread_rng();
+        let repair = self.constraint_handling == ConstraintHandling::Repair;
+        self.evaluated_genes.resize(new_generation.len(), false);
+        for (gene, evaluated) in new_generation
+            .iter_mut()
+            .zip(self.evaluated_genes.iter_mut())
+            .skip(offspring_start)
+        {
+            if !*evaluated {
+                if repair && !ConstraintHandling::is_feasible(gene.constraint_violation()) {
+                    gene.repair();
+                }
+                gene.calculate_fitness();
+                *evaluated = true;
+            }
+        }
+
+        let mut offspring: Vec<usize> = (offspring_start..new_generation.len()).collect();
+        let offspring = match self.local_search_scope {
+            LocalSearchScope::Best(k) => {
+                offspring.sort_by(|a, b| {
+                    new_generation[*b]
+                        .get_fitness()
+                        .total_cmp(&new_generation[*a].get_fitness())
+                });
+                offspring.truncate(k);
+                offspring
+            }
+            LocalSearchScope::Fraction(fraction) => offspring
+                .into_iter()
+                .filter(|_| rng.gen_range(0.0..1.0) < fraction)
+                .collect(),
+            LocalSearchScope::All => offspring,
+        };
+        let refined: Vec<(usize, T)> = offspring
+            .into_iter()
+            .map(|idx| (idx, local_search.search(&new_generation[idx])))
+            .collect();
+
+        let mut improvements = 0;
+        let num_refined = refined.len();
+        for (idx, gene) in refined {
+            if gene.get_fitness() > new_generation[idx].get_fitness() {
+                improvements += 1;
+            }
+            match self.local_search_write_back {
+                LocalSearchWriteBack::Lamarckian => {
+                    new_generation[idx] = gene;
+                    let gene = &mut new_generation[idx];
+                    if repair && !ConstraintHandling::is_feasible(gene.constraint_violation()) {
+                        gene.repair();
+                        gene.calculate_fitness();
+                    }
+                }
+                LocalSearchWriteBack::Baldwinian => {
+                    if self.learned_fitnesses.len() <= idx {
+                        self.learned_fitnesses.resize(idx + 1, None);
+                    }
+                    self.learned_fitnesses[idx] = Some(gene.get_fitness());
+                }
+            }
+        }
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!(
+                "{} of {} genes improved by the local search.",
+                improvements, num_refined
+            )
+            .as_str(),
+        );
+    }
+
+    /// Returns the fitness of every gene of `genes`, or the one found by the Baldwinian local search if there is one.
+    fn learned_or_fitnesses(&self, genes: &[T]) -> Vec<f64> {
+        genes
+            .iter()
+            .enumerate()
+            .map(|(i, gene)| {
+                self.learned_fitnesses
+                    .get(i)
+                    .copied()
+                    .flatten()
+                    .unwrap_or(gene.get_fitness())
+            })
+            .collect()
+    }
+
+    /// Returns the value used to select every gene of `genes`: its novelty with `NoveltySearch`, otherwise its fitness
+    /// or the one found by the Baldwinian local search.
+    fn selection_fitnesses(&self, genes: &[T]) -> Vec<f64> {
+        if self.novelty_search.is_none() {
+            return self.learned_or_fitnesses(genes);
+        }
+
+        (0..genes.len())
+            .map(|i| self.novelty_scores.get(i).copied().unwrap_or(0.0))
+            .collect()
+    }
+
+    /// Applies the constraint handling to the fitnesses of `genes`.
+    fn penalized_fitnesses<'a>(
+        &self,
+        genes: impl IntoIterator<Item = &'a T>,
+        fitnesses: &[f64],
+    ) -> Vec<f64>
+    where
+        T: 'a,
+    {
+        if self.constraint_handling == ConstraintHandling::None {
+            return fitnesses.to_vec();
+        }
+
+        let violations: Vec<f64> = genes
+            .into_iter()
+            .map(|gene| gene.constraint_violation())
+            .collect();
+        self.constraint_handling
+            .penalized_fitnesses(fitnesses, &violations, self.current_iteration)
+    }
+
+    /// Creates a new gene with the gene init function, or with `Gene::init` by default.
+    pub(crate) fn new_gene(&self) -> T {
+        match &self.gene_init {
+            Some(gene_init) => gene_init(),
+            None => T::init(),
+        }
+    }
+
+    /// Returns the constraint violation of `genes` if the selection is a tournament compared with Deb's feasibility
+    /// rules, or `None` if the penalized fitnesses are used.
+    fn tournament_violations<'a>(&self, genes: impl IntoIterator<Item = &'a T>) -> Option<Vec<f64>>
+    where
+        T: 'a,
+    {
+        if !self.constraint_handling.uses_feasibility_rules()
+            || self.selection_algorithm.tournament_size().is_none()
+        {
+            return None;
+        }
+
+        Some(
+            genes
+                .into_iter()
+                .map(|gene| gene.constraint_violation())
+                .collect(),
+        )
+    }
+
+    /// Selects a gene with the selection algorithm, or with a tournament compared with Deb's feasibility rules if
+    /// `violations` are given.
+    fn select(&self, fitnesses: &Vec<f64>, violations: Option<&[f64]>) -> usize {
+        match (violations, self.selection_algorithm.tournament_size()) {
+            (Some(violations), Some(size)) => {
+                ConstraintHandling::tournament(fitnesses, violations, size)
+            }
+            _ => self.selection_algorithm.select(fitnesses),
+        }
+    }
+
+    /// Selects the parents of a crossover with the selection algorithm, avoiding repeated parents if possible.
+    ///
+    /// # Returns
+    ///
+    /// `parents` - The indexes of the parents in `fitnesses`.
+    fn select_parents(
+        &self,
+        fitnesses: &Vec<f64>,
+        violations: Option<&[f64]>,
+        num_parents: usize,
+    ) -> Vec<usize> {
+        let mut parents: Vec<usize> = Vec::with_capacity(num_parents);
+        while parents.len() < num_parents {
+            let mut idx = self.select(fitnesses, violations);
+            for _ in 0..PARENT_SELECTION_ATTEMPTS {
+                if !parents.contains(&idx) || fitnesses.len() < num_parents {
+                    break;
+                }
+                idx = self.select(fitnesses, violations);
+            }
+            parents.push(idx);
+        }
+        parents
+    }
+
+    /// Mutates the genes of the new generation according to the mutation rate.
+    fn mutation_phase(&mut self, new_generation: &mut [T]) {
+        let _phase_span = self.logger.enter_phase("mutation");
+        self.logger.log(VerbosityLevel::HIGH, ">> Mutation phase.");
+        let mut rng = rand::thread_rng();
+        let mut num_of_mutations = 0;
+        let count_successes = matches!(
+            self.mutation_adaptation,
+            MutationAdaptation::OneFifthRule { .. }
+        );
+        for (idx, gen) in new_generation.iter_mut().enumerate() {
+            if rng.gen_range(0.0..1.0) < self.gene_mutation_rate(gen) {
+                if count_successes {
+                    self.mutated_genes.push((idx, gen.calculate_fitness()));
+                }
+                self.mutate_gene(gen);
+                if let Some(learned) = self.learned_fitnesses.get_mut(idx) {
+                    *learned = None;
+                }
+                num_of_mutations += 1;
+            }
+        }
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!("{} mutations performed.", num_of_mutations).as_str(),
+        );
     }
 
-    /// Saves the generation just created and the best gene.
+    /// Saves the generation just created.
     fn save_generation(&mut self) {
-        logger::LOG(logger::VerbosityLevel::HIGH, ">> Saving generation data.");
+        let _phase_span = self.logger.enter_phase("save");
+        self.logger
+            .log(VerbosityLevel::HIGH, ">> Saving generation data.");
         self.generation_historic.push(Vec::clone(&self.generation));
+    }
 
-        let mut best_fitness: f64 = self.best_gene.get_fitness();
+    /// Updates the best genes with the generation just evaluated.
+    fn update_best_genes(&mut self) {
+        for gene in self.generation.iter() {
+            if self
+                .best_gene
+                .as_ref()
+                .map_or(true, |best| gene.get_fitness() > best.get_fitness())
+            {
+                self.best_gene = Some(gene.clone());
+            }
+        }
+        if let Some(best_gene) = &self.best_gene {
+            self.logger.log_best_fitness(best_gene.get_fitness());
+        }
 
         for gene in self.generation.iter() {
-            if gene.get_fitness() > best_fitness {
-                self.best_gene = *gene;
-                best_fitness = gene.get_fitness();
+            let is_better = self
+                .best_feasible_gene
+                .as_ref()
+                .map_or(true, |best| gene.get_fitness() > best.get_fitness());
+            if is_better && ConstraintHandling::is_feasible(gene.constraint_violation()) {
+                self.best_feasible_gene = Some(gene.clone());
             }
         }
-        logger::LOG(
-            logger::VerbosityLevel::LOW,
-            format!("Best gene with fitness = {:?}", best_fitness).as_str(),
-        );
+
+        if let Some(niche_radius) = self.niching.niche_radius() {
+            let mut candidates = std::mem::take(&mut self.niche_best_genes);
+            candidates.extend_from_slice(&self.generation);
+            self.niche_best_genes = niching::niche_best_genes(&candidates, niche_radius);
+            self.logger.log(
+                VerbosityLevel::MID,
+                format!("{} niches found.", self.niche_best_genes.len()).as_str(),
+            );
+        }
     }
 
     /// Checks if the algorithm should stop or not.
     fn check_stop_criteria(&mut self) {
-        if self.best_gene.get_fitness() >= self.fitness_goal {
+        if self
+            .best_gene
+            .as_ref()
+            .is_some_and(|best| best.get_fitness() >= self.fitness_goal)
+        {
             self.running = false;
             self.stop_criteria = StopCriteria::FitnessAchieved;
-            logger::LOG(
-                logger::VerbosityLevel::LOW,
+            self.logger.log(
+                VerbosityLevel::LOW,
                 format!("Algorithm must stop because of {:?}", self.stop_criteria).as_str(),
             );
         }
 
+        let mean_pairwise_distance = self
+            .statistics
+            .last()
+            .and_then(|statistics| statistics.diversity.mean_pairwise_distance);
+        if let (Some(threshold), Some(distance)) =
+            (self.convergence_threshold, mean_pairwise_distance)
+        {
+            if distance <= threshold {
+                self.running = false;
+                self.stop_criteria = StopCriteria::Converged;
+                self.logger.log(
+                    VerbosityLevel::LOW,
+                    format!("Algorithm must stop because of {:?}", self.stop_criteria).as_str(),
+                );
+            }
+        }
+
         if self.current_iteration >= self.iterations {
             self.running = false;
             self.stop_criteria = StopCriteria::MaxIterations;
-            logger::LOG(
-                logger::VerbosityLevel::LOW,
+            self.logger.log(
+                VerbosityLevel::LOW,
                 format!("Algorithm must stop because of {:?}", self.stop_criteria).as_str(),
             );
         }
@@ -323,10 +1355,11 @@ impl<T: Gene + Copy> GeneticAlgorithm<T> {
     /// If the population size is greather than the actual size, the generation ir resized filling the empty values with new genes of value `T`.
     pub fn population_size(mut self, population_size: usize) -> Self {
         if population_size >= self.population_size {
-            self.generation
-                .extend((0..population_size - self.generation.len()).map(|_| T::init()));
+            let missing = population_size - self.generation.len();
+            let genes: Vec<T> = (0..missing).map(|_| self.new_gene()).collect();
+            self.generation.extend(genes);
         } else {
-            self.generation.resize(population_size, T::init());
+            self.generation.truncate(population_size);
         }
         self.population_size = population_size;
         self
@@ -353,6 +1386,13 @@ impl<T: Gene + Copy> GeneticAlgorithm<T> {
         }
 
         self.mutation_rate = mutation_rate;
+        self.current_mutation_rate = mutation_rate;
+        self
+    }
+
+    /// Sets the strategy to adapt the mutation rate during the execution.
+    pub fn mutation_adaptation(mut self, mutation_adaptation: MutationAdaptation) -> Self {
+        self.mutation_adaptation = mutation_adaptation;
         self
     }
 
@@ -372,12 +1412,204 @@ impl<T: Gene + Copy> GeneticAlgorithm<T> {
         self
     }
 
+    /// Sets the crossover rate.
+    pub fn crossover_rate(mut self, crossover_rate: f32) -> Self {
+        if crossover_rate > 1.0 || crossover_rate < 0.0 {
+            panic!("Crossover rate not in rage between 0.0 and 1.0");
+        }
+
+        self.crossover_rate = crossover_rate;
+        self
+    }
+
+    /// Sets the crossover operator. By default `Gene::crossover` is used.
+    ///
+    /// # Panics
+    ///
+    /// If `CrossoverOperator::parents` of the operator is lower than 2.
+    pub fn crossover_operator(mut self, crossover_operator: Box<dyn CrossoverOperator<T>>) -> Self {
+        if crossover_operator.parents() < 2 {
+            panic!("Crossover operator needs at least 2 parents");
+        }
+
+        self.crossover_operator = crossover_operator;
+        self
+    }
+
+    /// Sets the mutation operator. By default `Gene::mutate` is used.
+    pub fn mutation_operator(mut self, mutation_operator: Box<dyn MutationOperator<T>>) -> Self {
+        self.mutation_operator = mutation_operator;
+        self
+    }
+
+    /// Sets the function creating the new genes, used instead of `Gene::init` to create genes carrying runtime data,
+    /// e.g. the problem instance of the built-in genotypes. The current generation is created again with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `gene_init` - The function creating a new random gene.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_ga::genotypes::{BitStringGene, BitStringProblem};
+    /// use easy_ga::{Gene, GeneticAlgorithm};
+    /// use std::sync::Arc;
+    ///
+    /// struct Target(Vec<bool>);
+    ///
+    /// impl BitStringProblem for Target {
+    ///     const LENGTH: usize = 8;
+    ///
+    ///     fn evaluate(&self, gene: &BitStringGene<Self>) -> f64 {
+    ///         gene.iter().zip(self.0.iter()).filter(|(a, b)| a == *b).count() as f64
+    ///     }
+    /// }
+    ///
+    /// let target = Arc::new(Target(vec![true, false, true, false, true, false, true, false]));
+    /// let (gene, _) = GeneticAlgorithm::<BitStringGene<Target>>::new()
+    ///     .iterations(10)
+    ///     .gene_init(Box::new(move || BitStringGene::init().with_problem(target.clone())))
+    ///     .run();
+    /// ```
+    pub fn gene_init(mut self, gene_init: Box<dyn Fn() -> T>) -> Self {
+        self.gene_init = Some(gene_init);
+        self.generation = (0..self.generation.len())
+            .map(|_| self.new_gene())
+            .collect();
+        self
+    }
+
     /// Sets the fitness goal to reach and stop the algorithm.
     pub fn fitness_goal(mut self, fitness_goal: f64) -> Self {
         self.fitness_goal = fitness_goal;
         self
     }
 
+    /// Sets the mean pairwise distance under which the generation is considered converged and the algorithm stops.
+    ///
+    /// # Notes
+    ///
+    /// It only has effect if the gene defines `Gene::distance`.
+    pub fn convergence_threshold(mut self, convergence_threshold: f64) -> Self {
+        self.convergence_threshold = Some(convergence_threshold);
+        self
+    }
+
+    /// Sets if the mean pairwise distance and the unique genomes are saved in the statistics of every generation.
+    ///
+    /// # Notes
+    ///
+    /// They need O(n²) calls to `Gene::distance` every generation, so they are only calculated by default when the
+    /// convergence threshold, `MutationAdaptation::Hypermutation` or `RestartTrigger::LowDiversity` use them.
+    pub fn diversity_statistics(mut self, diversity_statistics: bool) -> Self {
+        self.diversity_statistics = diversity_statistics;
+        self
+    }
+
+    /// Sets the niching mode to keep several optima in the population.
+    pub fn niching(mut self, niching: Niching) -> Self {
+        if let Niching::Speciation { threshold, .. } = niching {
+            self.species_threshold = threshold;
+        }
+
+        self.niching = niching;
+        self
+    }
+
+    /// Sets the hall of fame that keeps the best distinct genes evaluated in every generation.
+    pub fn hall_of_fame(mut self, hall_of_fame: HallOfFame<T>) -> Self {
+        self.hall_of_fame = Some(hall_of_fame);
+        self
+    }
+
+    /// Sets the restart strategy to re-initialise the generation when the execution stagnates.
+    ///
+    /// # Panics
+    ///
+    /// If the fraction of `Reinitialisation::Partial` is not between 0.0 and 1.0 or if the population growth is lower
+    /// than 1.0.
+    pub fn restart(mut self, restart: Restart) -> Self {
+        if let Reinitialisation::Partial { fraction } = restart.reinitialisation {
+            if fraction > 1.0 || fraction < 0.0 {
+                panic!("Reinitialisation fraction not in rage between 0.0 and 1.0");
+            }
+        }
+        if restart.population_growth.is_nan() || restart.population_growth < 1.0 {
+            panic!("Population growth must be at least 1.0");
+        }
+
+        self.restart = Some(restart);
+        self
+    }
+
+    /// Sets the strategy to handle the constraints defined by `Gene::constraint_violation`.
+    pub fn constraint_handling(mut self, constraint_handling: ConstraintHandling) -> Self {
+        self.constraint_handling = constraint_handling;
+        self
+    }
+
+    /// Sets the local searcher that refines the offspring of every iteration, making the algorithm memetic.
+    ///
+    /// # Notes
+    ///
+    /// The built-in local searchers are `HillClimbing` and `SimulatedAnnealing`.
+    pub fn local_search(mut self, local_search: Box<dyn LocalSearch<T>>) -> Self {
+        self.local_search = Some(local_search);
+        self
+    }
+
+    /// Sets the offspring the local search is applied to. With `Niching::DeterministicCrowding` the offspring are the
+    /// whole new generation.
+    ///
+    /// # Panics
+    ///
+    /// If the fraction of `LocalSearchScope::Fraction` is not between 0.0 and 1.0.
+    pub fn local_search_scope(mut self, local_search_scope: LocalSearchScope) -> Self {
+        if let LocalSearchScope::Fraction(fraction) = local_search_scope {
+            if fraction > 1.0 || fraction < 0.0 {
+                panic!("Local search fraction not in rage between 0.0 and 1.0");
+            }
+        }
+
+        self.local_search_scope = local_search_scope;
+        self
+    }
+
+    /// Sets how the result of the local search is written back.
+    pub fn local_search_write_back(
+        mut self,
+        local_search_write_back: LocalSearchWriteBack,
+    ) -> Self {
+        self.local_search_write_back = local_search_write_back;
+        self
+    }
+
+    /// Sets the novelty search mode. The genes are selected by their novelty in the behaviour space defined by
+    /// `Gene::behaviour` instead of their fitness, while the best gene is still the one with the best fitness.
+    ///
+    /// # Notes
+    ///
+    /// The novelty is not used by `Niching::DeterministicCrowding`, whose children compete by fitness.
+    ///
+    /// # Panics
+    ///
+    /// If `k` is 0.
+    pub fn novelty_search(mut self, novelty_search: NoveltySearch) -> Self {
+        if novelty_search.k == 0 {
+            panic!("Number of nearest neighbours of the novelty search is 0");
+        }
+
+        self.novelty_search = Some(novelty_search);
+        self
+    }
+
+    /// Sets the logger of this execution.
+    pub fn logger(mut self, logger: Logger) -> Self {
+        self.logger = logger;
+        self
+    }
+
     /// Returns the population size.
     pub fn get_population_size(&self) -> usize {
         self.population_size
@@ -398,6 +1630,21 @@ impl<T: Gene + Copy> GeneticAlgorithm<T> {
         self.generation.clone()
     }
 
+    /// Returns the current generation as mutable, for the drivers assigning the fitness of the genes.
+    pub(crate) fn generation_mut(&mut self) -> &mut [T] {
+        self.evaluated_genes.clear();
+        &mut self.generation
+    }
+
+    /// Replaces the current generation, for the drivers moving genes between algorithms. The learned fitnesses and
+    /// the novelty scores of the old generation are discarded.
+    pub(crate) fn set_generation(&mut self, generation: Vec<T>) {
+        self.generation = generation;
+        self.learned_fitnesses.clear();
+        self.evaluated_genes.clear();
+        self.novelty_scores.clear();
+    }
+
     /// Returns all the generations the algorithm passed.
     pub fn get_generation_historic(&self) -> Vec<Vec<T>> {
         self.generation_historic.clone()
@@ -408,18 +1655,33 @@ impl<T: Gene + Copy> GeneticAlgorithm<T> {
         self.mutation_rate
     }
 
+    /// Returns the strategy to adapt the mutation rate.
+    pub fn get_mutation_adaptation(&self) -> MutationAdaptation {
+        self.mutation_adaptation
+    }
+
+    /// Returns the mutation rate of the current generation.
+    pub fn get_current_mutation_rate(&self) -> f32 {
+        self.current_mutation_rate
+    }
+
     /// Returns the selection rate.
     pub fn get_selection_rate(&self) -> f32 {
         self.selection_rate
     }
 
+    /// Returns the crossover rate.
+    pub fn get_crossover_rate(&self) -> f32 {
+        self.crossover_rate
+    }
+
     pub fn get_fitness_goal(&self) -> f64 {
         self.fitness_goal
     }
 
-    /// Returns the best gene in all the generations.
-    pub fn get_best_gene(&self) -> T {
-        self.best_gene
+    /// Returns the best gene in all the evaluated generations, or `None` before the first iteration.
+    pub fn get_best_gene(&self) -> Option<T> {
+        self.best_gene.clone()
     }
 
     /// Returns if the algorithm is currently running
@@ -430,21 +1692,126 @@ impl<T: Gene + Copy> GeneticAlgorithm<T> {
     pub fn get_stop_criteria(&self) -> StopCriteria {
         self.stop_criteria
     }
+
+    /// Returns the convergence threshold.
+    pub fn get_convergence_threshold(&self) -> Option<f64> {
+        self.convergence_threshold
+    }
+
+    /// Returns if the distance metrics of the diversity are saved in the statistics.
+    pub fn get_diversity_statistics(&self) -> bool {
+        self.diversity_statistics
+    }
+
+    /// Returns the statistics of every evaluated generation.
+    pub fn get_statistics(&self) -> &[GenerationStatistics] {
+        &self.statistics
+    }
+
+    /// Returns the niching mode.
+    pub fn get_niching(&self) -> Niching {
+        self.niching
+    }
+
+    /// Returns the best gene of every niche found, sorted from the best to the worst fitness.
+    ///
+    /// # Notes
+    ///
+    /// Without niching it only contains the best gene overall.
+    pub fn get_niche_best_genes(&self) -> Vec<T> {
+        match self.niching {
+            Niching::None => self.best_gene.iter().cloned().collect(),
+            _ => self.niche_best_genes.clone(),
+        }
+    }
+
+    /// Returns the hall of fame with the best distinct genes seen so far.
+    pub fn get_hall_of_fame(&self) -> Option<&HallOfFame<T>> {
+        self.hall_of_fame.as_ref()
+    }
+
+    /// Returns the species of `Niching::Speciation` in the last iteration.
+    pub fn get_species(&self) -> &[Species<T>] {
+        &self.species
+    }
+
+    /// Returns the current compatibility threshold of `Niching::Speciation`.
+    pub fn get_species_threshold(&self) -> f64 {
+        self.species_threshold
+    }
+
+    /// Returns the strategy to handle the constraints.
+    pub fn get_constraint_handling(&self) -> ConstraintHandling {
+        self.constraint_handling
+    }
+
+    /// Returns the best feasible gene overall, or `None` if no feasible gene has been found.
+    pub fn get_best_feasible_gene(&self) -> Option<T> {
+        self.best_feasible_gene.clone()
+    }
+
+    /// Returns the offspring the local search is applied to.
+    pub fn get_local_search_scope(&self) -> LocalSearchScope {
+        self.local_search_scope
+    }
+
+    /// Returns how the result of the local search is written back.
+    pub fn get_local_search_write_back(&self) -> LocalSearchWriteBack {
+        self.local_search_write_back
+    }
+
+    /// Returns the fitness found by the Baldwinian local search for every gene of the current generation, or `None`
+    /// for the genes without one.
+    pub fn get_learned_fitnesses(&self) -> Vec<Option<f64>> {
+        let mut learned_fitnesses = self.learned_fitnesses.clone();
+        learned_fitnesses.resize(self.generation.len(), None);
+        learned_fitnesses
+    }
+
+    /// Returns the novelty search mode.
+    pub fn get_novelty_search(&self) -> Option<NoveltySearch> {
+        self.novelty_search
+    }
+
+    /// Returns the archive of novel behaviours found by the novelty search.
+    pub fn get_novelty_archive(&self) -> &[Vec<f64>] {
+        &self.novelty_archive
+    }
+
+    /// Returns the restart strategy.
+    pub fn get_restart(&self) -> Option<Restart> {
+        self.restart
+    }
+
+    /// Returns the number of restarts performed.
+    pub fn get_restarts(&self) -> u32 {
+        self.restarts
+    }
+
+    /// Returns the logger of this execution.
+    pub fn get_logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// Returns the logger of this execution as mutable, for example to flush it.
+    pub fn get_logger_mut(&mut self) -> &mut Logger {
+        &mut self.logger
+    }
 }
 
 /// Default trait implementation for GeneticAlgorithm.
-impl<T: Gene + Copy> Default for GeneticAlgorithm<T> {
+impl<T: Gene + Clone> Default for GeneticAlgorithm<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
 /// Display trait implementation for GeneticAlgorithm.
-impl<T: Gene + Copy> fmt::Display for GeneticAlgorithm<T> {
+impl<T: Gene + Clone> fmt::Display for GeneticAlgorithm<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-             "{{\n\tpopulation_size: {},\n\titerations: {},\n\tcurrent_iteration: {},\n\tselection_rate: {},\n\tmutation_rate: {},\n\tfitness_goal: {:?},\n\tstop_criteria: {:?},\n\tbest_gene_fitness: {}\n}}",
+             "{{\n\tpopulation_size: {},\n\titerations: {},\n\tcurrent_iteration: {},\n\tselection_rate: {},\n\tmutation_rate: {},\n\tfitness_goal: {:?},\n\tstop_criteria: {:?},\n\tbest_gene_fitness: {:?}\n}}",
             self.population_size,
             self.iterations,
             self.current_iteration,
@@ -452,7 +1819,7 @@ impl<T: Gene + Copy> fmt::Display for GeneticAlgorithm<T> {
             self.mutation_rate,
             self.fitness_goal,
             self.stop_criteria,
-            self.best_gene.get_fitness()
+            self.best_gene.as_ref().map(|gene| gene.get_fitness())
         )
     }
 }
@@ -0,0 +1,455 @@
+//! This module contains a fixed length binary string genotype.
+
+use core::fmt;
+use rand::Rng;
+use std::sync::Arc;
+
+use crate::operators::{self, CrossoverOperator, DiagonalCrossover};
+use crate::Gene;
+
+/// Number of bits in every word of the packed genome.
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Crossover operators for `BitStringGene`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitStringCrossover {
+    /// The child takes the bits of `self` up to a random point and the bits of `other` after it.
+    OnePoint,
+    /// The child takes the bits of `other` between two random points and the bits of `self` outside them.
+    TwoPoint,
+    /// Every bit of the child is taken from `self` or `other` with the same probability.
+    Uniform,
+}
+
+impl<P: BitStringProblem> CrossoverOperator<BitStringGene<P>> for BitStringCrossover {
+    fn crossover(
+        &self,
+        parent1: &BitStringGene<P>,
+        parent2: &BitStringGene<P>,
+    ) -> BitStringGene<P> {
+        match self {
+            BitStringCrossover::OnePoint => parent1.one_point_crossover(parent2),
+            BitStringCrossover::TwoPoint => parent1.two_point_crossover(parent2),
+            BitStringCrossover::Uniform => parent1.uniform_crossover(parent2),
+        }
+    }
+}
+
+impl<P: BitStringProblem> CrossoverOperator<BitStringGene<P>> for DiagonalCrossover {
+    fn crossover(
+        &self,
+        parent1: &BitStringGene<P>,
+        parent2: &BitStringGene<P>,
+    ) -> BitStringGene<P> {
+        self.crossover_parents(&[parent1, parent2]).remove(0)
+    }
+
+    fn parents(&self) -> usize {
+        self.parents
+    }
+
+    fn crossover_parents(&self, parents: &[&BitStringGene<P>]) -> Vec<BitStringGene<P>> {
+        let segments = operators::diagonal_segments(P::LENGTH, parents.len());
+        (0..parents.len())
+            .map(|i| {
+                let mut child = BitStringGene::zeros();
+                child.problem = parents[i].problem.clone();
+                for (position, segment) in segments.iter().enumerate() {
+                    child.set(
+                        position,
+                        parents[(i + segment) % parents.len()].get(position),
+                    );
+                }
+                child
+            })
+            .collect()
+    }
+}
+
+/// Definition of a problem solved with `BitStringGene`. A problem that only needs the length and the fitness can use a
+/// closure with `BitStringGene::problem` instead.
+///
+/// # Examples
+///
+/// ```
+/// use easy_ga::genotypes::{BitStringGene, BitStringProblem};
+/// use easy_ga::GeneticAlgorithm;
+///
+/// struct OneMax;
+///
+/// impl BitStringProblem for OneMax {
+///     const LENGTH: usize = 64;
+///
+///     fn fitness(gene: &BitStringGene<Self>) -> f64 {
+///         gene.count_ones() as f64
+///     }
+/// }
+///
+/// let (gene, _) = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+///     .iterations(10)
+///     .run();
+/// ```
+///
+/// # Notes
+///
+/// The associated constants and `fitness` are static. A problem with runtime data implements `evaluate` instead and
+/// its genes carry the problem instance, set with `BitStringGene::with_problem` and created with
+/// `GeneticAlgorithm::gene_init`. The constraint violation, the repair and the behaviour are always static.
+pub trait BitStringProblem {
+    /// Number of bits of every gene.
+    const LENGTH: usize;
+    /// Crossover operator used by `Gene::crossover`.
+    const CROSSOVER: BitStringCrossover = BitStringCrossover::OnePoint;
+    /// Probability of flipping every bit in `Gene::mutate`. By default one bit per gene is flipped on average.
+    const MUTATION_PROBABILITY: f64 = 1.0 / Self::LENGTH as f64;
+
+    /// The fitness function of the genes without a problem instance.
+    ///
+    /// # Panics
+    ///
+    /// By default, because a problem must implement either `fitness` or `evaluate`.
+    fn fitness(_gene: &BitStringGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        panic!("BitStringProblem::fitness not implemented and the gene has no problem instance");
+    }
+
+    /// The fitness function of the genes with a problem instance, that can use the runtime data of `self`. By default
+    /// it is `fitness`.
+    fn evaluate(&self, gene: &BitStringGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        Self::fitness(gene)
+    }
+
+    /// The constraint violation used by `Gene::constraint_violation`. By default every gene is feasible.
+    fn constraint_violation(_gene: &BitStringGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        0.0
+    }
+
+    /// The repair used by `Gene::repair`. By default the genes are not repaired.
+    fn repair(_gene: &mut BitStringGene<Self>)
+    where
+        Self: Sized,
+    {
+    }
+
+    /// The behaviour descriptor used by `Gene::behaviour`. By default the genes do not define a behaviour.
+    fn behaviour(_gene: &BitStringGene<Self>) -> Option<Vec<f64>>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// Fitness closure of a `BitStringFitness` problem.
+type FitnessClosure<P> = Box<dyn Fn(&BitStringGene<P>) -> f64 + Send + Sync>;
+
+/// A `BitStringProblem` of `N` bits defined only by a fitness closure. Its genes are created with
+/// `BitStringGene::problem`.
+pub struct BitStringFitness<const N: usize> {
+    /// The fitness function of the genes.
+    fitness: FitnessClosure<Self>,
+}
+
+impl<const N: usize> BitStringFitness<N> {
+    /// Creates a new problem evaluating the genes with `fitness`.
+    pub fn new<F>(fitness: F) -> Self
+    where
+        F: Fn(&BitStringGene<Self>) -> f64 + Send + Sync + 'static,
+    {
+        BitStringFitness {
+            fitness: Box::new(fitness),
+        }
+    }
+}
+
+impl<const N: usize> BitStringProblem for BitStringFitness<N> {
+    const LENGTH: usize = N;
+
+    fn evaluate(&self, gene: &BitStringGene<Self>) -> f64 {
+        (self.fitness)(gene)
+    }
+}
+
+/// Fixed length binary string gene with its bits packed in `u64` words.
+pub struct BitStringGene<P: BitStringProblem> {
+    /// The packed bits. The unused bits of the last word are always 0.
+    words: Vec<u64>,
+    /// The fitness value.
+    fitness: f64,
+    /// The problem instance evaluating the gene, `None` to use `BitStringProblem::fitness`.
+    problem: Option<Arc<P>>,
+}
+
+impl<const N: usize> BitStringGene<BitStringFitness<N>> {
+    /// Returns the function creating random genes of `N` bits evaluated with the `fitness` closure, set with
+    /// `GeneticAlgorithm::gene_init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `fitness` - The fitness function of the genes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_ga::genotypes::{BitStringFitness, BitStringGene};
+    /// use easy_ga::GeneticAlgorithm;
+    ///
+    /// let (gene, _) = GeneticAlgorithm::<BitStringGene<BitStringFitness<64>>>::new()
+    ///     .iterations(10)
+    ///     .gene_init(BitStringGene::problem(|gene| gene.count_ones() as f64))
+    ///     .run();
+    /// ```
+    pub fn problem<F>(fitness: F) -> Box<dyn Fn() -> Self>
+    where
+        F: Fn(&Self) -> f64 + Send + Sync + 'static,
+    {
+        let problem = Arc::new(BitStringFitness::new(fitness));
+        Box::new(move || Self::init().with_problem(problem.clone()))
+    }
+}
+
+impl<P: BitStringProblem> BitStringGene<P> {
+    /// Creates a new gene with every bit set to `false`.
+    pub fn zeros() -> Self {
+        BitStringGene {
+            words: vec![0; P::LENGTH.div_ceil(WORD_BITS)],
+            fitness: 0.0,
+            problem: None,
+        }
+    }
+
+    /// Sets the problem instance evaluating the gene with `BitStringProblem::evaluate`. The children of the crossover
+    /// take the problem instance of their first parent.
+    pub fn with_problem(mut self, problem: Arc<P>) -> Self {
+        self.problem = Some(problem);
+        self
+    }
+
+    /// Returns the problem instance evaluating the gene, if any.
+    pub fn get_problem(&self) -> Option<&Arc<P>> {
+        self.problem.as_ref()
+    }
+
+    /// Creates a new gene from a slice of bools.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `bits` is not `P::LENGTH`.
+    pub fn from_bools(bits: &[bool]) -> Self {
+        assert_eq!(bits.len(), P::LENGTH, "Wrong number of bits");
+        let mut gene = Self::zeros();
+        for (i, bit) in bits.iter().enumerate() {
+            gene.set(i, *bit);
+        }
+        gene
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        P::LENGTH
+    }
+
+    /// Returns if the gene has no bits.
+    pub fn is_empty(&self) -> bool {
+        P::LENGTH == 0
+    }
+
+    /// Returns the bit at `index`.
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < P::LENGTH, "Bit index out of range");
+        self.words[index / WORD_BITS] >> (index % WORD_BITS) & 1 == 1
+    }
+
+    /// Sets the bit at `index`.
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < P::LENGTH, "Bit index out of range");
+        let mask = 1 << (index % WORD_BITS);
+        if value {
+            self.words[index / WORD_BITS] |= mask;
+        } else {
+            self.words[index / WORD_BITS] &= !mask;
+        }
+    }
+
+    /// Flips the bit at `index`.
+    pub fn flip(&mut self, index: usize) {
+        assert!(index < P::LENGTH, "Bit index out of range");
+        self.words[index / WORD_BITS] ^= 1 << (index % WORD_BITS);
+    }
+
+    /// Returns the number of bits set to `true`.
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns an iterator over the bits.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..P::LENGTH).map(move |index| self.get(index))
+    }
+
+    /// Returns the packed words. The bit `i` is the bit `i % 64` of the word `i / 64`.
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Returns the number of different bits with `other`.
+    pub fn hamming_distance(&self, other: &Self) -> usize {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .map(|(a, b)| (a ^ b).count_ones() as usize)
+            .sum()
+    }
+
+    /// One-point crossover.
+    pub fn one_point_crossover(&self, other: &Self) -> Self {
+        let point = rand::thread_rng().gen_range(0..=P::LENGTH);
+        self.crossover_range(other, point, P::LENGTH)
+    }
+
+    /// Two-point crossover.
+    pub fn two_point_crossover(&self, other: &Self) -> Self {
+        let mut rng = rand::thread_rng();
+        let (a, b) = (rng.gen_range(0..=P::LENGTH), rng.gen_range(0..=P::LENGTH));
+        self.crossover_range(other, a.min(b), a.max(b))
+    }
+
+    /// Uniform crossover.
+    pub fn uniform_crossover(&self, other: &Self) -> Self {
+        let mut rng = rand::thread_rng();
+        let mut child = Self::zeros();
+        child.problem = self.problem.clone();
+        for (i, word) in child.words.iter_mut().enumerate() {
+            let mask: u64 = rng.gen();
+            *word = (self.words[i] & mask) | (other.words[i] & !mask);
+        }
+        child
+    }
+
+    /// Flips every bit with probability `probability`.
+    pub fn bit_flip_mutation(&mut self, probability: f64) {
+        let mut rng = rand::thread_rng();
+        for index in 0..P::LENGTH {
+            if rng.gen_bool(probability) {
+                self.flip(index);
+            }
+        }
+    }
+
+    /// Returns a copy of `self` with the bits in `start..end` taken from `other`.
+    fn crossover_range(&self, other: &Self, start: usize, end: usize) -> Self {
+        let mut child = self.clone();
+        for (i, word) in child.words.iter_mut().enumerate() {
+            let word_start = i * WORD_BITS;
+            let from = start.clamp(word_start, word_start + WORD_BITS) - word_start;
+            let to = end.clamp(word_start, word_start + WORD_BITS) - word_start;
+            let mask = low_bits(to) & !low_bits(from);
+            *word = (*word & !mask) | (other.words[i] & mask);
+        }
+        child.fitness = 0.0;
+        child
+    }
+}
+
+/// Returns a mask with the `n` lowest bits set.
+fn low_bits(n: usize) -> u64 {
+    if n >= WORD_BITS {
+        u64::MAX
+    } else {
+        (1 << n) - 1
+    }
+}
+
+impl<P: BitStringProblem> Gene for BitStringGene<P> {
+    fn init() -> Self {
+        let mut rng = rand::thread_rng();
+        let mut gene = Self::zeros();
+        for word in gene.words.iter_mut() {
+            *word = rng.gen();
+        }
+        let unused_bits = gene.words.len() * WORD_BITS - P::LENGTH;
+        if let Some(last) = gene.words.last_mut() {
+            *last &= low_bits(WORD_BITS - unused_bits);
+        }
+        gene
+    }
+
+    fn calculate_fitness(&mut self) -> f64 {
+        self.fitness = match &self.problem {
+            Some(problem) => problem.evaluate(self),
+            None => P::fitness(self),
+        };
+        self.fitness
+    }
+
+    fn crossover(&self, other: &Self) -> Self {
+        P::CROSSOVER.crossover(self, other)
+    }
+
+    fn mutate(&mut self) {
+        self.bit_flip_mutation(P::MUTATION_PROBABILITY);
+    }
+
+    fn get_fitness(&self) -> f64 {
+        self.fitness
+    }
+
+    fn distance(&self, other: &Self) -> Option<f64> {
+        Some(self.hamming_distance(other) as f64)
+    }
+
+    fn constraint_violation(&self) -> f64 {
+        P::constraint_violation(self)
+    }
+
+    fn repair(&mut self) {
+        P::repair(self)
+    }
+
+    fn behaviour(&self) -> Option<Vec<f64>> {
+        P::behaviour(self)
+    }
+}
+
+impl<P: BitStringProblem> Clone for BitStringGene<P> {
+    fn clone(&self) -> Self {
+        BitStringGene {
+            words: self.words.clone(),
+            fitness: self.fitness,
+            problem: self.problem.clone(),
+        }
+    }
+}
+
+impl<P: BitStringProblem> PartialEq for BitStringGene<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.words == other.words
+    }
+}
+
+impl<P: BitStringProblem> fmt::Debug for BitStringGene<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BitStringGene({self})")
+    }
+}
+
+/// Display trait implementation for BitStringGene, printing the bits from the first to the last.
+impl<P: BitStringProblem> fmt::Display for BitStringGene<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in self.iter() {
+            write!(f, "{}", bit as u8)?;
+        }
+        Ok(())
+    }
+}
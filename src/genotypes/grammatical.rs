@@ -0,0 +1,491 @@
+//! This module contains a grammatical evolution genotype.
+//!
+//! A `GrammaticalGene` is a list of integer codons mapped through a BNF `Grammar` into a string. Starting from the
+//! first rule, the leftmost non-terminal is always expanded with the production `codon % productions`. Rules with a
+//! single production do not consume codons.
+
+use core::fmt;
+use rand::Rng;
+use std::collections::HashMap;
+use std::error::Error;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::{fs, io};
+
+use crate::Gene;
+
+/// Errors loading a `Grammar`.
+#[derive(Debug)]
+pub enum GrammarError {
+    /// The grammar file could not be read.
+    Io(io::Error),
+    /// The grammar is not valid.
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrammarError::Io(error) => write!(f, "Cannot read the grammar: {error}"),
+            GrammarError::Parse { line, message } => {
+                write!(f, "Invalid grammar at line {line}: {message}")
+            }
+        }
+    }
+}
+
+impl Error for GrammarError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GrammarError::Io(error) => Some(error),
+            GrammarError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for GrammarError {
+    fn from(error: io::Error) -> Self {
+        GrammarError::Io(error)
+    }
+}
+
+/// Symbol of a production.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Symbol {
+    Terminal(String),
+    /// Index of the rule.
+    NonTerminal(usize),
+}
+
+/// Rule of a grammar with its alternative productions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Rule {
+    name: String,
+    productions: Vec<Vec<Symbol>>,
+}
+
+/// BNF grammar.
+///
+/// Every rule has the form `<name> ::= production | production`, and can continue in the next lines starting with
+/// `|`. Symbols are separated by whitespace, `<name>` is a non-terminal and anything else is a terminal. Terminals
+/// with whitespace or `|` can be quoted with `"` or `'`. Terminals are concatenated without separator. Empty lines
+/// and lines starting with `#` are ignored. The first rule is the start symbol.
+///
+/// ```text
+/// <expr> ::= <expr> <op> <expr> | ( <expr> ) | <var>
+/// <op>   ::= + | - | *
+/// <var>  ::= x | 1.0
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grammar {
+    rules: Vec<Rule>,
+}
+
+impl Grammar {
+    /// Loads a grammar from a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GrammarError::Io` if the file cannot be read and `GrammarError::Parse` if the grammar is not valid.
+    pub fn from_file<T: AsRef<Path>>(path: T) -> Result<Grammar, GrammarError> {
+        Grammar::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parses a grammar.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GrammarError::Parse` if the grammar is not valid.
+    pub fn parse(text: &str) -> Result<Grammar, GrammarError> {
+        let mut raw_rules: Vec<(String, usize, Vec<Vec<String>>)> = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let productions = if let Some((name, productions)) = line.split_once("::=") {
+                let name = name.trim();
+                if !is_non_terminal(name) {
+                    return Err(parse_error(
+                        line_number,
+                        format!("invalid rule name {name}"),
+                    ));
+                }
+                raw_rules.push((name.to_string(), line_number, Vec::new()));
+                productions
+            } else if let Some(productions) = line.strip_prefix('|') {
+                if raw_rules.is_empty() {
+                    return Err(parse_error(line_number, "production without rule".into()));
+                }
+                productions
+            } else {
+                return Err(parse_error(line_number, "expected ::= or |".into()));
+            };
+
+            let productions =
+                tokenize(productions).map_err(|message| parse_error(line_number, message))?;
+            raw_rules.last_mut().unwrap().2.extend(productions);
+        }
+
+        if raw_rules.is_empty() {
+            return Err(parse_error(0, "the grammar has no rules".into()));
+        }
+
+        let mut indexes = HashMap::new();
+        for (index, (name, line_number, _)) in raw_rules.iter().enumerate() {
+            if indexes.insert(name.clone(), index).is_some() {
+                return Err(parse_error(*line_number, format!("duplicated rule {name}")));
+            }
+        }
+
+        let mut rules = Vec::with_capacity(raw_rules.len());
+        for (name, line_number, productions) in raw_rules {
+            let productions = productions
+                .into_iter()
+                .map(|production| {
+                    production
+                        .into_iter()
+                        .map(|token| {
+                            if is_non_terminal(&token) {
+                                indexes
+                                    .get(&token)
+                                    .map(|index| Symbol::NonTerminal(*index))
+                                    .ok_or_else(|| {
+                                        parse_error(line_number, format!("undefined rule {token}"))
+                                    })
+                            } else {
+                                Ok(Symbol::Terminal(token))
+                            }
+                        })
+                        .collect::<Result<Vec<Symbol>, GrammarError>>()
+                })
+                .collect::<Result<Vec<Vec<Symbol>>, GrammarError>>()?;
+            rules.push(Rule { name, productions });
+        }
+        Ok(Grammar { rules })
+    }
+
+    /// Maps a list of codons into a string.
+    ///
+    /// # Arguments
+    ///
+    /// * `codons` - The codons choosing the productions.
+    /// * `max_wraps` - Number of times the codons can be reused from the beginning when they are exhausted.
+    /// * `max_depth` - Maximum depth of the derivation tree. The start symbol has depth 0.
+    pub fn map(&self, codons: &[u32], max_wraps: usize, max_depth: usize) -> Mapping {
+        let mut phenotype = String::new();
+        let mut used_codons = 0;
+        let mut depth = 0;
+        let mut pending = vec![(Symbol::NonTerminal(0), 0)];
+
+        while let Some((symbol, symbol_depth)) = pending.pop() {
+            let rule = match symbol {
+                Symbol::Terminal(terminal) => {
+                    phenotype.push_str(&terminal);
+                    continue;
+                }
+                Symbol::NonTerminal(rule) => &self.rules[rule],
+            };
+            if symbol_depth > max_depth {
+                return Mapping::Invalid(InvalidReason::MaxDepthExceeded);
+            }
+            depth = depth.max(symbol_depth);
+
+            let production = if rule.productions.len() == 1 {
+                &rule.productions[0]
+            } else {
+                if codons.is_empty() || used_codons >= codons.len() * (max_wraps + 1) {
+                    return Mapping::Invalid(InvalidReason::CodonsExhausted);
+                }
+                let codon = codons[used_codons % codons.len()] as usize;
+                used_codons += 1;
+                &rule.productions[codon % rule.productions.len()]
+            };
+            pending.extend(
+                production
+                    .iter()
+                    .rev()
+                    .map(|symbol| (symbol.clone(), symbol_depth + 1)),
+            );
+        }
+
+        Mapping::Valid {
+            phenotype,
+            used_codons,
+            depth,
+        }
+    }
+}
+
+/// Returns if `token` has the form `<name>`.
+fn is_non_terminal(token: &str) -> bool {
+    token.len() > 2 && token.starts_with('<') && token.ends_with('>')
+}
+
+fn parse_error(line: usize, message: String) -> GrammarError {
+    GrammarError::Parse { line, message }
+}
+
+/// Splits the productions of a line by `|` and every production by whitespace, honouring the quotes.
+fn tokenize(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut productions = vec![Vec::new()];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '|' => productions.push(Vec::new()),
+            '"' | '\'' => {
+                let mut token = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => token.push(next),
+                        None => return Err(format!("unclosed quote {c}")),
+                    }
+                }
+                productions.last_mut().unwrap().push(token);
+            }
+            _ => {
+                let mut token = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || next == '|' {
+                        break;
+                    }
+                    token.push(next);
+                    chars.next();
+                }
+                productions.last_mut().unwrap().push(token);
+            }
+        }
+    }
+    Ok(productions)
+}
+
+/// Reasons of an invalid mapping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidReason {
+    /// The codons were exhausted after every wrap with non-terminals still to expand.
+    CodonsExhausted,
+    /// The derivation tree exceeded the maximum depth.
+    MaxDepthExceeded,
+}
+
+/// Result of mapping the codons through a grammar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mapping {
+    Valid {
+        /// The derived string.
+        phenotype: String,
+        /// Number of codons consumed, counting the wraps.
+        used_codons: usize,
+        /// Depth of the derivation tree.
+        depth: usize,
+    },
+    Invalid(InvalidReason),
+}
+
+/// Definition of a problem solved with `GrammaticalGene`.
+///
+/// # Examples
+///
+/// ```
+/// use easy_ga::genotypes::{Grammar, GrammaticalGene, GrammaticalProblem};
+/// use easy_ga::GeneticAlgorithm;
+/// use std::sync::OnceLock;
+///
+/// struct Expression;
+///
+/// impl GrammaticalProblem for Expression {
+///     fn grammar() -> &'static Grammar {
+///         static GRAMMAR: OnceLock<Grammar> = OnceLock::new();
+///         GRAMMAR.get_or_init(|| {
+///             Grammar::parse("<e> ::= <e> + <e> | <e> * <e> | x | 1").unwrap()
+///         })
+///     }
+///
+///     fn fitness(phenotype: &str) -> f64 {
+///         -(phenotype.len() as f64 - 9.0).abs()
+///     }
+/// }
+///
+/// let (gene, _) = GeneticAlgorithm::<GrammaticalGene<Expression>>::new()
+///     .iterations(10)
+///     .run();
+/// println!("{:?}", gene.phenotype());
+/// ```
+pub trait GrammaticalProblem {
+    /// Minimum number of codons of the initial genes.
+    const MIN_INIT_CODONS: usize = 20;
+    /// Maximum number of codons of the initial genes.
+    const MAX_INIT_CODONS: usize = 100;
+    /// Maximum number of codons the crossover can produce.
+    const MAX_CODONS: usize = 1000;
+    /// Every codon is a random value in `0..=CODON_MAX`.
+    const CODON_MAX: u32 = 255;
+    /// Number of times the codons can be reused from the beginning when they are exhausted.
+    const MAX_WRAPS: usize = 2;
+    /// Maximum depth of the derivation tree.
+    const MAX_DEPTH: usize = 20;
+    /// Fitness of the invalid genes. It is finite so the mean fitness and the fitness-proportional selections
+    /// keep working with invalid genes in the generation.
+    const INVALID_FITNESS: f64 = -1.0e12;
+
+    /// The grammar mapping the codons.
+    fn grammar() -> &'static Grammar;
+
+    /// The fitness function of the valid genes.
+    fn fitness(phenotype: &str) -> f64;
+}
+
+/// Grammatical evolution gene: integer codons mapped through the grammar of its `GrammaticalProblem`.
+pub struct GrammaticalGene<P: GrammaticalProblem> {
+    /// The codons.
+    codons: Vec<u32>,
+    /// The result of mapping the codons.
+    mapping: Mapping,
+    /// The fitness value.
+    fitness: f64,
+    problem: PhantomData<P>,
+}
+
+impl<P: GrammaticalProblem> GrammaticalGene<P> {
+    /// Creates a new gene with specific codons.
+    pub fn from_codons(codons: Vec<u32>) -> Self {
+        let mapping = P::grammar().map(&codons, P::MAX_WRAPS, P::MAX_DEPTH);
+        GrammaticalGene {
+            codons,
+            mapping,
+            fitness: 0.0,
+            problem: PhantomData,
+        }
+    }
+
+    /// Returns the codons.
+    pub fn codons(&self) -> &[u32] {
+        &self.codons
+    }
+
+    /// Returns the result of mapping the codons.
+    pub fn mapping(&self) -> &Mapping {
+        &self.mapping
+    }
+
+    /// Returns the derived string, or `None` if the gene is invalid.
+    pub fn phenotype(&self) -> Option<&str> {
+        match &self.mapping {
+            Mapping::Valid { phenotype, .. } => Some(phenotype),
+            Mapping::Invalid(_) => None,
+        }
+    }
+
+    /// Returns if the codons map to a string.
+    pub fn is_valid(&self) -> bool {
+        matches!(self.mapping, Mapping::Valid { .. })
+    }
+
+    /// Returns why the gene is invalid, or `None` if it is valid.
+    pub fn invalid_reason(&self) -> Option<InvalidReason> {
+        match self.mapping {
+            Mapping::Valid { .. } => None,
+            Mapping::Invalid(reason) => Some(reason),
+        }
+    }
+
+    /// One-point crossover with a different point in every parent, so the child can change its length. The points
+    /// are chosen inside the used codons of valid parents.
+    pub fn one_point_crossover(&self, other: &Self) -> Self {
+        let mut rng = rand::thread_rng();
+        let a = rng.gen_range(0..=self.effective_len());
+        let b = rng.gen_range(0..=other.effective_len());
+        let mut codons = self.codons[..a].to_vec();
+        codons.extend_from_slice(&other.codons[b..]);
+        codons.truncate(P::MAX_CODONS);
+        Self::from_codons(codons)
+    }
+
+    /// Int-flip mutation. Every codon is replaced with a random one with probability `probability`.
+    pub fn int_flip_mutation(&mut self, probability: f64) {
+        let mut rng = rand::thread_rng();
+        for codon in self.codons.iter_mut() {
+            if rng.gen_bool(probability) {
+                *codon = rng.gen_range(0..=P::CODON_MAX);
+            }
+        }
+        self.mapping = P::grammar().map(&self.codons, P::MAX_WRAPS, P::MAX_DEPTH);
+    }
+
+    /// Number of codons used by the mapping without wrapping, or every codon if the gene is invalid.
+    fn effective_len(&self) -> usize {
+        match self.mapping {
+            Mapping::Valid { used_codons, .. } => used_codons.min(self.codons.len()),
+            Mapping::Invalid(_) => self.codons.len(),
+        }
+    }
+}
+
+/// Returns the number of invalid genes of a generation. The optimisers save it in
+/// `GenerationStatistics::invalid_genes` after evaluating every generation.
+pub fn invalid_genes<P: GrammaticalProblem>(generation: &[GrammaticalGene<P>]) -> usize {
+    generation.iter().filter(|gene| !gene.is_valid()).count()
+}
+
+impl<P: GrammaticalProblem> Gene for GrammaticalGene<P> {
+    fn init() -> Self {
+        let mut rng = rand::thread_rng();
+        let length = rng.gen_range(P::MIN_INIT_CODONS..=P::MAX_INIT_CODONS.max(P::MIN_INIT_CODONS));
+        let codons = (0..length)
+            .map(|_| rng.gen_range(0..=P::CODON_MAX))
+            .collect();
+        Self::from_codons(codons)
+    }
+
+    fn calculate_fitness(&mut self) -> f64 {
+        self.fitness = match self.phenotype() {
+            Some(phenotype) => P::fitness(phenotype),
+            None => P::INVALID_FITNESS,
+        };
+        self.fitness
+    }
+
+    fn crossover(&self, other: &Self) -> Self {
+        self.one_point_crossover(other)
+    }
+
+    fn mutate(&mut self) {
+        let probability = 1.0 / self.codons.len().max(1) as f64;
+        self.int_flip_mutation(probability);
+    }
+
+    fn get_fitness(&self) -> f64 {
+        self.fitness
+    }
+
+    fn is_valid(&self) -> bool {
+        matches!(self.mapping, Mapping::Valid { .. })
+    }
+}
+
+impl<P: GrammaticalProblem> Clone for GrammaticalGene<P> {
+    fn clone(&self) -> Self {
+        GrammaticalGene {
+            codons: self.codons.clone(),
+            mapping: self.mapping.clone(),
+            fitness: self.fitness,
+            problem: PhantomData,
+        }
+    }
+}
+
+impl<P: GrammaticalProblem> PartialEq for GrammaticalGene<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.codons == other.codons
+    }
+}
+
+impl<P: GrammaticalProblem> fmt::Debug for GrammaticalGene<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GrammaticalGene({:?})", self.mapping)
+    }
+}
@@ -0,0 +1,392 @@
+//! This module contains an integer and categorical vector genotype with a domain per locus.
+
+use core::fmt;
+use rand::Rng;
+use std::sync::Arc;
+
+use crate::operators::{CrossoverOperator, MutationOperator};
+use crate::Gene;
+
+/// Crossover operators for `IntegerVectorGene`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntegerVectorCrossover {
+    /// Every locus of the child is taken from `self` or `other` with the same probability.
+    Uniform,
+}
+
+/// Mutation operators for `IntegerVectorGene`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntegerVectorMutation {
+    /// Creep mutation of the integer loci with a random non-zero step in `-step..=step`.
+    Creep { step: i64 },
+    /// Random-reset mutation of the categorical and value loci.
+    RandomReset,
+    /// Creep mutation of the integer loci followed by random-reset mutation of the others, as `Gene::mutate` does
+    /// with `P::CREEP_STEP`.
+    CreepAndRandomReset { step: i64 },
+}
+
+impl<P: IntegerVectorProblem> CrossoverOperator<IntegerVectorGene<P>> for IntegerVectorCrossover {
+    fn crossover(
+        &self,
+        parent1: &IntegerVectorGene<P>,
+        parent2: &IntegerVectorGene<P>,
+    ) -> IntegerVectorGene<P> {
+        match self {
+            IntegerVectorCrossover::Uniform => parent1.uniform_crossover(parent2),
+        }
+    }
+}
+
+/// Mutates every locus with probability `P::MUTATION_PROBABILITY`.
+impl<P: IntegerVectorProblem> MutationOperator<IntegerVectorGene<P>> for IntegerVectorMutation {
+    fn mutate(&self, gene: &mut IntegerVectorGene<P>) {
+        match self {
+            IntegerVectorMutation::Creep { step } => {
+                gene.creep_mutation(P::MUTATION_PROBABILITY, *step)
+            }
+            IntegerVectorMutation::RandomReset => {
+                gene.random_reset_mutation(P::MUTATION_PROBABILITY)
+            }
+            IntegerVectorMutation::CreepAndRandomReset { step } => {
+                gene.creep_mutation(P::MUTATION_PROBABILITY, *step);
+                gene.random_reset_mutation(P::MUTATION_PROBABILITY);
+            }
+        }
+    }
+}
+
+/// Domain of a locus of `IntegerVectorGene`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Domain {
+    /// Integer in `min..=max`. Mutated with creep mutation.
+    Integer { min: i64, max: i64 },
+    /// One of the categories, stored as its index. Mutated with random-reset mutation.
+    Categorical(&'static [&'static str]),
+    /// One of the allowed integers, stored as the integer itself. Mutated with random-reset mutation.
+    Values(&'static [i64]),
+}
+
+impl Domain {
+    /// Returns if `value` belongs to the domain.
+    pub fn contains(&self, value: i64) -> bool {
+        match self {
+            Domain::Integer { min, max } => (*min..=*max).contains(&value),
+            Domain::Categorical(categories) => value >= 0 && (value as usize) < categories.len(),
+            Domain::Values(values) => values.contains(&value),
+        }
+    }
+
+    /// Returns a random value of the domain.
+    ///
+    /// # Panics
+    ///
+    /// If the domain is empty.
+    pub fn random(&self) -> i64 {
+        let mut rng = rand::thread_rng();
+        match self {
+            Domain::Integer { min, max } => rng.gen_range(*min..=*max),
+            Domain::Categorical(categories) => rng.gen_range(0..categories.len()) as i64,
+            Domain::Values(values) => values[rng.gen_range(0..values.len())],
+        }
+    }
+}
+
+/// Definition of a problem solved with `IntegerVectorGene`.
+///
+/// # Examples
+///
+/// ```
+/// use easy_ga::genotypes::{Domain, IntegerVectorGene, IntegerVectorProblem};
+/// use easy_ga::GeneticAlgorithm;
+///
+/// struct Hyperparameters;
+///
+/// impl IntegerVectorProblem for Hyperparameters {
+///     const DOMAINS: &'static [Domain] = &[
+///         Domain::Integer { min: 1, max: 8 },
+///         Domain::Categorical(&["relu", "tanh", "sigmoid"]),
+///     ];
+///
+///     fn fitness(gene: &IntegerVectorGene<Self>) -> f64 {
+///         let layers = gene.get(0) as f64;
+///         let bonus = if gene.category(1) == Some("relu") { 1.0 } else { 0.0 };
+///         bonus - (layers - 4.0).abs()
+///     }
+/// }
+///
+/// let (gene, _) = GeneticAlgorithm::<IntegerVectorGene<Hyperparameters>>::new()
+///     .iterations(10)
+///     .run();
+/// ```
+///
+/// # Notes
+///
+/// The associated constants and `fitness` are static. A problem with runtime data implements `evaluate` instead and
+/// its genes carry the problem instance, set with `IntegerVectorGene::with_problem` and created with
+/// `GeneticAlgorithm::gene_init`. The domains, the constraint violation, the repair and the behaviour are always static.
+pub trait IntegerVectorProblem {
+    /// Domain of every locus. Its length is the number of loci.
+    const DOMAINS: &'static [Domain];
+    /// Probability of mutating every locus in `Gene::mutate`. By default one locus per gene is mutated on average.
+    const MUTATION_PROBABILITY: f64 = 1.0 / Self::DOMAINS.len() as f64;
+    /// Maximum step of the creep mutation of the integer loci.
+    const CREEP_STEP: i64 = 1;
+
+    /// The fitness function of the genes without a problem instance.
+    ///
+    /// # Panics
+    ///
+    /// By default, because a problem must implement either `fitness` or `evaluate`.
+    fn fitness(_gene: &IntegerVectorGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        panic!(
+            "IntegerVectorProblem::fitness not implemented and the gene has no problem instance"
+        );
+    }
+
+    /// The fitness function of the genes with a problem instance, that can use the runtime data of `self`. By default
+    /// it is `fitness`.
+    fn evaluate(&self, gene: &IntegerVectorGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        Self::fitness(gene)
+    }
+
+    /// The constraint violation used by `Gene::constraint_violation`. By default every gene is feasible.
+    fn constraint_violation(_gene: &IntegerVectorGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        0.0
+    }
+
+    /// The repair used by `Gene::repair`. By default the genes are not repaired.
+    fn repair(_gene: &mut IntegerVectorGene<Self>)
+    where
+        Self: Sized,
+    {
+    }
+
+    /// The behaviour descriptor used by `Gene::behaviour`. By default the genes do not define a behaviour.
+    fn behaviour(_gene: &IntegerVectorGene<Self>) -> Option<Vec<f64>>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// Vector of integer and categorical loci with the domain of every locus defined by its `IntegerVectorProblem`.
+pub struct IntegerVectorGene<P: IntegerVectorProblem> {
+    /// The values, one per locus. The categorical loci store the index of their category.
+    values: Vec<i64>,
+    /// The fitness value.
+    fitness: f64,
+    /// The problem instance evaluating the gene, `None` to use `IntegerVectorProblem::fitness`.
+    problem: Option<Arc<P>>,
+}
+
+impl<P: IntegerVectorProblem> IntegerVectorGene<P> {
+    /// Creates a new gene with specific values.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `values` is not the number of loci or any value is outside its domain.
+    pub fn from_values(values: Vec<i64>) -> Self {
+        assert_eq!(values.len(), P::DOMAINS.len(), "Wrong number of values");
+        assert!(
+            values
+                .iter()
+                .zip(P::DOMAINS.iter())
+                .all(|(value, domain)| domain.contains(*value)),
+            "Value outside its domain"
+        );
+        IntegerVectorGene {
+            values,
+            fitness: 0.0,
+            problem: None,
+        }
+    }
+
+    /// Sets the problem instance evaluating the gene with `IntegerVectorProblem::evaluate`. The children of the
+    /// crossover take the problem instance of `self`.
+    pub fn with_problem(mut self, problem: Arc<P>) -> Self {
+        self.problem = Some(problem);
+        self
+    }
+
+    /// Returns the problem instance evaluating the gene, if any.
+    pub fn get_problem(&self) -> Option<&Arc<P>> {
+        self.problem.as_ref()
+    }
+
+    /// Returns the values.
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+
+    /// Returns the value of `locus`.
+    pub fn get(&self, locus: usize) -> i64 {
+        self.values[locus]
+    }
+
+    /// Returns the category of `locus`, or `None` if it is an integer locus.
+    pub fn category(&self, locus: usize) -> Option<&'static str> {
+        match P::DOMAINS[locus] {
+            Domain::Integer { .. } | Domain::Values(_) => None,
+            Domain::Categorical(categories) => Some(categories[self.values[locus] as usize]),
+        }
+    }
+
+    /// Returns the number of loci.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns if the gene has no loci.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the sum of the absolute differences of the integer and value loci plus the number of different
+    /// categorical loci.
+    pub fn mixed_distance(&self, other: &Self) -> f64 {
+        self.values
+            .iter()
+            .zip(other.values.iter())
+            .zip(P::DOMAINS.iter())
+            .map(|((a, b), domain)| match domain {
+                Domain::Integer { .. } | Domain::Values(_) => a.abs_diff(*b) as f64,
+                Domain::Categorical(_) => (a != b) as u8 as f64,
+            })
+            .sum()
+    }
+
+    /// Uniform crossover.
+    pub fn uniform_crossover(&self, other: &Self) -> Self {
+        let mut rng = rand::thread_rng();
+        let values = self
+            .values
+            .iter()
+            .zip(other.values.iter())
+            .map(|(a, b)| if rng.gen_bool(0.5) { *a } else { *b })
+            .collect();
+        let mut child = Self::from_values(values);
+        child.problem = self.problem.clone();
+        child
+    }
+
+    /// Creep mutation. Every integer locus is mutated with probability `probability` adding a random non-zero step
+    /// in `-step..=step`, clamped to its domain.
+    pub fn creep_mutation(&mut self, probability: f64, step: i64) {
+        let mut rng = rand::thread_rng();
+        for (value, domain) in self.values.iter_mut().zip(P::DOMAINS.iter()) {
+            if let Domain::Integer { min, max } = domain {
+                if step > 0 && rng.gen_bool(probability) {
+                    let creep = rng.gen_range(1..=step) * if rng.gen_bool(0.5) { 1 } else { -1 };
+                    *value = (*value + creep).clamp(*min, *max);
+                }
+            }
+        }
+    }
+
+    /// Random-reset mutation. Every categorical and value locus is mutated with probability `probability` choosing a
+    /// random category or allowed integer.
+    pub fn random_reset_mutation(&mut self, probability: f64) {
+        let mut rng = rand::thread_rng();
+        for (value, domain) in self.values.iter_mut().zip(P::DOMAINS.iter()) {
+            if let Domain::Categorical(_) | Domain::Values(_) = domain {
+                if rng.gen_bool(probability) {
+                    *value = domain.random();
+                }
+            }
+        }
+    }
+}
+
+impl<P: IntegerVectorProblem> Gene for IntegerVectorGene<P> {
+    fn init() -> Self {
+        let values = P::DOMAINS.iter().map(|domain| domain.random()).collect();
+        Self::from_values(values)
+    }
+
+    fn calculate_fitness(&mut self) -> f64 {
+        self.fitness = match &self.problem {
+            Some(problem) => problem.evaluate(self),
+            None => P::fitness(self),
+        };
+        self.fitness
+    }
+
+    fn crossover(&self, other: &Self) -> Self {
+        self.uniform_crossover(other)
+    }
+
+    fn mutate(&mut self) {
+        self.creep_mutation(P::MUTATION_PROBABILITY, P::CREEP_STEP);
+        self.random_reset_mutation(P::MUTATION_PROBABILITY);
+    }
+
+    fn get_fitness(&self) -> f64 {
+        self.fitness
+    }
+
+    fn distance(&self, other: &Self) -> Option<f64> {
+        Some(self.mixed_distance(other))
+    }
+
+    fn constraint_violation(&self) -> f64 {
+        P::constraint_violation(self)
+    }
+
+    fn repair(&mut self) {
+        P::repair(self)
+    }
+
+    fn behaviour(&self) -> Option<Vec<f64>> {
+        P::behaviour(self)
+    }
+}
+
+impl<P: IntegerVectorProblem> Clone for IntegerVectorGene<P> {
+    fn clone(&self) -> Self {
+        IntegerVectorGene {
+            values: self.values.clone(),
+            fitness: self.fitness,
+            problem: self.problem.clone(),
+        }
+    }
+}
+
+impl<P: IntegerVectorProblem> PartialEq for IntegerVectorGene<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<P: IntegerVectorProblem> fmt::Debug for IntegerVectorGene<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IntegerVectorGene({self})")
+    }
+}
+
+/// Display trait implementation for IntegerVectorGene, printing the integers and the names of the categories.
+impl<P: IntegerVectorProblem> fmt::Display for IntegerVectorGene<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for locus in 0..self.len() {
+            if locus > 0 {
+                write!(f, ", ")?;
+            }
+            match self.category(locus) {
+                Some(category) => write!(f, "{category}")?,
+                None => write!(f, "{}", self.values[locus])?,
+            }
+        }
+        write!(f, "]")
+    }
+}
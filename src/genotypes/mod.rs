@@ -0,0 +1,24 @@
+//! This module contains ready to use genotypes with their standard operators.
+
+pub mod bit_string;
+pub mod grammatical;
+pub mod integer_vector;
+pub mod permutation;
+pub mod real_vector;
+pub mod tree;
+
+pub use bit_string::{BitStringCrossover, BitStringFitness, BitStringGene, BitStringProblem};
+pub use grammatical::{
+    Grammar, GrammarError, GrammaticalGene, GrammaticalProblem, InvalidReason, Mapping,
+};
+pub use integer_vector::{
+    Domain, IntegerVectorCrossover, IntegerVectorGene, IntegerVectorMutation, IntegerVectorProblem,
+};
+pub use permutation::{
+    PermutationCrossover, PermutationGene, PermutationMutation, PermutationProblem,
+};
+pub use real_vector::{
+    BoundHandling, DifferentialCrossover, RealVectorCrossover, RealVectorFitness, RealVectorGene,
+    RealVectorMutation, RealVectorProblem,
+};
+pub use tree::{PrimitiveSetError, TreeGene, TreeMutation, TreeProblem};
@@ -0,0 +1,493 @@
+//! This module contains a permutation genotype for ordering problems.
+
+use core::fmt;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::sync::Arc;
+
+use crate::operators::{CrossoverOperator, MutationOperator};
+use crate::Gene;
+
+/// Crossover operators for `PermutationGene`. All of them return a valid permutation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PermutationCrossover {
+    /// Order crossover (OX). The child keeps a random segment of `self` and the rest of the elements in the order they
+    /// appear in `other`.
+    Order,
+    /// Partially mapped crossover (PMX). The child keeps a random segment of `self` and the positions of `other`
+    /// outside it, mapping the conflicting elements.
+    PartiallyMapped,
+    /// Cycle crossover (CX). Every element keeps the position it has in one of the parents, alternating the parents in
+    /// every cycle.
+    Cycle,
+    /// Edge recombination crossover. The child is built from the adjacencies of both parents.
+    EdgeRecombination,
+}
+
+/// Mutation operators for `PermutationGene`. All of them return a valid permutation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PermutationMutation {
+    /// Swaps two random elements.
+    Swap,
+    /// Moves a random element to a random position.
+    Insertion,
+    /// Shuffles a random segment.
+    Scramble,
+    /// Reverses a random segment.
+    Inversion,
+}
+
+impl<P: PermutationProblem> CrossoverOperator<PermutationGene<P>> for PermutationCrossover {
+    fn crossover(
+        &self,
+        parent1: &PermutationGene<P>,
+        parent2: &PermutationGene<P>,
+    ) -> PermutationGene<P> {
+        match self {
+            PermutationCrossover::Order => parent1.order_crossover(parent2),
+            PermutationCrossover::PartiallyMapped => parent1.partially_mapped_crossover(parent2),
+            PermutationCrossover::Cycle => parent1.cycle_crossover(parent2),
+            PermutationCrossover::EdgeRecombination => {
+                parent1.edge_recombination_crossover(parent2)
+            }
+        }
+    }
+}
+
+impl<P: PermutationProblem> MutationOperator<PermutationGene<P>> for PermutationMutation {
+    fn mutate(&self, gene: &mut PermutationGene<P>) {
+        match self {
+            PermutationMutation::Swap => gene.swap_mutation(),
+            PermutationMutation::Insertion => gene.insertion_mutation(),
+            PermutationMutation::Scramble => gene.scramble_mutation(),
+            PermutationMutation::Inversion => gene.inversion_mutation(),
+        }
+    }
+}
+
+/// Definition of a problem solved with `PermutationGene`.
+///
+/// # Examples
+///
+/// ```
+/// use easy_ga::genotypes::{PermutationGene, PermutationProblem};
+/// use easy_ga::GeneticAlgorithm;
+///
+/// struct Sorting;
+///
+/// impl PermutationProblem for Sorting {
+///     const LENGTH: usize = 10;
+///
+///     fn fitness(gene: &PermutationGene<Self>) -> f64 {
+///         gene.order().iter().enumerate().filter(|(i, x)| i == *x).count() as f64
+///     }
+/// }
+///
+/// let (gene, _) = GeneticAlgorithm::<PermutationGene<Sorting>>::new()
+///     .iterations(10)
+///     .run();
+/// ```
+///
+/// # Notes
+///
+/// The associated constants and `fitness` are static. A problem with runtime data, e.g. the distances of a TSP loaded
+/// from a file, implements `evaluate` instead and its genes carry the problem instance, set with
+/// `PermutationGene::with_problem` and created with `GeneticAlgorithm::gene_init`. The constraint violation, the repair
+/// and the behaviour are always static.
+pub trait PermutationProblem {
+    /// Number of elements of every permutation. The elements are `0..LENGTH`.
+    const LENGTH: usize;
+    /// Crossover operator used by `Gene::crossover`.
+    const CROSSOVER: PermutationCrossover = PermutationCrossover::Order;
+    /// Mutation operator used by `Gene::mutate`.
+    const MUTATION: PermutationMutation = PermutationMutation::Swap;
+
+    /// The fitness function of the genes without a problem instance.
+    ///
+    /// # Panics
+    ///
+    /// By default, because a problem must implement either `fitness` or `evaluate`.
+    fn fitness(_gene: &PermutationGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        panic!("PermutationProblem::fitness not implemented and the gene has no problem instance");
+    }
+
+    /// The fitness function of the genes with a problem instance, that can use the runtime data of `self`. By default
+    /// it is `fitness`.
+    fn evaluate(&self, gene: &PermutationGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        Self::fitness(gene)
+    }
+
+    /// The constraint violation used by `Gene::constraint_violation`. By default every gene is feasible.
+    fn constraint_violation(_gene: &PermutationGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        0.0
+    }
+
+    /// The repair used by `Gene::repair`. By default the genes are not repaired.
+    fn repair(_gene: &mut PermutationGene<Self>)
+    where
+        Self: Sized,
+    {
+    }
+
+    /// The behaviour descriptor used by `Gene::behaviour`. By default the genes do not define a behaviour.
+    fn behaviour(_gene: &PermutationGene<Self>) -> Option<Vec<f64>>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// Permutation of the elements `0..P::LENGTH`.
+pub struct PermutationGene<P: PermutationProblem> {
+    /// The elements in their order.
+    order: Vec<usize>,
+    /// The fitness value.
+    fitness: f64,
+    /// The problem instance evaluating the gene, `None` to use `PermutationProblem::fitness`.
+    problem: Option<Arc<P>>,
+}
+
+impl<P: PermutationProblem> PermutationGene<P> {
+    /// Creates a new gene with the identity permutation.
+    pub fn identity() -> Self {
+        Self::new((0..P::LENGTH).collect())
+    }
+
+    /// Creates a new gene with a specific order.
+    ///
+    /// # Panics
+    ///
+    /// If `order` is not a permutation of `0..P::LENGTH`.
+    pub fn from_order(order: Vec<usize>) -> Self {
+        assert!(is_permutation(&order, P::LENGTH), "Not a valid permutation");
+        Self::new(order)
+    }
+
+    /// Sets the problem instance evaluating the gene with `PermutationProblem::evaluate`. The children of the
+    /// crossover take the problem instance of `self`.
+    pub fn with_problem(mut self, problem: Arc<P>) -> Self {
+        self.problem = Some(problem);
+        self
+    }
+
+    /// Returns the problem instance evaluating the gene, if any.
+    pub fn get_problem(&self) -> Option<&Arc<P>> {
+        self.problem.as_ref()
+    }
+
+    /// Returns the elements in their order.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        P::LENGTH
+    }
+
+    /// Returns if the permutation has no elements.
+    pub fn is_empty(&self) -> bool {
+        P::LENGTH == 0
+    }
+
+    /// Returns if the gene is a permutation of `0..P::LENGTH`.
+    pub fn is_valid(&self) -> bool {
+        is_permutation(&self.order, P::LENGTH)
+    }
+
+    /// Returns the number of positions with a different element than `other`.
+    pub fn position_distance(&self, other: &Self) -> usize {
+        self.order
+            .iter()
+            .zip(other.order.iter())
+            .filter(|(a, b)| a != b)
+            .count()
+    }
+
+    /// Order crossover (OX).
+    pub fn order_crossover(&self, other: &Self) -> Self {
+        let n = P::LENGTH;
+        let (start, end) = random_segment(n);
+        let mut used = vec![false; n];
+        let mut child = vec![usize::MAX; n];
+        for i in start..end {
+            child[i] = self.order[i];
+            used[self.order[i]] = true;
+        }
+
+        let mut position = end % n.max(1);
+        for offset in 0..n {
+            let element = other.order[(end + offset) % n];
+            if used[element] {
+                continue;
+            }
+            child[position] = element;
+            used[element] = true;
+            position = (position + 1) % n;
+        }
+        self.child(child)
+    }
+
+    /// Partially mapped crossover (PMX).
+    pub fn partially_mapped_crossover(&self, other: &Self) -> Self {
+        let n = P::LENGTH;
+        let (start, end) = random_segment(n);
+        let other_positions = positions(&other.order);
+        let mut child: Vec<Option<usize>> = vec![None; n];
+        let mut used = vec![false; n];
+        for i in start..end {
+            child[i] = Some(self.order[i]);
+            used[self.order[i]] = true;
+        }
+
+        for i in start..end {
+            let element = other.order[i];
+            if used[element] {
+                continue;
+            }
+            // Follows the mapping until a position outside the segment is found.
+            let mut position = i;
+            while (start..end).contains(&position) {
+                position = other_positions[self.order[position]];
+            }
+            child[position] = Some(element);
+            used[element] = true;
+        }
+
+        let child = child
+            .into_iter()
+            .zip(other.order.iter())
+            .map(|(element, other_element)| element.unwrap_or(*other_element))
+            .collect();
+        self.child(child)
+    }
+
+    /// Cycle crossover (CX).
+    pub fn cycle_crossover(&self, other: &Self) -> Self {
+        let n = P::LENGTH;
+        let self_positions = positions(&self.order);
+        let mut child = vec![usize::MAX; n];
+        let mut visited = vec![false; n];
+        let mut from_self = true;
+        for start in 0..n {
+            if visited[start] {
+                continue;
+            }
+            let mut position = start;
+            while !visited[position] {
+                visited[position] = true;
+                child[position] = if from_self {
+                    self.order[position]
+                } else {
+                    other.order[position]
+                };
+                position = self_positions[other.order[position]];
+            }
+            from_self = !from_self;
+        }
+        self.child(child)
+    }
+
+    /// Edge recombination crossover.
+    pub fn edge_recombination_crossover(&self, other: &Self) -> Self {
+        let n = P::LENGTH;
+        if n == 0 {
+            return self.child(Vec::new());
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut edges: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for parent in [&self.order, &other.order] {
+            for i in 0..n {
+                let element = parent[i];
+                for neighbour in [parent[(i + n - 1) % n], parent[(i + 1) % n]] {
+                    if neighbour != element && !edges[element].contains(&neighbour) {
+                        edges[element].push(neighbour);
+                    }
+                }
+            }
+        }
+
+        let mut used = vec![false; n];
+        let mut child = Vec::with_capacity(n);
+        let mut current = self.order[0];
+        loop {
+            child.push(current);
+            used[current] = true;
+            for neighbours in edges.iter_mut() {
+                neighbours.retain(|&neighbour| neighbour != current);
+            }
+            if child.len() == n {
+                break;
+            }
+
+            let fewest_edges = edges[current]
+                .iter()
+                .map(|&neighbour| edges[neighbour].len())
+                .min();
+            current = match fewest_edges {
+                Some(fewest_edges) => {
+                    let candidates: Vec<usize> = edges[current]
+                        .iter()
+                        .copied()
+                        .filter(|&neighbour| edges[neighbour].len() == fewest_edges)
+                        .collect();
+                    *candidates.choose(&mut rng).unwrap()
+                }
+                None => {
+                    let unused: Vec<usize> = (0..n).filter(|&element| !used[element]).collect();
+                    *unused.choose(&mut rng).unwrap()
+                }
+            };
+        }
+        self.child(child)
+    }
+
+    /// Swaps two random elements.
+    pub fn swap_mutation(&mut self) {
+        if P::LENGTH < 2 {
+            return;
+        }
+        let mut rng = rand::thread_rng();
+        let (a, b) = (rng.gen_range(0..P::LENGTH), rng.gen_range(0..P::LENGTH));
+        self.order.swap(a, b);
+    }
+
+    /// Moves a random element to a random position.
+    pub fn insertion_mutation(&mut self) {
+        if P::LENGTH < 2 {
+            return;
+        }
+        let mut rng = rand::thread_rng();
+        let element = self.order.remove(rng.gen_range(0..P::LENGTH));
+        self.order.insert(rng.gen_range(0..P::LENGTH), element);
+    }
+
+    /// Shuffles a random segment.
+    pub fn scramble_mutation(&mut self) {
+        let (start, end) = random_segment(P::LENGTH);
+        self.order[start..end].shuffle(&mut rand::thread_rng());
+    }
+
+    /// Reverses a random segment.
+    pub fn inversion_mutation(&mut self) {
+        let (start, end) = random_segment(P::LENGTH);
+        self.order[start..end].reverse();
+    }
+
+    fn new(order: Vec<usize>) -> Self {
+        PermutationGene {
+            order,
+            fitness: 0.0,
+            problem: None,
+        }
+    }
+
+    /// Creates a child of `self` with a specific order, keeping the problem instance.
+    fn child(&self, order: Vec<usize>) -> Self {
+        let mut child = Self::new(order);
+        child.problem = self.problem.clone();
+        child
+    }
+}
+
+/// Returns a random segment `start..end` of a sequence of length `n`.
+fn random_segment(n: usize) -> (usize, usize) {
+    let mut rng = rand::thread_rng();
+    let (a, b) = (rng.gen_range(0..=n), rng.gen_range(0..=n));
+    (a.min(b), a.max(b))
+}
+
+/// Returns the position of every element of a permutation.
+fn positions(order: &[usize]) -> Vec<usize> {
+    let mut positions = vec![0; order.len()];
+    for (position, element) in order.iter().enumerate() {
+        positions[*element] = position;
+    }
+    positions
+}
+
+/// Returns if `order` is a permutation of `0..n`.
+fn is_permutation(order: &[usize], n: usize) -> bool {
+    let mut seen = vec![false; n];
+    order.len() == n
+        && order
+            .iter()
+            .all(|&element| element < n && !std::mem::replace(&mut seen[element], true))
+}
+
+impl<P: PermutationProblem> Gene for PermutationGene<P> {
+    fn init() -> Self {
+        let mut gene = Self::identity();
+        gene.order.shuffle(&mut rand::thread_rng());
+        gene
+    }
+
+    fn calculate_fitness(&mut self) -> f64 {
+        self.fitness = match &self.problem {
+            Some(problem) => problem.evaluate(self),
+            None => P::fitness(self),
+        };
+        self.fitness
+    }
+
+    fn crossover(&self, other: &Self) -> Self {
+        P::CROSSOVER.crossover(self, other)
+    }
+
+    fn mutate(&mut self) {
+        P::MUTATION.mutate(self)
+    }
+
+    fn get_fitness(&self) -> f64 {
+        self.fitness
+    }
+
+    fn distance(&self, other: &Self) -> Option<f64> {
+        Some(self.position_distance(other) as f64)
+    }
+
+    fn constraint_violation(&self) -> f64 {
+        P::constraint_violation(self)
+    }
+
+    fn repair(&mut self) {
+        P::repair(self)
+    }
+
+    fn behaviour(&self) -> Option<Vec<f64>> {
+        P::behaviour(self)
+    }
+}
+
+impl<P: PermutationProblem> Clone for PermutationGene<P> {
+    fn clone(&self) -> Self {
+        PermutationGene {
+            order: self.order.clone(),
+            fitness: self.fitness,
+            problem: self.problem.clone(),
+        }
+    }
+}
+
+impl<P: PermutationProblem> PartialEq for PermutationGene<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.order == other.order
+    }
+}
+
+impl<P: PermutationProblem> fmt::Debug for PermutationGene<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PermutationGene({:?})", self.order)
+    }
+}
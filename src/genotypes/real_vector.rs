@@ -0,0 +1,673 @@
+//! This module contains a real-valued vector genotype with per-dimension bounds.
+
+use core::fmt;
+use rand::Rng;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::operators::{self, CrossoverOperator, DiagonalCrossover, MutationOperator};
+use crate::Gene;
+
+/// Crossover operators for `RealVectorGene`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RealVectorCrossover {
+    /// Blend crossover (BLX-α). Every value is taken uniformly from the interval between both parents extended by
+    /// `alpha` times its length on each side.
+    Blend { alpha: f64 },
+    /// Arithmetic crossover. The child is `λ * self + (1 - λ) * other` with a random `λ` in `[0, 1]`.
+    Arithmetic,
+    /// Simulated binary crossover (SBX). The bigger `eta` is, the closer the child is to its parents.
+    SimulatedBinary { eta: f64 },
+}
+
+/// Mutation operators for `RealVectorGene`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RealVectorMutation {
+    /// Adds a normal noise with standard deviation `sigma` times the range of the dimension.
+    Gaussian { sigma: f64 },
+    /// Polynomial mutation. The bigger `eta` is, the smaller the perturbation is.
+    Polynomial { eta: f64 },
+    /// Self-adaptive Gaussian mutation. Every value is mutated with its own step size, encoded in the gene and mutated
+    /// first with the log-normal rule of evolution strategies. It ignores `P::MUTATION_PROBABILITY`. The same mutation is
+    /// used by `MutationAdaptation::SelfAdaptive` whatever `P::MUTATION` is.
+    SelfAdaptive,
+}
+
+impl<P: RealVectorProblem> CrossoverOperator<RealVectorGene<P>> for RealVectorCrossover {
+    fn crossover(
+        &self,
+        parent1: &RealVectorGene<P>,
+        parent2: &RealVectorGene<P>,
+    ) -> RealVectorGene<P> {
+        match self {
+            RealVectorCrossover::Blend { alpha } => parent1.blend_crossover(parent2, *alpha),
+            RealVectorCrossover::Arithmetic => parent1.arithmetic_crossover(parent2),
+            RealVectorCrossover::SimulatedBinary { eta } => {
+                parent1.simulated_binary_crossover(parent2, *eta)
+            }
+        }
+    }
+}
+
+/// Mutates every value with probability `P::MUTATION_PROBABILITY`.
+impl<P: RealVectorProblem> MutationOperator<RealVectorGene<P>> for RealVectorMutation {
+    fn mutate(&self, gene: &mut RealVectorGene<P>) {
+        match self {
+            RealVectorMutation::Gaussian { sigma } => {
+                gene.gaussian_mutation(P::MUTATION_PROBABILITY, *sigma)
+            }
+            RealVectorMutation::Polynomial { eta } => {
+                gene.polynomial_mutation(P::MUTATION_PROBABILITY, *eta)
+            }
+            RealVectorMutation::SelfAdaptive => gene.self_adaptive_mutation(),
+        }
+    }
+}
+
+impl<P: RealVectorProblem> CrossoverOperator<RealVectorGene<P>> for DiagonalCrossover {
+    fn crossover(
+        &self,
+        parent1: &RealVectorGene<P>,
+        parent2: &RealVectorGene<P>,
+    ) -> RealVectorGene<P> {
+        self.crossover_parents(&[parent1, parent2]).remove(0)
+    }
+
+    fn parents(&self) -> usize {
+        self.parents
+    }
+
+    fn crossover_parents(&self, parents: &[&RealVectorGene<P>]) -> Vec<RealVectorGene<P>> {
+        let segments = operators::diagonal_segments(P::BOUNDS.len(), parents.len());
+        (0..parents.len())
+            .map(|i| {
+                let values = segments
+                    .iter()
+                    .enumerate()
+                    .map(|(position, segment)| {
+                        parents[(i + segment) % parents.len()].values[position]
+                    })
+                    .collect();
+                RealVectorGene::from_values(values).inherit(parents)
+            })
+            .collect()
+    }
+}
+
+/// Differential crossover with three parents. The child is `parent1 + weight * (parent2 - parent3)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DifferentialCrossover {
+    /// The differential weight, usually in `0.0..=2.0`.
+    pub weight: f64,
+}
+
+impl<P: RealVectorProblem> CrossoverOperator<RealVectorGene<P>> for DifferentialCrossover {
+    /// With two parents the child is `parent1 + weight * (parent2 - parent1)`.
+    fn crossover(
+        &self,
+        parent1: &RealVectorGene<P>,
+        parent2: &RealVectorGene<P>,
+    ) -> RealVectorGene<P> {
+        self.crossover_parents(&[parent1, parent2, parent1])
+            .remove(0)
+    }
+
+    fn parents(&self) -> usize {
+        3
+    }
+
+    fn crossover_parents(&self, parents: &[&RealVectorGene<P>]) -> Vec<RealVectorGene<P>> {
+        let values = (0..P::BOUNDS.len())
+            .map(|i| {
+                parents[0].values[i] + self.weight * (parents[1].values[i] - parents[2].values[i])
+            })
+            .collect();
+        vec![RealVectorGene::from_values(values).inherit(parents)]
+    }
+}
+
+/// Strategies to bring back the values outside the bounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundHandling {
+    /// The value is set to the closest bound.
+    Clamp,
+    /// The value is mirrored back from the bound it crossed.
+    Reflect,
+    /// The value is replaced by a random value inside the bounds.
+    Resample,
+}
+
+impl BoundHandling {
+    /// Returns `value` inside `[lower, upper]` according to the strategy.
+    pub fn apply(&self, value: f64, lower: f64, upper: f64) -> f64 {
+        if value >= lower && value <= upper {
+            return value;
+        }
+
+        match self {
+            BoundHandling::Clamp => value.clamp(lower, upper),
+            BoundHandling::Reflect => {
+                let range = upper - lower;
+                if range <= 0.0 || !value.is_finite() {
+                    return lower;
+                }
+                let offset = (value - lower).rem_euclid(2.0 * range);
+                if offset > range {
+                    upper - (offset - range)
+                } else {
+                    lower + offset
+                }
+            }
+            BoundHandling::Resample => {
+                if upper > lower {
+                    rand::thread_rng().gen_range(lower..=upper)
+                } else {
+                    lower
+                }
+            }
+        }
+    }
+}
+
+/// Definition of a problem solved with `RealVectorGene`. A problem can also take its constants from another problem
+/// and evaluate the genes with a closure with `RealVectorFitness`.
+///
+/// # Examples
+///
+/// ```
+/// use easy_ga::genotypes::{RealVectorGene, RealVectorProblem};
+/// use easy_ga::GeneticAlgorithm;
+///
+/// struct Sphere;
+///
+/// impl RealVectorProblem for Sphere {
+///     const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0); 3];
+///
+///     fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+///         -gene.values().iter().map(|x| x * x).sum::<f64>()
+///     }
+/// }
+///
+/// let (gene, _) = GeneticAlgorithm::<RealVectorGene<Sphere>>::new()
+///     .iterations(10)
+///     .run();
+/// ```
+///
+/// # Notes
+///
+/// The associated constants and `fitness` are static. A problem with runtime data implements `evaluate` instead and
+/// its genes carry the problem instance, set with `RealVectorGene::with_problem` and created with
+/// `GeneticAlgorithm::gene_init`, or `ParticleSwarm::problem` for the particles. The bounds, the constraint violation,
+/// the repair and the behaviour are always static, and `DifferentialEvolution` and `CmaEs` always use `fitness`.
+pub trait RealVectorProblem {
+    /// Lower and upper bound of every dimension. Its length is the number of dimensions.
+    const BOUNDS: &'static [(f64, f64)];
+    /// Crossover operator used by `Gene::crossover`.
+    const CROSSOVER: RealVectorCrossover = RealVectorCrossover::SimulatedBinary { eta: 15.0 };
+    /// Mutation operator used by `Gene::mutate`.
+    const MUTATION: RealVectorMutation = RealVectorMutation::Polynomial { eta: 20.0 };
+    /// Probability of mutating every value in `Gene::mutate`. By default one value per gene is mutated on average.
+    const MUTATION_PROBABILITY: f64 = 1.0 / Self::BOUNDS.len() as f64;
+    /// Strategy to bring back the values outside the bounds after the crossover and the mutation.
+    const BOUND_HANDLING: BoundHandling = BoundHandling::Clamp;
+    /// Initial step size of every value for `RealVectorMutation::SelfAdaptive`, relative to the range of its dimension.
+    const INITIAL_STEP_SIZE: f64 = 0.1;
+    /// Minimum step size for `RealVectorMutation::SelfAdaptive`, relative to the range of its dimension.
+    const MIN_STEP_SIZE: f64 = 1e-8;
+
+    /// The fitness function of the genes without a problem instance.
+    ///
+    /// # Panics
+    ///
+    /// By default, because a problem must implement either `fitness` or `evaluate`.
+    fn fitness(_gene: &RealVectorGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        panic!("RealVectorProblem::fitness not implemented and the gene has no problem instance");
+    }
+
+    /// The fitness function of the genes with a problem instance, that can use the runtime data of `self`. By default
+    /// it is `fitness`.
+    fn evaluate(&self, gene: &RealVectorGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        Self::fitness(gene)
+    }
+
+    /// The constraint violation used by `Gene::constraint_violation`. By default every gene is feasible.
+    fn constraint_violation(_gene: &RealVectorGene<Self>) -> f64
+    where
+        Self: Sized,
+    {
+        0.0
+    }
+
+    /// The repair used by `Gene::repair`. By default the genes are not repaired.
+    fn repair(_gene: &mut RealVectorGene<Self>)
+    where
+        Self: Sized,
+    {
+    }
+
+    /// The behaviour descriptor used by `Gene::behaviour`. By default the genes do not define a behaviour.
+    fn behaviour(_gene: &RealVectorGene<Self>) -> Option<Vec<f64>>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// Fitness closure of a `RealVectorFitness` problem.
+type FitnessClosure<P> = Box<dyn Fn(&RealVectorGene<P>) -> f64 + Send + Sync>;
+
+/// A `RealVectorProblem` with the constants, the constraint violation, the repair and the behaviour of `B`, evaluating
+/// the genes with a closure. `B` does not need to implement `fitness`.
+///
+/// # Examples
+///
+/// ```
+/// use easy_ga::genotypes::{RealVectorFitness, RealVectorGene, RealVectorProblem};
+/// use easy_ga::GeneticAlgorithm;
+///
+/// struct Bounds;
+///
+/// impl RealVectorProblem for Bounds {
+///     const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0); 3];
+/// }
+///
+/// let (gene, _) = GeneticAlgorithm::<RealVectorGene<RealVectorFitness<Bounds>>>::new()
+///     .iterations(10)
+///     .gene_init(RealVectorGene::problem(|gene| {
+///         -gene.values().iter().map(|x| x * x).sum::<f64>()
+///     }))
+///     .run();
+/// ```
+pub struct RealVectorFitness<B: RealVectorProblem> {
+    /// The fitness function of the genes.
+    fitness: FitnessClosure<Self>,
+    /// The problem defining the constants.
+    constants: PhantomData<B>,
+}
+
+impl<B: RealVectorProblem> RealVectorFitness<B> {
+    /// Creates a new problem evaluating the genes with `fitness`.
+    pub fn new<F>(fitness: F) -> Self
+    where
+        F: Fn(&RealVectorGene<Self>) -> f64 + Send + Sync + 'static,
+    {
+        RealVectorFitness {
+            fitness: Box::new(fitness),
+            constants: PhantomData,
+        }
+    }
+}
+
+impl<B: RealVectorProblem> RealVectorProblem for RealVectorFitness<B> {
+    const BOUNDS: &'static [(f64, f64)] = B::BOUNDS;
+    const CROSSOVER: RealVectorCrossover = B::CROSSOVER;
+    const MUTATION: RealVectorMutation = B::MUTATION;
+    const MUTATION_PROBABILITY: f64 = B::MUTATION_PROBABILITY;
+    const BOUND_HANDLING: BoundHandling = B::BOUND_HANDLING;
+    const INITIAL_STEP_SIZE: f64 = B::INITIAL_STEP_SIZE;
+    const MIN_STEP_SIZE: f64 = B::MIN_STEP_SIZE;
+
+    fn evaluate(&self, gene: &RealVectorGene<Self>) -> f64 {
+        (self.fitness)(gene)
+    }
+
+    fn constraint_violation(gene: &RealVectorGene<Self>) -> f64 {
+        B::constraint_violation(&gene.with_constants())
+    }
+
+    fn repair(gene: &mut RealVectorGene<Self>) {
+        let mut repaired = gene.with_constants();
+        B::repair(&mut repaired);
+        gene.values = repaired.values;
+    }
+
+    fn behaviour(gene: &RealVectorGene<Self>) -> Option<Vec<f64>> {
+        B::behaviour(&gene.with_constants())
+    }
+}
+
+impl<B: RealVectorProblem + 'static> RealVectorGene<RealVectorFitness<B>> {
+    /// Returns the function creating random genes evaluated with the `fitness` closure, set with
+    /// `GeneticAlgorithm::gene_init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `fitness` - The fitness function of the genes.
+    pub fn problem<F>(fitness: F) -> Box<dyn Fn() -> Self>
+    where
+        F: Fn(&Self) -> f64 + Send + Sync + 'static,
+    {
+        let problem = Arc::new(RealVectorFitness::new(fitness));
+        Box::new(move || Self::init().with_problem(problem.clone()))
+    }
+}
+
+impl<B: RealVectorProblem> RealVectorGene<RealVectorFitness<B>> {
+    /// Returns a copy of the gene as a gene of `B`, to use its static functions.
+    fn with_constants(&self) -> RealVectorGene<B> {
+        RealVectorGene {
+            values: self.values.clone(),
+            step_sizes: self.step_sizes.clone(),
+            fitness: self.fitness,
+            problem: None,
+        }
+    }
+}
+
+/// Real-valued vector gene with the bounds of every dimension defined by its `RealVectorProblem`.
+pub struct RealVectorGene<P: RealVectorProblem> {
+    /// The values, one per dimension.
+    values: Vec<f64>,
+    /// The step sizes of `RealVectorMutation::SelfAdaptive`, one per dimension.
+    step_sizes: Vec<f64>,
+    /// The fitness value.
+    fitness: f64,
+    /// The problem instance evaluating the gene, `None` to use `RealVectorProblem::fitness`.
+    problem: Option<Arc<P>>,
+}
+
+impl<P: RealVectorProblem> RealVectorGene<P> {
+    /// Creates a new gene with specific values. The values outside the bounds are brought back with `P::BOUND_HANDLING`.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `values` is not the number of dimensions of the problem.
+    pub fn from_values(values: Vec<f64>) -> Self {
+        assert_eq!(values.len(), P::BOUNDS.len(), "Wrong number of values");
+        let mut gene = RealVectorGene {
+            step_sizes: vec![P::INITIAL_STEP_SIZE; values.len()],
+            values,
+            fitness: 0.0,
+            problem: None,
+        };
+        gene.apply_bounds(P::BOUND_HANDLING);
+        gene
+    }
+
+    /// Sets the problem instance evaluating the gene with `RealVectorProblem::evaluate`. The children of the crossover
+    /// take the problem instance of their first parent.
+    pub fn with_problem(mut self, problem: Arc<P>) -> Self {
+        self.problem = Some(problem);
+        self
+    }
+
+    /// Returns the problem instance evaluating the gene, if any.
+    pub fn get_problem(&self) -> Option<&Arc<P>> {
+        self.problem.as_ref()
+    }
+
+    /// Returns the values.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns the step sizes of `RealVectorMutation::SelfAdaptive`, relative to the range of every dimension.
+    pub fn step_sizes(&self) -> &[f64] {
+        &self.step_sizes
+    }
+
+    /// Returns the number of dimensions.
+    pub fn dimensions(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the Euclidean distance to `other`.
+    pub fn euclidean_distance(&self, other: &Self) -> f64 {
+        self.values
+            .iter()
+            .zip(other.values.iter())
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Blend crossover (BLX-α).
+    pub fn blend_crossover(&self, other: &Self, alpha: f64) -> Self {
+        let mut rng = rand::thread_rng();
+        let values = self
+            .values
+            .iter()
+            .zip(other.values.iter())
+            .map(|(&a, &b)| {
+                let (min, max) = (a.min(b), a.max(b));
+                let extension = alpha * (max - min);
+                if max - min > 0.0 {
+                    rng.gen_range(min - extension..=max + extension)
+                } else {
+                    a
+                }
+            })
+            .collect();
+        Self::from_values(values).inherit(&[self, other])
+    }
+
+    /// Whole arithmetic crossover.
+    pub fn arithmetic_crossover(&self, other: &Self) -> Self {
+        let lambda: f64 = rand::thread_rng().gen_range(0.0..=1.0);
+        let values = self
+            .values
+            .iter()
+            .zip(other.values.iter())
+            .map(|(a, b)| lambda * a + (1.0 - lambda) * b)
+            .collect();
+        Self::from_values(values).inherit(&[self, other])
+    }
+
+    /// Bounded simulated binary crossover (SBX). Every value is crossed with probability 0.5 and the child is one of the
+    /// two SBX children chosen randomly.
+    pub fn simulated_binary_crossover(&self, other: &Self, eta: f64) -> Self {
+        let mut rng = rand::thread_rng();
+        let mut values = self.values.clone();
+        for (i, value) in values.iter_mut().enumerate() {
+            let (a, b) = (self.values[i], other.values[i]);
+            if !rng.gen_bool(0.5) || (a - b).abs() <= f64::EPSILON {
+                continue;
+            }
+
+            let (lower, upper) = P::BOUNDS[i];
+            let (y1, y2) = (a.min(b), a.max(b));
+            let u: f64 = rng.gen_range(0.0..1.0);
+            let spread = |beta: f64| {
+                let alpha = 2.0 - beta.powf(-(eta + 1.0));
+                if u <= 1.0 / alpha {
+                    (u * alpha).powf(1.0 / (eta + 1.0))
+                } else {
+                    (1.0 / (2.0 - u * alpha)).powf(1.0 / (eta + 1.0))
+                }
+            };
+
+            let beta_lower = 1.0 + 2.0 * (y1 - lower) / (y2 - y1);
+            let child1 = 0.5 * ((y1 + y2) - spread(beta_lower) * (y2 - y1));
+            let beta_upper = 1.0 + 2.0 * (upper - y2) / (y2 - y1);
+            let child2 = 0.5 * ((y1 + y2) + spread(beta_upper) * (y2 - y1));
+
+            *value = if rng.gen_bool(0.5) { child1 } else { child2 };
+        }
+        Self::from_values(values).inherit(&[self, other])
+    }
+
+    /// Gaussian mutation. Every value is mutated with probability `probability` adding a normal noise with standard
+    /// deviation `sigma` times the range of its dimension.
+    pub fn gaussian_mutation(&mut self, probability: f64, sigma: f64) {
+        let mut rng = rand::thread_rng();
+        for (i, value) in self.values.iter_mut().enumerate() {
+            if rng.gen_bool(probability) {
+                let (lower, upper) = P::BOUNDS[i];
+                *value += standard_normal(&mut rng) * sigma * (upper - lower);
+            }
+        }
+        self.apply_bounds(P::BOUND_HANDLING);
+    }
+
+    /// Bounded polynomial mutation. Every value is mutated with probability `probability`.
+    pub fn polynomial_mutation(&mut self, probability: f64, eta: f64) {
+        let mut rng = rand::thread_rng();
+        for (i, value) in self.values.iter_mut().enumerate() {
+            let (lower, upper) = P::BOUNDS[i];
+            if !rng.gen_bool(probability) || upper <= lower {
+                continue;
+            }
+
+            let delta1 = (*value - lower) / (upper - lower);
+            let delta2 = (upper - *value) / (upper - lower);
+            let u: f64 = rng.gen_range(0.0..1.0);
+            let exponent = 1.0 / (eta + 1.0);
+            let delta = if u < 0.5 {
+                let xy = 1.0 - delta1;
+                let val = 2.0 * u + (1.0 - 2.0 * u) * xy.powf(eta + 1.0);
+                val.powf(exponent) - 1.0
+            } else {
+                let xy = 1.0 - delta2;
+                let val = 2.0 * (1.0 - u) + 2.0 * (u - 0.5) * xy.powf(eta + 1.0);
+                1.0 - val.powf(exponent)
+            };
+            *value += delta * (upper - lower);
+        }
+        self.apply_bounds(P::BOUND_HANDLING);
+    }
+
+    /// Self-adaptive Gaussian mutation. Every step size is multiplied by `exp(τ' * N + τ * N_i)`, with
+    /// `τ' = 1 / sqrt(2n)` and `τ = 1 / sqrt(2 * sqrt(n))`, and then every value is mutated adding a normal noise with
+    /// its new step size times the range of its dimension.
+    pub fn self_adaptive_mutation(&mut self) {
+        let mut rng = rand::thread_rng();
+        let n = self.values.len().max(1) as f64;
+        let global_tau = 1.0 / (2.0 * n).sqrt();
+        let tau = 1.0 / (2.0 * n.sqrt()).sqrt();
+        let global_noise = global_tau * standard_normal(&mut rng);
+        for (i, value) in self.values.iter_mut().enumerate() {
+            let (lower, upper) = P::BOUNDS[i];
+            let step_size = &mut self.step_sizes[i];
+            *step_size = (*step_size * (global_noise + tau * standard_normal(&mut rng)).exp())
+                .max(P::MIN_STEP_SIZE);
+            *value += standard_normal(&mut rng) * *step_size * (upper - lower);
+        }
+        self.apply_bounds(P::BOUND_HANDLING);
+    }
+
+    /// Brings back the values outside the bounds.
+    pub fn apply_bounds(&mut self, bound_handling: BoundHandling) {
+        for (value, (lower, upper)) in self.values.iter_mut().zip(P::BOUNDS.iter()) {
+            *value = bound_handling.apply(*value, *lower, *upper);
+        }
+    }
+
+    /// Sets the mean step sizes of the parents and the problem instance of the first parent, inherited by their
+    /// children.
+    fn inherit(mut self, parents: &[&Self]) -> Self {
+        self.step_sizes = Self::mean_step_sizes(parents);
+        self.problem = parents.first().and_then(|parent| parent.problem.clone());
+        self
+    }
+
+    /// Returns the mean step sizes of the parents, inherited by their children.
+    fn mean_step_sizes(parents: &[&Self]) -> Vec<f64> {
+        (0..P::BOUNDS.len())
+            .map(|i| {
+                parents
+                    .iter()
+                    .map(|parent| parent.step_sizes[i])
+                    .sum::<f64>()
+                    / parents.len() as f64
+            })
+            .collect()
+    }
+}
+
+/// Returns a sample of the standard normal distribution using the Box-Muller transform.
+pub(crate) fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = rng.gen_range(f64::MIN_POSITIVE..1.0);
+    let u2: f64 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+impl<P: RealVectorProblem> Gene for RealVectorGene<P> {
+    fn init() -> Self {
+        let mut rng = rand::thread_rng();
+        let values = P::BOUNDS
+            .iter()
+            .map(|&(lower, upper)| {
+                if upper > lower {
+                    rng.gen_range(lower..=upper)
+                } else {
+                    lower
+                }
+            })
+            .collect();
+        Self::from_values(values)
+    }
+
+    fn calculate_fitness(&mut self) -> f64 {
+        self.fitness = match &self.problem {
+            Some(problem) => problem.evaluate(self),
+            None => P::fitness(self),
+        };
+        self.fitness
+    }
+
+    fn crossover(&self, other: &Self) -> Self {
+        P::CROSSOVER.crossover(self, other)
+    }
+
+    fn mutate(&mut self) {
+        P::MUTATION.mutate(self)
+    }
+
+    fn get_fitness(&self) -> f64 {
+        self.fitness
+    }
+
+    fn distance(&self, other: &Self) -> Option<f64> {
+        Some(self.euclidean_distance(other))
+    }
+
+    fn step_size(&self) -> Option<f64> {
+        Some(self.step_sizes.iter().sum::<f64>() / self.step_sizes.len().max(1) as f64)
+    }
+
+    fn self_adaptive_mutate(&mut self) {
+        self.self_adaptive_mutation();
+    }
+
+    fn constraint_violation(&self) -> f64 {
+        P::constraint_violation(self)
+    }
+
+    fn repair(&mut self) {
+        P::repair(self)
+    }
+
+    fn behaviour(&self) -> Option<Vec<f64>> {
+        P::behaviour(self)
+    }
+}
+
+impl<P: RealVectorProblem> Clone for RealVectorGene<P> {
+    fn clone(&self) -> Self {
+        RealVectorGene {
+            values: self.values.clone(),
+            step_sizes: self.step_sizes.clone(),
+            fitness: self.fitness,
+            problem: self.problem.clone(),
+        }
+    }
+}
+
+impl<P: RealVectorProblem> PartialEq for RealVectorGene<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<P: RealVectorProblem> fmt::Debug for RealVectorGene<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RealVectorGene({:?})", self.values)
+    }
+}
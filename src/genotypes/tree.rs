@@ -0,0 +1,735 @@
+//! This module contains a typed expression tree genotype for genetic programming.
+//!
+//! The trees are built from the function and terminal sets of a `TreeProblem`. Every function and terminal has a
+//! `Type` and the operators only connect nodes with matching types, so every tree is always well typed.
+
+use core::fmt;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::error::Error;
+use std::marker::PhantomData;
+
+use crate::operators::MutationOperator;
+use crate::Gene;
+
+/// Number of attempts of the crossover and the mutations to produce a tree inside the depth limit.
+const ATTEMPTS: usize = 10;
+
+/// Maximum mean squared error returned by `TreeGene::mean_squared_error`. It is finite so the mean fitness and the
+/// selections keep working with trees producing huge or non-finite results.
+pub const MAX_ERROR: f64 = 1.0e12;
+
+/// Types of the values of the trees.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Type {
+    Number,
+    Boolean,
+}
+
+/// Values of the trees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Boolean(bool),
+}
+
+impl Value {
+    /// Returns the type of the value.
+    pub fn get_type(&self) -> Type {
+        match self {
+            Value::Number(_) => Type::Number,
+            Value::Boolean(_) => Type::Boolean,
+        }
+    }
+
+    /// Returns the value as a number. `true` is 1.0 and `false` is 0.0.
+    pub fn as_number(&self) -> f64 {
+        match self {
+            Value::Number(number) => *number,
+            Value::Boolean(boolean) => *boolean as u8 as f64,
+        }
+    }
+
+    /// Returns the value as a boolean. Every number but 0.0 is `true`.
+    pub fn as_boolean(&self) -> bool {
+        match self {
+            Value::Number(number) => *number != 0.0,
+            Value::Boolean(boolean) => *boolean,
+        }
+    }
+}
+
+/// Function of the function set.
+#[derive(Clone, Copy, Debug)]
+pub struct Function {
+    /// Name printed when displaying the tree.
+    pub name: &'static str,
+    /// Type of every argument.
+    pub arguments: &'static [Type],
+    /// Type of the result.
+    pub output: Type,
+    /// Evaluates the function. The arguments are in the same order as `arguments`.
+    pub evaluate: fn(&[Value]) -> Value,
+}
+
+/// Terminal of the terminal set.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Terminal {
+    /// The input `index` of the variables passed to `TreeGene::evaluate`.
+    Variable {
+        name: &'static str,
+        index: usize,
+        output: Type,
+    },
+    /// A fixed value.
+    Constant { name: &'static str, value: Value },
+    /// Ephemeral random constant. Every time it is added to a tree it takes a random number in `min..max`.
+    RandomConstant { min: f64, max: f64 },
+}
+
+impl Terminal {
+    /// Returns the type of the terminal.
+    pub fn output(&self) -> Type {
+        match self {
+            Terminal::Variable { output, .. } => *output,
+            Terminal::Constant { value, .. } => value.get_type(),
+            Terminal::RandomConstant { .. } => Type::Number,
+        }
+    }
+}
+
+/// `a + b`.
+pub const ADD: Function = Function {
+    name: "+",
+    arguments: &[Type::Number, Type::Number],
+    output: Type::Number,
+    evaluate: add,
+};
+
+/// `a - b`.
+pub const SUB: Function = Function {
+    name: "-",
+    arguments: &[Type::Number, Type::Number],
+    output: Type::Number,
+    evaluate: sub,
+};
+
+/// `a * b`.
+pub const MUL: Function = Function {
+    name: "*",
+    arguments: &[Type::Number, Type::Number],
+    output: Type::Number,
+    evaluate: mul,
+};
+
+/// Protected division: `a / b`, or 1.0 if `b` is almost 0.0.
+pub const DIV: Function = Function {
+    name: "/",
+    arguments: &[Type::Number, Type::Number],
+    output: Type::Number,
+    evaluate: div,
+};
+
+/// `sin(a)`.
+pub const SIN: Function = Function {
+    name: "sin",
+    arguments: &[Type::Number],
+    output: Type::Number,
+    evaluate: sin,
+};
+
+/// `cos(a)`.
+pub const COS: Function = Function {
+    name: "cos",
+    arguments: &[Type::Number],
+    output: Type::Number,
+    evaluate: cos,
+};
+
+/// `a < b`.
+pub const LESS_THAN: Function = Function {
+    name: "<",
+    arguments: &[Type::Number, Type::Number],
+    output: Type::Boolean,
+    evaluate: less_than,
+};
+
+/// `if condition { a } else { b }`.
+pub const IF_THEN_ELSE: Function = Function {
+    name: "if",
+    arguments: &[Type::Boolean, Type::Number, Type::Number],
+    output: Type::Number,
+    evaluate: if_then_else,
+};
+
+/// The arithmetic functions `+`, `-`, `*` and protected `/`.
+pub const ARITHMETIC: &[Function] = &[ADD, SUB, MUL, DIV];
+
+fn add(arguments: &[Value]) -> Value {
+    Value::Number(arguments[0].as_number() + arguments[1].as_number())
+}
+
+fn sub(arguments: &[Value]) -> Value {
+    Value::Number(arguments[0].as_number() - arguments[1].as_number())
+}
+
+fn mul(arguments: &[Value]) -> Value {
+    Value::Number(arguments[0].as_number() * arguments[1].as_number())
+}
+
+fn div(arguments: &[Value]) -> Value {
+    let divisor = arguments[1].as_number();
+    if divisor.abs() < 1e-9 {
+        Value::Number(1.0)
+    } else {
+        Value::Number(arguments[0].as_number() / divisor)
+    }
+}
+
+fn sin(arguments: &[Value]) -> Value {
+    Value::Number(arguments[0].as_number().sin())
+}
+
+fn cos(arguments: &[Value]) -> Value {
+    Value::Number(arguments[0].as_number().cos())
+}
+
+fn less_than(arguments: &[Value]) -> Value {
+    Value::Boolean(arguments[0].as_number() < arguments[1].as_number())
+}
+
+fn if_then_else(arguments: &[Value]) -> Value {
+    if arguments[0].as_boolean() {
+        arguments[1]
+    } else {
+        arguments[2]
+    }
+}
+
+/// Mutation operators for `TreeGene`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeMutation {
+    /// Replaces a random node with another one of the same type and arguments.
+    Point,
+    /// Replaces a random subtree with a new random subtree of the same type.
+    Subtree,
+    /// Replaces the tree with one of its own subtrees.
+    Hoist,
+}
+
+/// Keeps the tree inside `P::MAX_DEPTH`, leaving it unchanged if no valid mutation is found.
+impl<P: TreeProblem> MutationOperator<TreeGene<P>> for TreeMutation {
+    fn mutate(&self, gene: &mut TreeGene<P>) {
+        for _ in 0..ATTEMPTS {
+            let mut mutated = gene.clone();
+            match self {
+                TreeMutation::Point => mutated.point_mutation(),
+                TreeMutation::Subtree => mutated.subtree_mutation(),
+                TreeMutation::Hoist => mutated.hoist_mutation(),
+            }
+            if mutated.depth() <= P::MAX_DEPTH {
+                *gene = mutated;
+                return;
+            }
+        }
+    }
+}
+
+/// Errors of the function and terminal sets of a `TreeProblem`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrimitiveSetError {
+    /// A type reachable from the root type has no terminal, so its branches cannot end at the maximum depth.
+    MissingTerminal(Type),
+}
+
+impl fmt::Display for PrimitiveSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrimitiveSetError::MissingTerminal(output) => write!(
+                f,
+                "Invalid primitive set: the type {output:?} is reachable from the root type but has no terminal"
+            ),
+        }
+    }
+}
+
+impl Error for PrimitiveSetError {}
+
+/// Checks that every type reachable from `P::ROOT_TYPE` through the arguments of the functions has a terminal.
+///
+/// # Errors
+///
+/// Returns `PrimitiveSetError::MissingTerminal` with the first reachable type without a terminal.
+pub fn validate<P: TreeProblem>() -> Result<(), PrimitiveSetError> {
+    let mut reachable = vec![P::ROOT_TYPE];
+    let mut i = 0;
+    while i < reachable.len() {
+        let output = reachable[i];
+        if !P::TERMINALS
+            .iter()
+            .any(|terminal| terminal.output() == output)
+        {
+            return Err(PrimitiveSetError::MissingTerminal(output));
+        }
+        for function in P::FUNCTIONS
+            .iter()
+            .filter(|function| function.output == output)
+        {
+            for argument in function.arguments {
+                if !reachable.contains(argument) {
+                    reachable.push(*argument);
+                }
+            }
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Definition of a problem solved with `TreeGene`.
+///
+/// # Examples
+///
+/// ```
+/// use easy_ga::genotypes::tree::{self, Function, Terminal, TreeGene, TreeProblem, Type};
+/// use easy_ga::GeneticAlgorithm;
+///
+/// struct SymbolicRegression;
+///
+/// impl TreeProblem for SymbolicRegression {
+///     const FUNCTIONS: &'static [Function] = tree::ARITHMETIC;
+///     const TERMINALS: &'static [Terminal] = &[
+///         Terminal::Variable { name: "x", index: 0, output: Type::Number },
+///         Terminal::RandomConstant { min: -1.0, max: 1.0 },
+///     ];
+///
+///     fn fitness(gene: &TreeGene<Self>) -> f64 {
+///         let samples: Vec<(Vec<f64>, f64)> = (-10..=10)
+///             .map(|x| x as f64 / 10.0)
+///             .map(|x| (vec![x], x * x + x))
+///             .collect();
+///         -gene.mean_squared_error(&samples)
+///     }
+/// }
+///
+/// let (gene, _) = GeneticAlgorithm::<TreeGene<SymbolicRegression>>::new()
+///     .iterations(10)
+///     .run();
+/// println!("{gene}");
+/// ```
+pub trait TreeProblem {
+    /// The function set.
+    const FUNCTIONS: &'static [Function];
+    /// The terminal set. Every type reachable from `ROOT_TYPE` needs at least one terminal, checked with `validate`.
+    const TERMINALS: &'static [Terminal];
+    /// Type of the result of the trees.
+    const ROOT_TYPE: Type = Type::Number;
+    /// Minimum depth of the ramped half-and-half initialisation.
+    const MIN_INIT_DEPTH: usize = 2;
+    /// Maximum depth of the ramped half-and-half initialisation.
+    const MAX_INIT_DEPTH: usize = 6;
+    /// Maximum depth of the trees produced by the crossover and the mutations. A tree with a single node has depth 0.
+    const MAX_DEPTH: usize = 17;
+    /// Parsimony pressure against bloat. The fitness of the gene is `fitness - PARSIMONY_COEFFICIENT * size`.
+    const PARSIMONY_COEFFICIENT: f64 = 0.0;
+    /// Mutation operator used by `Gene::mutate`.
+    const MUTATION: TreeMutation = TreeMutation::Subtree;
+
+    /// The fitness function.
+    fn fitness(gene: &TreeGene<Self>) -> f64
+    where
+        Self: Sized;
+}
+
+/// Node of a tree.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Node {
+    /// Index of the function in `TreeProblem::FUNCTIONS`.
+    Function(usize),
+    /// Index of the terminal in `TreeProblem::TERMINALS`.
+    Terminal(usize),
+    /// Value of an ephemeral random constant.
+    Constant(Value),
+}
+
+/// Expression tree gene built from the function and terminal sets of its `TreeProblem`.
+pub struct TreeGene<P: TreeProblem> {
+    /// The nodes in prefix order.
+    nodes: Vec<Node>,
+    /// The fitness value.
+    fitness: f64,
+    problem: PhantomData<P>,
+}
+
+impl<P: TreeProblem> TreeGene<P> {
+    /// Creates a new random tree of type `P::ROOT_TYPE`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_depth` - The maximum depth of the tree.
+    /// * `full` - If `true` every branch reaches `max_depth` (full method), otherwise the branches can stop earlier
+    ///   (grow method).
+    ///
+    /// # Panics
+    ///
+    /// If the primitive set of `P` is not valid, see `validate`.
+    pub fn random(max_depth: usize, full: bool) -> Self {
+        if let Err(error) = validate::<P>() {
+            panic!("{error}");
+        }
+        let mut nodes = Vec::new();
+        generate::<P>(&mut nodes, P::ROOT_TYPE, 0, max_depth, full);
+        Self::new(nodes)
+    }
+
+    /// Returns the number of nodes.
+    pub fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the depth of the tree. A tree with a single node has depth 0.
+    pub fn depth(&self) -> usize {
+        self.node_depths().into_iter().max().unwrap_or(0)
+    }
+
+    /// Evaluates the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `variables` - The values of the `Terminal::Variable`s, indexed by their `index`.
+    pub fn evaluate(&self, variables: &[Value]) -> Value {
+        self.evaluate_node(0, variables).0
+    }
+
+    /// Returns the mean squared error of the tree for symbolic regression, capped at `MAX_ERROR`, which is also
+    /// returned if the error is not finite.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The values of the variables and the expected result of every sample.
+    pub fn mean_squared_error(&self, samples: &[(Vec<f64>, f64)]) -> f64 {
+        let mut variables = Vec::new();
+        let error = samples
+            .iter()
+            .map(|(inputs, expected)| {
+                variables.clear();
+                variables.extend(inputs.iter().map(|input| Value::Number(*input)));
+                (self.evaluate(&variables).as_number() - expected).powi(2)
+            })
+            .sum::<f64>()
+            / samples.len().max(1) as f64;
+
+        if error.is_finite() {
+            error.min(MAX_ERROR)
+        } else {
+            MAX_ERROR
+        }
+    }
+
+    /// Subtree crossover. A random subtree of `self` is replaced with a random subtree of `other` of the same type.
+    /// If no child inside `P::MAX_DEPTH` is found, the child is a copy of `self`.
+    pub fn subtree_crossover(&self, other: &Self) -> Self {
+        let mut rng = rand::thread_rng();
+        for _ in 0..ATTEMPTS {
+            let position = rng.gen_range(0..self.nodes.len());
+            let output = node_type::<P>(&self.nodes[position]);
+            let candidates: Vec<usize> = (0..other.nodes.len())
+                .filter(|&candidate| node_type::<P>(&other.nodes[candidate]) == output)
+                .collect();
+            let Some(&candidate) = candidates.choose(&mut rng) else {
+                continue;
+            };
+
+            let subtree = &other.nodes[candidate..other.subtree_end(candidate)];
+            let child = self.replace_subtree(position, subtree);
+            if child.depth() <= P::MAX_DEPTH {
+                return child;
+            }
+        }
+        Self::new(self.nodes.clone())
+    }
+
+    /// Point mutation. A random node is replaced with another function with the same arguments or another terminal of
+    /// the same type.
+    pub fn point_mutation(&mut self) {
+        let mut rng = rand::thread_rng();
+        let position = rng.gen_range(0..self.nodes.len());
+        match self.nodes[position] {
+            Node::Function(function) => {
+                let current = &P::FUNCTIONS[function];
+                let candidates: Vec<usize> = (0..P::FUNCTIONS.len())
+                    .filter(|&candidate| {
+                        P::FUNCTIONS[candidate].output == current.output
+                            && P::FUNCTIONS[candidate].arguments == current.arguments
+                    })
+                    .collect();
+                self.nodes[position] = Node::Function(*candidates.choose(&mut rng).unwrap());
+            }
+            node => {
+                self.nodes[position] = random_terminal::<P>(node_type::<P>(&node));
+            }
+        }
+    }
+
+    /// Subtree mutation. A random subtree is replaced with a new random subtree of the same type created with the
+    /// grow method, without exceeding `P::MAX_DEPTH`.
+    pub fn subtree_mutation(&mut self) {
+        let mut rng = rand::thread_rng();
+        let position = rng.gen_range(0..self.nodes.len());
+        let depth = self.node_depths()[position];
+        let max_depth = P::MAX_INIT_DEPTH.min(P::MAX_DEPTH.saturating_sub(depth));
+
+        let mut subtree = Vec::new();
+        generate::<P>(
+            &mut subtree,
+            node_type::<P>(&self.nodes[position]),
+            0,
+            rng.gen_range(0..=max_depth),
+            false,
+        );
+        *self = self.replace_subtree(position, &subtree);
+    }
+
+    /// Hoist mutation. The tree is replaced with one of its subtrees of type `P::ROOT_TYPE`, reducing its size.
+    pub fn hoist_mutation(&mut self) {
+        let candidates: Vec<usize> = (1..self.nodes.len())
+            .filter(|&position| node_type::<P>(&self.nodes[position]) == P::ROOT_TYPE)
+            .collect();
+        if let Some(&position) = candidates.choose(&mut rand::thread_rng()) {
+            *self = Self::new(self.nodes[position..self.subtree_end(position)].to_vec());
+        }
+    }
+
+    fn new(nodes: Vec<Node>) -> Self {
+        TreeGene {
+            nodes,
+            fitness: 0.0,
+            problem: PhantomData,
+        }
+    }
+
+    /// Returns the end of the subtree starting at `position`.
+    fn subtree_end(&self, position: usize) -> usize {
+        let mut pending = 1;
+        let mut end = position;
+        while pending > 0 {
+            pending += arity::<P>(&self.nodes[end]);
+            pending -= 1;
+            end += 1;
+        }
+        end
+    }
+
+    /// Returns a copy of the tree with the subtree starting at `position` replaced with `subtree`.
+    fn replace_subtree(&self, position: usize, subtree: &[Node]) -> Self {
+        let end = self.subtree_end(position);
+        let mut nodes = Vec::with_capacity(self.nodes.len() - (end - position) + subtree.len());
+        nodes.extend_from_slice(&self.nodes[..position]);
+        nodes.extend_from_slice(subtree);
+        nodes.extend_from_slice(&self.nodes[end..]);
+        Self::new(nodes)
+    }
+
+    /// Returns the depth of every node.
+    fn node_depths(&self) -> Vec<usize> {
+        let mut depths = Vec::with_capacity(self.nodes.len());
+        let mut pending = vec![0];
+        for node in &self.nodes {
+            let depth = pending.pop().unwrap_or(0);
+            depths.push(depth);
+            pending.extend(std::iter::repeat(depth + 1).take(arity::<P>(node)));
+        }
+        depths
+    }
+
+    /// Evaluates the subtree starting at `position`. Returns its value and the position after it.
+    fn evaluate_node(&self, position: usize, variables: &[Value]) -> (Value, usize) {
+        match self.nodes[position] {
+            Node::Function(function) => {
+                let function = &P::FUNCTIONS[function];
+                let mut arguments = Vec::with_capacity(function.arguments.len());
+                let mut next = position + 1;
+                for _ in function.arguments {
+                    let (value, after) = self.evaluate_node(next, variables);
+                    arguments.push(value);
+                    next = after;
+                }
+                ((function.evaluate)(&arguments), next)
+            }
+            Node::Terminal(terminal) => {
+                let value = match P::TERMINALS[terminal] {
+                    Terminal::Variable { index, .. } => variables[index],
+                    Terminal::Constant { value, .. } => value,
+                    Terminal::RandomConstant { min, .. } => Value::Number(min),
+                };
+                (value, position + 1)
+            }
+            Node::Constant(value) => (value, position + 1),
+        }
+    }
+
+    fn fmt_node(&self, position: usize, f: &mut fmt::Formatter<'_>) -> Result<usize, fmt::Error> {
+        match self.nodes[position] {
+            Node::Function(function) => {
+                let function = &P::FUNCTIONS[function];
+                write!(f, "({}", function.name)?;
+                let mut next = position + 1;
+                for _ in function.arguments {
+                    write!(f, " ")?;
+                    next = self.fmt_node(next, f)?;
+                }
+                write!(f, ")")?;
+                Ok(next)
+            }
+            Node::Terminal(terminal) => {
+                match P::TERMINALS[terminal] {
+                    Terminal::Variable { name, .. } | Terminal::Constant { name, .. } => {
+                        write!(f, "{name}")?
+                    }
+                    Terminal::RandomConstant { min, .. } => write!(f, "{min}")?,
+                }
+                Ok(position + 1)
+            }
+            Node::Constant(value) => {
+                match value {
+                    Value::Number(number) => write!(f, "{number}")?,
+                    Value::Boolean(boolean) => write!(f, "{boolean}")?,
+                }
+                Ok(position + 1)
+            }
+        }
+    }
+}
+
+/// Returns the type of a node.
+fn node_type<P: TreeProblem>(node: &Node) -> Type {
+    match node {
+        Node::Function(function) => P::FUNCTIONS[*function].output,
+        Node::Terminal(terminal) => P::TERMINALS[*terminal].output(),
+        Node::Constant(value) => value.get_type(),
+    }
+}
+
+/// Returns the number of arguments of a node.
+fn arity<P: TreeProblem>(node: &Node) -> usize {
+    match node {
+        Node::Function(function) => P::FUNCTIONS[*function].arguments.len(),
+        _ => 0,
+    }
+}
+
+/// Returns a random terminal node of type `output`.
+///
+/// # Panics
+///
+/// If there is no terminal of type `output`.
+fn random_terminal<P: TreeProblem>(output: Type) -> Node {
+    let mut rng = rand::thread_rng();
+    let terminals: Vec<usize> = (0..P::TERMINALS.len())
+        .filter(|&terminal| P::TERMINALS[terminal].output() == output)
+        .collect();
+    let terminal = *terminals
+        .choose(&mut rng)
+        .unwrap_or_else(|| panic!("No terminal of type {output:?}"));
+    match P::TERMINALS[terminal] {
+        Terminal::RandomConstant { min, max } => Node::Constant(Value::Number(if max > min {
+            rng.gen_range(min..max)
+        } else {
+            min
+        })),
+        _ => Node::Terminal(terminal),
+    }
+}
+
+/// Appends a random subtree of type `output` in prefix order with the full or grow method.
+fn generate<P: TreeProblem>(
+    nodes: &mut Vec<Node>,
+    output: Type,
+    depth: usize,
+    max_depth: usize,
+    full: bool,
+) {
+    let mut rng = rand::thread_rng();
+    let functions: Vec<usize> = (0..P::FUNCTIONS.len())
+        .filter(|&function| P::FUNCTIONS[function].output == output)
+        .collect();
+    let terminals = P::TERMINALS
+        .iter()
+        .filter(|terminal| terminal.output() == output)
+        .count();
+
+    let use_terminal = functions.is_empty()
+        || depth >= max_depth
+        || (!full
+            && terminals > 0
+            && rng.gen_bool(terminals as f64 / (terminals + functions.len()) as f64));
+    if use_terminal {
+        nodes.push(random_terminal::<P>(output));
+        return;
+    }
+
+    let function = *functions.choose(&mut rng).unwrap();
+    nodes.push(Node::Function(function));
+    for argument in P::FUNCTIONS[function].arguments {
+        generate::<P>(nodes, *argument, depth + 1, max_depth, full);
+    }
+}
+
+impl<P: TreeProblem> Gene for TreeGene<P> {
+    /// Ramped half-and-half initialisation: a random depth between `P::MIN_INIT_DEPTH` and `P::MAX_INIT_DEPTH` with
+    /// the full or grow method.
+    fn init() -> Self {
+        let mut rng = rand::thread_rng();
+        let max_depth = rng.gen_range(P::MIN_INIT_DEPTH..=P::MAX_INIT_DEPTH.max(P::MIN_INIT_DEPTH));
+        Self::random(max_depth, rng.gen_bool(0.5))
+    }
+
+    fn calculate_fitness(&mut self) -> f64 {
+        self.fitness = P::fitness(self) - P::PARSIMONY_COEFFICIENT * self.size() as f64;
+        self.fitness
+    }
+
+    fn crossover(&self, other: &Self) -> Self {
+        self.subtree_crossover(other)
+    }
+
+    fn mutate(&mut self) {
+        P::MUTATION.mutate(self)
+    }
+
+    fn get_fitness(&self) -> f64 {
+        self.fitness
+    }
+}
+
+impl<P: TreeProblem> Clone for TreeGene<P> {
+    fn clone(&self) -> Self {
+        TreeGene {
+            nodes: self.nodes.clone(),
+            fitness: self.fitness,
+            problem: PhantomData,
+        }
+    }
+}
+
+impl<P: TreeProblem> PartialEq for TreeGene<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes
+    }
+}
+
+impl<P: TreeProblem> fmt::Debug for TreeGene<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TreeGene({self})")
+    }
+}
+
+/// Display trait implementation for TreeGene, printing the tree as an S-expression like `(+ x (* 2 x))`.
+impl<P: TreeProblem> fmt::Display for TreeGene<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.nodes.is_empty() {
+            return Ok(());
+        }
+        self.fmt_node(0, f).map(|_| ())
+    }
+}
@@ -0,0 +1,180 @@
+//! This module contains the `HallOfFame`, which keeps the best distinct genes seen over the whole execution.
+//!
+//! Two genes are duplicates if they are equal (see `HallOfFame::equality`) or if their `Gene::distance` is not greater
+//! than the minimum distance. Without a distance nor equality, genes with the same fitness are duplicates.
+
+use core::fmt;
+
+use crate::Gene;
+
+/// The `size` best distinct genes seen, sorted from the best to the worst fitness.
+#[derive(Clone, Debug)]
+pub struct HallOfFame<T> {
+    /// The maximum number of genes.
+    size: usize,
+    /// The distance under which two genes are duplicates.
+    min_distance: f64,
+    /// Compares two genes for equality, if set with `HallOfFame::equality`.
+    equality: Option<fn(&T, &T) -> bool>,
+    /// The genes, sorted from the best to the worst fitness.
+    genes: Vec<T>,
+}
+
+impl<T: Gene + Clone> HallOfFame<T> {
+    /// Creates a new empty `HallOfFame` with a `min_distance` of 0.0, so only identical genes are duplicates.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The maximum number of genes.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is 0.
+    pub fn new(size: usize) -> Self {
+        if size == 0 {
+            panic!("Hall of fame size must be greater than 0");
+        }
+
+        HallOfFame {
+            size,
+            min_distance: 0.0,
+            equality: None,
+            genes: Vec::with_capacity(size),
+        }
+    }
+
+    /// Sets the distance under which two genes are duplicates.
+    ///
+    /// # Panics
+    ///
+    /// If `min_distance` is negative.
+    pub fn min_distance(mut self, min_distance: f64) -> Self {
+        if min_distance < 0.0 {
+            panic!("Minimum distance must be positive");
+        }
+
+        self.min_distance = min_distance;
+        self
+    }
+
+    /// Uses `PartialEq` to find the duplicates instead of `Gene::distance`.
+    pub fn equality(mut self) -> Self
+    where
+        T: PartialEq,
+    {
+        self.equality = Some(T::eq);
+        self
+    }
+
+    /// Tries to add a gene. If it is a duplicate of better or equal genes it is discarded, otherwise it replaces its
+    /// duplicates and the worst gene if the hall of fame is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `gene` - The gene with its fitness already calculated.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the gene was added.
+    pub fn insert(&mut self, gene: &T) -> bool {
+        let fitness = gene.get_fitness();
+        if fitness.is_nan() {
+            return false;
+        }
+        let full = self.genes.len() >= self.size;
+        if full
+            && self
+                .genes
+                .last()
+                .is_some_and(|worst| worst.get_fitness() >= fitness)
+        {
+            return false;
+        }
+        if self
+            .genes
+            .iter()
+            .any(|other| other.get_fitness() >= fitness && self.is_duplicate(gene, other))
+        {
+            return false;
+        }
+
+        let genes = std::mem::take(&mut self.genes);
+        self.genes = genes
+            .into_iter()
+            .filter(|other| !self.is_duplicate(gene, other))
+            .collect();
+        let position = self
+            .genes
+            .partition_point(|other| other.get_fitness() >= fitness);
+        self.genes.insert(position, gene.clone());
+        self.genes.truncate(self.size);
+        true
+    }
+
+    /// Tries to add every gene of a generation.
+    ///
+    /// # Returns
+    ///
+    /// The number of genes added.
+    pub fn update(&mut self, generation: &[T]) -> usize {
+        generation.iter().filter(|gene| self.insert(gene)).count()
+    }
+
+    /// Returns true if the genes are duplicates.
+    fn is_duplicate(&self, gene: &T, other: &T) -> bool {
+        if let Some(equality) = self.equality {
+            return equality(gene, other);
+        }
+
+        match gene.distance(other) {
+            Some(distance) => distance <= self.min_distance,
+            None => gene.get_fitness() == other.get_fitness(),
+        }
+    }
+
+    /// Returns the best gene.
+    pub fn best(&self) -> Option<&T> {
+        self.genes.first()
+    }
+
+    /// Returns the number of genes.
+    pub fn len(&self) -> usize {
+        self.genes.len()
+    }
+
+    /// Returns true if there are no genes.
+    pub fn is_empty(&self) -> bool {
+        self.genes.is_empty()
+    }
+
+    /// Removes every gene.
+    pub fn clear(&mut self) {
+        self.genes.clear();
+    }
+
+    /// Returns the genes, sorted from the best to the worst fitness.
+    pub fn get_genes(&self) -> &[T] {
+        &self.genes
+    }
+
+    /// Returns the maximum number of genes.
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the distance under which two genes are duplicates.
+    pub fn get_min_distance(&self) -> f64 {
+        self.min_distance
+    }
+}
+
+impl<T: Gene + Clone> fmt::Display for HallOfFame<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fitnesses: Vec<f64> = self.genes.iter().map(|gene| gene.get_fitness()).collect();
+        write!(
+            f,
+            "{{\n\tsize: {},\n\tmin_distance: {},\n\tfitnesses: {:?}\n}}",
+            self.size, self.min_distance, fitnesses
+        )
+    }
+}
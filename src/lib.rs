@@ -1,18 +1,50 @@
 //! Library to implement genetic algorithms
 
-#[macro_use]
-extern crate lazy_static;
-
+pub mod adaptation;
+pub mod alps;
+pub mod cma_es;
+pub mod coevolution;
+pub mod constraints;
+pub mod differential_evolution;
 pub mod gene;
 pub mod genetic_algorithm;
+pub mod genotypes;
+pub mod hall_of_fame;
+pub mod local_search;
 mod logger;
+pub mod niching;
+pub mod operators;
+pub mod particle_swarm;
+pub mod quality_diversity;
+pub mod restart;
 pub mod samples;
 pub mod selection;
+pub mod statistics;
 
+pub use adaptation::MutationAdaptation;
+pub use alps::{AgedGene, AgingScheme, Alps};
+pub use cma_es::{CmaEs, CmaRestart};
+pub use coevolution::{CoEvolution, CoEvolutionMode, CoevolvedGene};
+pub use constraints::ConstraintHandling;
+pub use differential_evolution::{
+    DifferentialAdaptation, DifferentialEvolution, DifferentialStrategy,
+};
 pub use gene::Gene;
 pub use genetic_algorithm::GeneticAlgorithm;
+pub use hall_of_fame::HallOfFame;
+pub use local_search::{
+    HillClimbing, LocalSearch, LocalSearchScope, LocalSearchWriteBack, SimulatedAnnealing,
+};
 pub use logger::LOG_verbosity;
 pub use logger::LOG_verbosity_type;
+pub use logger::LogDestination;
+pub use logger::LogFormat;
+pub use logger::Logger;
 pub use logger::VerbosityLevel;
 pub use logger::VerbosityType;
+pub use niching::{Niching, Species};
+pub use operators::{CrossoverOperator, MutationOperator};
+pub use particle_swarm::{InertiaSchedule, ParticleSwarm, SwarmTopology};
+pub use quality_diversity::{MapElites, MapElitesArchive, NoveltySearch};
+pub use restart::{Reinitialisation, Restart, RestartTrigger};
 pub use selection::*;
@@ -0,0 +1,119 @@
+//! This module contains the local searchers of the memetic `GeneticAlgorithm` and the ways to apply them.
+//!
+//! The built-in local searchers move through the neighbourhood of a gene given by `Gene::neighbour`. Any closure with
+//! the right signature is also a local searcher.
+
+use rand::Rng;
+
+use crate::Gene;
+
+/// This trait defines how the memetic `GeneticAlgorithm` refines its offspring. It is set with
+/// `GeneticAlgorithm::local_search`.
+pub trait LocalSearch<T> {
+    /// Refines a gene.
+    ///
+    /// # Arguments
+    ///
+    /// * `gene` - The gene to refine, with its fitness already calculated.
+    ///
+    /// # Returns
+    ///
+    /// * `gene` as `T` - The refined gene with its fitness calculated. It should not be worse than `gene`.
+    fn search(&self, gene: &T) -> T;
+}
+
+impl<T, F: Fn(&T) -> T> LocalSearch<T> for F {
+    fn search(&self, gene: &T) -> T {
+        self(gene)
+    }
+}
+
+/// The offspring the local search is applied to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LocalSearchScope {
+    /// The `k` best offspring.
+    Best(usize),
+    /// Every offspring with a probability of `fraction`.
+    Fraction(f32),
+    /// Every offspring.
+    All,
+}
+
+/// How the result of the local search is written back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LocalSearchWriteBack {
+    /// The gene is replaced by the refined gene.
+    Lamarckian,
+    /// The gene is kept and the fitness of the refined gene is used to select it.
+    Baldwinian,
+}
+
+/// Stochastic hill climbing. In every step `neighbours` neighbours are generated and the search moves to the best one if
+/// it is better than the current gene.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HillClimbing {
+    /// Number of steps.
+    pub steps: usize,
+    /// Number of neighbours generated in every step.
+    pub neighbours: usize,
+}
+
+impl<T: Gene + Clone> LocalSearch<T> for HillClimbing {
+    fn search(&self, gene: &T) -> T {
+        let mut current = gene.clone();
+        for _ in 0..self.steps {
+            let best_neighbour = (0..self.neighbours)
+                .map(|_| {
+                    let mut neighbour = current.neighbour();
+                    neighbour.calculate_fitness();
+                    neighbour
+                })
+                .max_by(|a, b| a.get_fitness().total_cmp(&b.get_fitness()));
+            if let Some(neighbour) = best_neighbour {
+                if neighbour.get_fitness() > current.get_fitness() {
+                    current = neighbour;
+                }
+            }
+        }
+
+        current
+    }
+}
+
+/// Simulated annealing. In every step a neighbour is generated and accepted if it is better than the current gene, or
+/// with probability `exp((neighbour - current) / temperature)` otherwise. The temperature starts at
+/// `initial_temperature` and it is multiplied by `cooling_rate` every step. The best gene visited is returned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimulatedAnnealing {
+    /// Number of neighbours generated.
+    pub steps: usize,
+    /// The temperature of the first step.
+    pub initial_temperature: f64,
+    /// The factor the temperature is multiplied by every step.
+    pub cooling_rate: f64,
+}
+
+impl<T: Gene + Clone> LocalSearch<T> for SimulatedAnnealing {
+    fn search(&self, gene: &T) -> T {
+        let mut rng = rand::thread_rng();
+        let mut current = gene.clone();
+        let mut best = gene.clone();
+        let mut temperature = self.initial_temperature;
+        for _ in 0..self.steps {
+            let mut neighbour = current.neighbour();
+            neighbour.calculate_fitness();
+            let delta = neighbour.get_fitness() - current.get_fitness();
+            if delta >= 0.0
+                || (temperature > 0.0 && rng.gen_range(0.0..1.0) < (delta / temperature).exp())
+            {
+                current = neighbour;
+                if current.get_fitness() > best.get_fitness() {
+                    best = current.clone();
+                }
+            }
+            temperature *= self.cooling_rate;
+        }
+
+        best
+    }
+}
@@ -1,22 +1,31 @@
 //! This module contains the implementation for verbosity debugging in the library.
+//!
+//! Every `GeneticAlgorithm` owns its own `Logger`, so several executions can run at the same time
+//! logging at different levels and into different destinations. `LOG_verbosity` and
+//! `LOG_verbosity_type` only change the global defaults used when a new `Logger` is created.
+//!
+//! With the `tracing` feature enabled every log is routed through the `tracing` macros instead,
+//! with a span per generation and per phase. The verbosity level, type, destination and format
+//! are then ignored and the filtering is left to the subscriber.
+
+// With the `tracing` feature the built-in output is never used.
+#![cfg_attr(feature = "tracing", allow(dead_code, unused_imports))]
 
 use cpu_time::ProcessTime;
-use std::fs::File;
-use std::fs::OpenOptions;
-use std::io::Write;
-use std::path::Path;
+use std::fmt::Write as _;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
 use uuid::Uuid;
 
-static mut LEVEL: VerbosityLevel = VerbosityLevel::DISABLED;
-static mut TYPE: VerbosityType = VerbosityType::LOG_AND_SAVE;
+static DEFAULT_LEVEL: AtomicU8 = AtomicU8::new(VerbosityLevel::DISABLED as u8);
+static DEFAULT_TYPE: AtomicU8 = AtomicU8::new(VerbosityType::LOG_AND_SAVE as u8);
 const PATH: &str = "target/easy_ga/logs/";
 
-lazy_static! {
-    static ref UUID: String = Uuid::new_v4().to_string();
-}
-
 /// Enumeration for the different levels of verbosity.
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
 pub enum VerbosityLevel {
     DISABLED = 0,
     LOW = 1,
@@ -24,55 +33,405 @@ pub enum VerbosityLevel {
     HIGH = 3,
 }
 
+impl VerbosityLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => VerbosityLevel::LOW,
+            2 => VerbosityLevel::MID,
+            3 => VerbosityLevel::HIGH,
+            _ => VerbosityLevel::DISABLED,
+        }
+    }
+}
+
 #[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum VerbosityType {
     LOG = 0,
     SAVE = 1,
     LOG_AND_SAVE = 2,
 }
 
-/// Sets de verbosity level.
+impl VerbosityType {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => VerbosityType::LOG,
+            1 => VerbosityType::SAVE,
+            _ => VerbosityType::LOG_AND_SAVE,
+        }
+    }
+}
+
+/// Where the saved logs are written.
+pub enum LogDestination {
+    /// A file in the given path. The parent directories are created if needed.
+    File(PathBuf),
+    /// Any writer, such as an in-memory buffer or a socket.
+    Writer(Box<dyn Write + Send>),
+}
+
+/// Format of every log line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    /// `MS |VerbosityLevel| Text`.
+    Text,
+    /// One JSON object per line with the `time`, `level` and `message` keys.
+    JsonLines,
+}
+
+/// Sets de default verbosity level for the loggers created from now on.
 #[allow(non_snake_case)]
 pub fn LOG_verbosity(verbosity: VerbosityLevel) {
-    // unsafe: Modification of static mutable variable.
-    unsafe {
-        LEVEL = verbosity;
-    }
+    DEFAULT_LEVEL.store(verbosity as u8, Ordering::Relaxed);
 }
 
-/// Sets de verbosity type.
+/// Sets de default verbosity type for the loggers created from now on.
 #[allow(non_snake_case)]
 pub fn LOG_verbosity_type(verbosity_type: VerbosityType) {
-    // unsafe: Modification of static mutable variable.
-    unsafe {
-        TYPE = verbosity_type;
-    }
+    DEFAULT_TYPE.store(verbosity_type as u8, Ordering::Relaxed);
 }
 
-/// Prints the log.
-#[allow(non_snake_case)]
-pub fn LOG(verbosity: VerbosityLevel, text: &str) {
-    unsafe {
-        if LEVEL != VerbosityLevel::DISABLED && verbosity != VerbosityLevel::DISABLED {
-            let now = ProcessTime::now();
-            // Format: MS |VerbosityLevel| Text
-            let message = format!("{:?} |{:?}| {text}", now.as_duration(), verbosity);
-            if verbosity <= LEVEL {
-                match TYPE {
-                    VerbosityType::LOG => {
-                        print(&message);
-                    }
-                    VerbosityType::SAVE => {
-                        save(&message);
-                    }
-                    VerbosityType::LOG_AND_SAVE => {
-                        print(&message);
-                        save(&message);
-                    }
+/// Guard of an entered span. The span is exited when it is dropped.
+#[cfg(feature = "tracing")]
+pub(crate) type SpanGuard = tracing::span::EnteredSpan;
+
+/// Guard of an entered span. Without the `tracing` feature there are no spans.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct SpanGuard;
+
+/// Logger of a single execution.
+pub struct Logger {
+    /// Max verbosity level of the messages to output.
+    level: VerbosityLevel,
+    /// Where the messages are output.
+    verbosity_type: VerbosityType,
+    /// Where the saved messages are written.
+    destination: LogDestination,
+    /// Format of every message.
+    format: LogFormat,
+    /// Max size in bytes of the log file before rotating it.
+    max_file_size: Option<u64>,
+    /// Buffered writer kept open during the execution.
+    writer: Option<BufWriter<Box<dyn Write + Send>>>,
+    /// Bytes written into the current file.
+    written_bytes: u64,
+    /// Suffix of the last rotated file.
+    rotations: u32,
+    /// First IO error found. Once there is an error nothing else is saved.
+    error: Option<io::Error>,
+    /// The iteration of the current generation, emitted as a field of every event.
+    #[cfg(feature = "tracing")]
+    iteration: Option<u32>,
+    /// The best fitness found so far, emitted as a field of every event.
+    #[cfg(feature = "tracing")]
+    best_fitness: Option<f64>,
+}
+
+impl Logger {
+    /// Creates a new `Logger` with specific values. The messages are saved into
+    /// `target/easy_ga/logs/easy_ga-<uuid>.log` using `LogFormat::Text` unless it is changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The max verbosity level of the messages to output.
+    /// * `verbosity_type` - Where the messages are output.
+    pub fn new(level: VerbosityLevel, verbosity_type: VerbosityType) -> Self {
+        Logger {
+            level,
+            verbosity_type,
+            destination: LogDestination::File(PathBuf::from(format!(
+                "{PATH}easy_ga-{}.log",
+                Uuid::new_v4()
+            ))),
+            format: LogFormat::Text,
+            max_file_size: None,
+            writer: None,
+            written_bytes: 0,
+            rotations: 0,
+            error: None,
+            #[cfg(feature = "tracing")]
+            iteration: None,
+            #[cfg(feature = "tracing")]
+            best_fitness: None,
+        }
+    }
+
+    /// Sets where the saved messages are written.
+    pub fn destination(mut self, destination: LogDestination) -> Self {
+        self.destination = destination;
+        self.writer = None;
+        self
+    }
+
+    /// Sets the format of every message.
+    pub fn format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the max size in bytes of the log file. When a message would exceed it, the current file
+    /// is renamed to `<path>.<n>`, using the first `n` not taken by a previous rotation, and a new
+    /// one is started. It has no effect on `LogDestination::Writer`.
+    pub fn max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = Some(max_file_size);
+        self
+    }
+
+    /// Returns the verbosity level.
+    pub fn get_level(&self) -> VerbosityLevel {
+        self.level
+    }
+
+    /// Returns the verbosity type.
+    pub fn get_verbosity_type(&self) -> VerbosityType {
+        self.verbosity_type
+    }
+
+    /// Returns the path of the file where the messages are saved, if the destination is a file.
+    pub fn get_file_path(&self) -> Option<&Path> {
+        match &self.destination {
+            LogDestination::File(path) => Some(path),
+            LogDestination::Writer(_) => None,
+        }
+    }
+
+    /// Returns the format of the messages.
+    pub fn get_format(&self) -> LogFormat {
+        self.format
+    }
+
+    /// Returns the first IO error found while saving, if any.
+    pub fn get_error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Opens the destination if the messages are going to be saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns the IO error if the destination could not be opened.
+    pub(crate) fn open(&mut self) -> io::Result<()> {
+        if self.saves() && self.writer.is_none() {
+            self.writer = Some(self.open_destination()?);
+        }
+        Ok(())
+    }
+
+    /// Writes all the buffered messages into the destination.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first IO error found while saving or the one produced when flushing.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        match self.writer.as_mut() {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Prints the log.
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn log(&mut self, verbosity: VerbosityLevel, text: &str) {
+        if self.level != VerbosityLevel::DISABLED
+            && verbosity != VerbosityLevel::DISABLED
+            && verbosity <= self.level
+        {
+            let message = self.format_message(ProcessTime::now().as_duration(), verbosity, text);
+            match self.verbosity_type {
+                VerbosityType::LOG => {
+                    print(&message);
+                }
+                VerbosityType::SAVE => {
+                    self.save(&message);
+                }
+                VerbosityType::LOG_AND_SAVE => {
+                    print(&message);
+                    self.save(&message);
                 }
             }
         }
     }
+
+    /// Emits the log as a `tracing` event with the `verbosity`, `iteration` and `best_fitness` fields. `LOW` maps to
+    /// `INFO`, `MID` to `DEBUG` and `HIGH` to `TRACE`.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn log(&mut self, verbosity: VerbosityLevel, text: &str) {
+        let (iteration, best_fitness) = (self.iteration, self.best_fitness);
+        let verbosity_name = format!("{verbosity:?}");
+        let verbosity_name = verbosity_name.as_str();
+        match verbosity {
+            VerbosityLevel::DISABLED => {}
+            VerbosityLevel::LOW => {
+                tracing::info!(
+                    verbosity = verbosity_name,
+                    iteration,
+                    best_fitness,
+                    "{text}"
+                )
+            }
+            VerbosityLevel::MID => {
+                tracing::debug!(
+                    verbosity = verbosity_name,
+                    iteration,
+                    best_fitness,
+                    "{text}"
+                )
+            }
+            VerbosityLevel::HIGH => {
+                tracing::trace!(
+                    verbosity = verbosity_name,
+                    iteration,
+                    best_fitness,
+                    "{text}"
+                )
+            }
+        }
+    }
+
+    /// Logs the best fitness found so far.
+    pub(crate) fn log_best_fitness(&mut self, best_fitness: f64) {
+        #[cfg(feature = "tracing")]
+        {
+            self.best_fitness = Some(best_fitness);
+            tracing::info!(
+                verbosity = "LOW",
+                iteration = self.iteration,
+                best_fitness,
+                "Best gene"
+            );
+        }
+        #[cfg(not(feature = "tracing"))]
+        self.log(
+            VerbosityLevel::LOW,
+            format!("Best gene with fitness = {:?}", best_fitness).as_str(),
+        );
+    }
+
+    /// Enters the span of a generation.
+    pub(crate) fn enter_generation(&mut self, iteration: u32) -> SpanGuard {
+        #[cfg(feature = "tracing")]
+        {
+            self.iteration = Some(iteration);
+        }
+        #[cfg(feature = "tracing")]
+        return tracing::info_span!("generation", iteration).entered();
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = iteration;
+            SpanGuard
+        }
+    }
+
+    /// Enters the span of a phase inside a generation.
+    pub(crate) fn enter_phase(&self, phase: &'static str) -> SpanGuard {
+        #[cfg(feature = "tracing")]
+        return tracing::debug_span!("phase", phase).entered();
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = phase;
+            SpanGuard
+        }
+    }
+
+    /// Returns if the messages are saved into the destination.
+    fn saves(&self) -> bool {
+        !cfg!(feature = "tracing")
+            && self.level != VerbosityLevel::DISABLED
+            && self.verbosity_type != VerbosityType::LOG
+    }
+
+    /// Formats the message with the format of the logger.
+    fn format_message(&self, time: Duration, verbosity: VerbosityLevel, text: &str) -> String {
+        match self.format {
+            // Format: MS |VerbosityLevel| Text
+            LogFormat::Text => format!("{:?} |{:?}| {text}", time, verbosity),
+            LogFormat::JsonLines => format!(
+                "{{\"time\":{},\"level\":\"{:?}\",\"message\":\"{}\"}}",
+                time.as_secs_f64(),
+                verbosity,
+                escape_json(text)
+            ),
+        }
+    }
+
+    /// Saves the LOG. Any IO error is kept and stops the saving.
+    fn save(&mut self, text: &str) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(error) = self.try_save(text) {
+            self.error = Some(error);
+        }
+    }
+
+    /// Saves the LOG rotating the file if needed.
+    fn try_save(&mut self, text: &str) -> io::Result<()> {
+        let line_size = text.len() as u64 + 1;
+        if let (Some(max_file_size), LogDestination::File(_)) =
+            (self.max_file_size, &self.destination)
+        {
+            if self.written_bytes > 0 && self.written_bytes + line_size > max_file_size {
+                self.rotate()?;
+            }
+        }
+
+        self.open()?;
+        if let Some(writer) = self.writer.as_mut() {
+            writeln!(writer, "{text}")?;
+            self.written_bytes += line_size;
+        }
+        Ok(())
+    }
+
+    /// Closes the current file, renames it to the first free `<path>.<n>` and starts a new one.
+    /// Rotated files left by previous executions are never overwritten.
+    fn rotate(&mut self) -> io::Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush()?;
+        }
+        if let LogDestination::File(path) = &self.destination {
+            let rotated_path = loop {
+                self.rotations += 1;
+                let mut rotated_path = path.clone().into_os_string();
+                rotated_path.push(format!(".{}", self.rotations));
+                if !Path::new(&rotated_path).exists() {
+                    break rotated_path;
+                }
+            };
+            fs::rename(path, rotated_path)?;
+        }
+        self.written_bytes = 0;
+        Ok(())
+    }
+
+    /// Opens the destination. The `LogDestination::Writer` is moved into the returned writer.
+    fn open_destination(&mut self) -> io::Result<BufWriter<Box<dyn Write + Send>>> {
+        let writer: Box<dyn Write + Send> = match &mut self.destination {
+            LogDestination::File(path) => {
+                if let Some(prefix) = path.parent() {
+                    fs::create_dir_all(prefix)?;
+                }
+                self.written_bytes = fs::metadata(&*path).map(|m| m.len()).unwrap_or(0);
+                let file: File = OpenOptions::new().create(true).append(true).open(path)?;
+                Box::new(file)
+            }
+            LogDestination::Writer(writer) => std::mem::replace(writer, Box::new(io::sink())),
+        };
+        Ok(BufWriter::new(writer))
+    }
+}
+
+/// Default trait implementation for Logger, using the global verbosity level and type.
+impl Default for Logger {
+    fn default() -> Self {
+        Logger::new(
+            VerbosityLevel::from_u8(DEFAULT_LEVEL.load(Ordering::Relaxed)),
+            VerbosityType::from_u8(DEFAULT_TYPE.load(Ordering::Relaxed)),
+        )
+    }
 }
 
 /// Prints the LOG.
@@ -80,23 +439,21 @@ fn print(text: &str) {
     println!("{text}");
 }
 
-/// Saves the LOG.
-#[allow(unused_assignments)]
-fn save(text: &str) {
-    let mut file: File;
-    let full_path = format!("{PATH}easy_ga-{}.log", *UUID);
-    let path = std::path::Path::new(&full_path);
-    if !Path::exists(path) {
-        let prefix = path.parent().unwrap();
-        std::fs::create_dir_all(prefix).unwrap();
-        file = File::create(&full_path).unwrap();
-    }
-
-    file = OpenOptions::new()
-        .write(true)
-        .append(true)
-        .open(path)
-        .unwrap();
-
-    writeln!(file, "{text}").unwrap();
+/// Escapes the text to be used inside a JSON string.
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
 }
@@ -0,0 +1,214 @@
+//! This module contains the niching modes to keep several optima in the population for multimodal problems.
+//!
+//! All of them rely on `Gene::distance`. Without a distance the niching has no effect: the fitness is not shared and
+//! there is a single niche.
+
+use crate::Gene;
+
+/// Niching modes supported by the `GeneticAlgorithm`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Niching {
+    /// No niching, the population converges to a single optimum.
+    None,
+    /// The fitness used in the selection, shifted by the worst fitness of the generation to be positive, is divided by
+    /// the niche count of the gene, so crowded niches are penalized.
+    ///
+    /// * `sigma` - The niche radius. Genes farther than `sigma` do not share fitness.
+    /// * `alpha` - The shape of the sharing function `1 - (distance / sigma)^alpha`.
+    FitnessSharing { sigma: f64, alpha: f64 },
+    /// Every pair of parents generates two children and each child only replaces its closest parent if it is better.
+    ///
+    /// * `niche_radius` - Distance under which two genes belong to the same niche when reporting the niche best genes.
+    DeterministicCrowding { niche_radius: f64 },
+    /// NEAT-style speciation. The genes are grouped in species by their distance to the representative of every
+    /// species, their fitness is shared within the species and every species gets a number of offspring proportional
+    /// to its shared fitness.
+    ///
+    /// * `threshold` - The initial compatibility distance under which a gene belongs to a species.
+    /// * `target_species` - The number of species the threshold is adjusted to, increasing or decreasing it by
+    ///   `threshold_step` every iteration. 0 keeps the threshold constant.
+    /// * `threshold_step` - The adjustment of the threshold.
+    /// * `stagnation` - The number of iterations without improving its best fitness after which a species gets no
+    ///   offspring, unless it has the best gene. 0 never culls the species.
+    Speciation {
+        threshold: f64,
+        target_species: usize,
+        threshold_step: f64,
+        stagnation: u32,
+    },
+}
+
+impl Niching {
+    /// Returns the distance under which two genes belong to the same niche.
+    pub fn niche_radius(&self) -> Option<f64> {
+        match self {
+            Niching::None => None,
+            Niching::FitnessSharing { sigma, .. } => Some(*sigma),
+            Niching::DeterministicCrowding { niche_radius } => Some(*niche_radius),
+            Niching::Speciation { threshold, .. } => Some(*threshold),
+        }
+    }
+}
+
+/// A species of `Niching::Speciation`.
+#[derive(Clone, Debug)]
+pub struct Species<T> {
+    /// The identifier of the species, unique in the execution.
+    id: usize,
+    /// The gene the distance of the genes is compared with to join the species.
+    pub(crate) representative: T,
+    /// The indexes of the members in the generation.
+    pub(crate) members: Vec<usize>,
+    /// The best fitness of the species in any iteration.
+    pub(crate) best_fitness: f64,
+    /// The last iteration the best fitness improved.
+    pub(crate) last_improvement: u32,
+    /// The number of genes of the next generation the species produced.
+    pub(crate) offspring: usize,
+}
+
+impl<T> Species<T> {
+    /// Creates a new species with a single member.
+    pub(crate) fn new(id: usize, representative: T, member: usize, iteration: u32) -> Self {
+        Species {
+            id,
+            representative,
+            members: vec![member],
+            best_fitness: f64::MIN,
+            last_improvement: iteration,
+            offspring: 0,
+        }
+    }
+
+    /// Returns the identifier of the species.
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the representative of the species.
+    pub fn get_representative(&self) -> &T {
+        &self.representative
+    }
+
+    /// Returns the number of members in the last speciated generation.
+    pub fn get_size(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns the best fitness of the species in any iteration.
+    pub fn get_best_fitness(&self) -> f64 {
+        self.best_fitness
+    }
+
+    /// Returns the last iteration the best fitness of the species improved.
+    pub fn get_last_improvement(&self) -> u32 {
+        self.last_improvement
+    }
+
+    /// Returns the number of genes of the next generation the species produced.
+    pub fn get_offspring(&self) -> usize {
+        self.offspring
+    }
+}
+
+/// Splits `total` proportionally to `weights` with the largest remainder method. If every weight is 0.0 it is split
+/// equally.
+pub(crate) fn allocate(weights: &[f64], total: usize) -> Vec<usize> {
+    if weights.is_empty() {
+        return Vec::new();
+    }
+
+    let sum: f64 = weights.iter().sum();
+    let quotas: Vec<f64> = if sum > 0.0 && sum.is_finite() {
+        weights.iter().map(|w| w / sum * total as f64).collect()
+    } else {
+        vec![total as f64 / weights.len() as f64; weights.len()]
+    };
+    let mut allocation: Vec<usize> = quotas.iter().map(|quota| quota.floor() as usize).collect();
+    let mut remainders: Vec<usize> = (0..weights.len()).collect();
+    remainders.sort_by(|a, b| {
+        (quotas[*b] - quotas[*b].floor()).total_cmp(&(quotas[*a] - quotas[*a].floor()))
+    });
+    let missing = total.saturating_sub(allocation.iter().sum());
+    for i in remainders.into_iter().cycle().take(missing) {
+        allocation[i] += 1;
+    }
+    allocation
+}
+
+/// Calculates the shared fitness of every gene, `(fitness - worst fitness) / niche count`.
+///
+/// # Arguments
+///
+/// * `generation` - The genes with their fitness already calculated.
+/// * `sigma` - The niche radius.
+/// * `alpha` - The shape of the sharing function.
+pub fn shared_fitnesses<T: Gene>(generation: &[T], sigma: f64, alpha: f64) -> Vec<f64> {
+    let fitnesses: Vec<f64> = generation.iter().map(|gene| gene.get_fitness()).collect();
+    share(&fitnesses, &niche_counts(generation, sigma, alpha))
+}
+
+/// Divides every fitness by its niche count. The fitnesses are shifted by the worst one first, so the division
+/// penalizes the crowded genes also when the fitness is negative.
+///
+/// # Arguments
+///
+/// * `fitnesses` - The fitness of every gene.
+/// * `niche_counts` - The niche count of every gene.
+pub(crate) fn share(fitnesses: &[f64], niche_counts: &[f64]) -> Vec<f64> {
+    let min_fitness = fitnesses.iter().copied().fold(f64::INFINITY, f64::min);
+    fitnesses
+        .iter()
+        .zip(niche_counts)
+        .map(|(fitness, niche_count)| (fitness - min_fitness) / niche_count)
+        .collect()
+}
+
+/// Calculates the niche count of every gene, never lower than 1.0, which divides its fitness with fitness sharing.
+///
+/// # Arguments
+///
+/// * `generation` - The genes.
+/// * `sigma` - The niche radius.
+/// * `alpha` - The shape of the sharing function.
+pub(crate) fn niche_counts<T: Gene>(generation: &[T], sigma: f64, alpha: f64) -> Vec<f64> {
+    generation
+        .iter()
+        .map(|gene| {
+            let niche_count: f64 = generation
+                .iter()
+                .map(|other| match gene.distance(other) {
+                    Some(distance) if distance < sigma => 1.0 - (distance / sigma).powf(alpha),
+                    Some(_) => 0.0,
+                    // Without distance every gene is only in its own niche.
+                    None if std::ptr::eq(gene, other) => 1.0,
+                    None => 0.0,
+                })
+                .sum();
+            niche_count.max(1.0)
+        })
+        .collect()
+}
+
+/// Returns the best gene of every niche, sorted from the best to the worst fitness.
+///
+/// # Arguments
+///
+/// * `genes` - The candidate genes.
+/// * `niche_radius` - Distance under which two genes belong to the same niche.
+pub fn niche_best_genes<T: Gene + Clone>(genes: &[T], niche_radius: f64) -> Vec<T> {
+    let mut sorted: Vec<&T> = genes.iter().collect();
+    sorted.sort_by(|a, b| b.get_fitness().total_cmp(&a.get_fitness()));
+
+    let mut best_genes: Vec<T> = Vec::new();
+    for gene in sorted {
+        let new_niche = best_genes.iter().all(|best| match gene.distance(best) {
+            Some(distance) => distance > niche_radius,
+            None => false,
+        });
+        if new_niche {
+            best_genes.push(gene.clone());
+        }
+    }
+    best_genes
+}
@@ -0,0 +1,284 @@
+//! This module defines the traits of the crossover and mutation operators and the ways to combine them.
+//!
+//! The `GeneticAlgorithm` uses `Gene::crossover` and `Gene::mutate` unless other operators are set with
+//! `GeneticAlgorithm::crossover_operator` and `GeneticAlgorithm::mutation_operator`. Any closure with the right
+//! signature is also an operator.
+
+use rand::Rng;
+
+use crate::Gene;
+
+/// This trait defines how the parents chosen by the `Selection` are crossed over into new genes. It is set with
+/// `GeneticAlgorithm::crossover_operator`.
+pub trait CrossoverOperator<T> {
+    /// Crosses over two parents.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent1` - The first parent.
+    /// * `parent2` - The second parent.
+    ///
+    /// # Returns
+    ///
+    /// * `child` as `T` - The gene result of doing the crossover between both parents.
+    fn crossover(&self, parent1: &T, parent2: &T) -> T;
+
+    /// Returns the number of parents of every crossover. 2 by default.
+    fn parents(&self) -> usize {
+        2
+    }
+
+    /// Crosses over `CrossoverOperator::parents` parents. It is the method used by the `GeneticAlgorithm`.
+    ///
+    /// # Arguments
+    ///
+    /// * `parents` - The parents chosen by the `Selection`.
+    ///
+    /// # Returns
+    ///
+    /// * `children` as `Vec<T>` - One or more children. By default the single child of `CrossoverOperator::crossover`.
+    fn crossover_parents(&self, parents: &[&T]) -> Vec<T> {
+        vec![self.crossover(parents[0], parents[1])]
+    }
+}
+
+/// This trait defines how a gene is mutated. It is set with `GeneticAlgorithm::mutation_operator`.
+pub trait MutationOperator<T> {
+    /// Mutates a gene to alter its values.
+    ///
+    /// # Arguments
+    ///
+    /// * `gene` - The gene to mutate.
+    fn mutate(&self, gene: &mut T);
+}
+
+impl<T, F: Fn(&T, &T) -> T> CrossoverOperator<T> for F {
+    fn crossover(&self, parent1: &T, parent2: &T) -> T {
+        self(parent1, parent2)
+    }
+}
+
+impl<T, F: Fn(&mut T)> MutationOperator<T> for F {
+    fn mutate(&self, gene: &mut T) {
+        self(gene)
+    }
+}
+
+/// Crossover operator calling `Gene::crossover`. It is the default one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GeneCrossover;
+
+impl<T: Gene> CrossoverOperator<T> for GeneCrossover {
+    fn crossover(&self, parent1: &T, parent2: &T) -> T {
+        parent1.crossover(parent2)
+    }
+}
+
+/// Mutation operator calling `Gene::mutate`. It is the default one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GeneMutation;
+
+impl<T: Gene> MutationOperator<T> for GeneMutation {
+    fn mutate(&self, gene: &mut T) {
+        gene.mutate()
+    }
+}
+
+/// Crossover operator producing two children from two parents, the second one swapping the roles of the parents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TwoChildren<C>(pub C);
+
+impl<T, C: CrossoverOperator<T>> CrossoverOperator<T> for TwoChildren<C> {
+    fn crossover(&self, parent1: &T, parent2: &T) -> T {
+        self.0.crossover(parent1, parent2)
+    }
+
+    fn crossover_parents(&self, parents: &[&T]) -> Vec<T> {
+        vec![
+            self.0.crossover(parents[0], parents[1]),
+            self.0.crossover(parents[1], parents[0]),
+        ]
+    }
+}
+
+/// Diagonal crossover. The genes are cut in `parents` segments at the same random points and the child `i` takes its
+/// segment `j` from the parent `(i + j) % parents`, so it produces one child per parent.
+///
+/// It is implemented by the built-in genotypes with positions: `BitStringGene` and `RealVectorGene`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DiagonalCrossover {
+    /// The number of parents, at least 2.
+    pub parents: usize,
+}
+
+/// Returns the segment of every position of a diagonal crossover with `parents` parents.
+pub(crate) fn diagonal_segments(length: usize, parents: usize) -> Vec<usize> {
+    let mut rng = rand::thread_rng();
+    let mut cuts: Vec<usize> = (1..parents).map(|_| rng.gen_range(0..=length)).collect();
+    cuts.sort_unstable();
+    (0..length)
+        .map(|position| cuts.iter().filter(|cut| **cut <= position).count())
+        .collect()
+}
+
+/// Crossover operator choosing one of its operators randomly every time, proportionally to their weights.
+pub struct CrossoverMix<T> {
+    operators: Vec<(Box<dyn CrossoverOperator<T>>, f64)>,
+}
+
+impl<T> CrossoverMix<T> {
+    /// Creates a new mix without operators.
+    pub fn new() -> Self {
+        CrossoverMix {
+            operators: Vec::new(),
+        }
+    }
+
+    /// Adds an operator with its weight.
+    ///
+    /// # Panics
+    ///
+    /// If the weight is negative or `CrossoverOperator::parents` of the operator is lower than 2.
+    pub fn operator(mut self, operator: Box<dyn CrossoverOperator<T>>, weight: f64) -> Self {
+        if weight < 0.0 {
+            panic!("Operator weight is negative");
+        }
+        if operator.parents() < 2 {
+            panic!("Crossover operator needs at least 2 parents");
+        }
+
+        self.operators.push((operator, weight));
+        self
+    }
+}
+
+impl<T> Default for CrossoverMix<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> CrossoverOperator<T> for CrossoverMix<T> {
+    /// # Panics
+    ///
+    /// If the mix has no operators.
+    fn crossover(&self, parent1: &T, parent2: &T) -> T {
+        let weights: Vec<f64> = self.operators.iter().map(|(_, weight)| *weight).collect();
+        self.operators[choose_weighted(&weights)]
+            .0
+            .crossover(parent1, parent2)
+    }
+
+    /// The maximum number of parents of its operators.
+    fn parents(&self) -> usize {
+        self.operators
+            .iter()
+            .map(|(operator, _)| operator.parents())
+            .max()
+            .unwrap_or(2)
+    }
+
+    /// Every operator only receives the first parents it needs.
+    fn crossover_parents(&self, parents: &[&T]) -> Vec<T> {
+        let weights: Vec<f64> = self.operators.iter().map(|(_, weight)| *weight).collect();
+        let operator = &self.operators[choose_weighted(&weights)].0;
+        operator.crossover_parents(&parents[..operator.parents().min(parents.len())])
+    }
+}
+
+/// Mutation operator choosing one of its operators randomly every time, proportionally to their weights.
+pub struct MutationMix<T> {
+    operators: Vec<(Box<dyn MutationOperator<T>>, f64)>,
+}
+
+impl<T> MutationMix<T> {
+    /// Creates a new mix without operators.
+    pub fn new() -> Self {
+        MutationMix {
+            operators: Vec::new(),
+        }
+    }
+
+    /// Adds an operator with its weight.
+    pub fn operator(mut self, operator: Box<dyn MutationOperator<T>>, weight: f64) -> Self {
+        if weight < 0.0 {
+            panic!("Operator weight is negative");
+        }
+
+        self.operators.push((operator, weight));
+        self
+    }
+}
+
+impl<T> Default for MutationMix<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> MutationOperator<T> for MutationMix<T> {
+    /// # Panics
+    ///
+    /// If the mix has no operators.
+    fn mutate(&self, gene: &mut T) {
+        let weights: Vec<f64> = self.operators.iter().map(|(_, weight)| *weight).collect();
+        self.operators[choose_weighted(&weights)].0.mutate(gene)
+    }
+}
+
+/// Mutation operator applying all its operators in order.
+pub struct MutationChain<T> {
+    operators: Vec<Box<dyn MutationOperator<T>>>,
+}
+
+impl<T> MutationChain<T> {
+    /// Creates a new chain without operators.
+    pub fn new() -> Self {
+        MutationChain {
+            operators: Vec::new(),
+        }
+    }
+
+    /// Adds an operator at the end of the chain.
+    pub fn operator(mut self, operator: Box<dyn MutationOperator<T>>) -> Self {
+        self.operators.push(operator);
+        self
+    }
+}
+
+impl<T> Default for MutationChain<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> MutationOperator<T> for MutationChain<T> {
+    fn mutate(&self, gene: &mut T) {
+        for operator in self.operators.iter() {
+            operator.mutate(gene);
+        }
+    }
+}
+
+/// Returns a random index proportionally to the weights. If every weight is 0.0 the index is uniform.
+///
+/// # Panics
+///
+/// If `weights` is empty.
+fn choose_weighted(weights: &[f64]) -> usize {
+    assert!(!weights.is_empty(), "No operators to choose from");
+    let mut rng = rand::thread_rng();
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return rng.gen_range(0..weights.len());
+    }
+
+    let mut value = rng.gen_range(0.0..total);
+    for (index, weight) in weights.iter().enumerate() {
+        if value < *weight {
+            return index;
+        }
+        value -= weight;
+    }
+    weights.len() - 1
+}
@@ -0,0 +1,657 @@
+//! This module contains the Particle Swarm Optimisation driver for `RealVectorGene`.
+//!
+//! Every particle is a `RealVectorGene` moved by its velocity, so the same `RealVectorProblem` and fitness function
+//! can be optimised with the `GeneticAlgorithm` and with the `ParticleSwarm`. The problems with runtime data and the
+//! fitness closures of `RealVectorFitness` are set with `ParticleSwarm::problem`.
+
+#![allow(clippy::manual_range_contains)]
+
+use core::fmt;
+use rand::Rng;
+use std::error::Error;
+use std::sync::Arc;
+
+use crate::genetic_algorithm::StopCriteria;
+use crate::genotypes::{RealVectorGene, RealVectorProblem};
+use crate::logger::{Logger, VerbosityLevel};
+use crate::statistics::GenerationStatistics;
+use crate::Gene;
+
+/// Default value for the swarm size.
+const SWARM_SIZE_DEFAULT: usize = 40;
+/// Default value for the max iterations.
+const MAX_ITERATIONS_DEFAULT: u32 = 1000;
+/// Default value for the inertia weight.
+const INERTIA_DEFAULT: f64 = 0.7298;
+/// Default value for the cognitive and social coefficients.
+const ACCELERATION_DEFAULT: f64 = 1.49618;
+
+/// Topologies defining the neighbourhood whose best position attracts every particle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwarmTopology {
+    /// Every particle is attracted by the best position of the whole swarm.
+    Global,
+    /// Local best. Every particle is attracted by the best position of the `neighbours` particles at each side of it
+    /// in a ring.
+    Ring { neighbours: usize },
+}
+
+/// Schedules of the inertia weight.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InertiaSchedule {
+    /// The inertia weight is `weight` in every iteration.
+    Constant { weight: f64 },
+    /// The inertia weight decreases linearly from `start` to `end` in the last iteration.
+    LinearDecay { start: f64, end: f64 },
+}
+
+impl InertiaSchedule {
+    /// Returns the inertia weight of an iteration.
+    pub fn weight(&self, iteration: u32, iterations: u32) -> f64 {
+        match *self {
+            InertiaSchedule::Constant { weight } => weight,
+            InertiaSchedule::LinearDecay { start, end } => {
+                let progress = iteration as f64 / iterations.saturating_sub(1).max(1) as f64;
+                start + (end - start) * progress.min(1.0)
+            }
+        }
+    }
+}
+
+/// Particle Swarm Optimisation driver for a `RealVectorProblem`. Higher fitness is better.
+pub struct ParticleSwarm<P: RealVectorProblem> {
+    /// Number of particles.
+    swarm_size: usize,
+    /// Num of the max iterations the optimiser will perform.
+    iterations: u32,
+    /// The current iteration.
+    current_iteration: u32,
+    /// The current position of every particle.
+    particles: Vec<RealVectorGene<P>>,
+    /// The velocity of every particle, relative to the range of every dimension.
+    velocities: Vec<Vec<f64>>,
+    /// The best position found by every particle.
+    personal_bests: Vec<RealVectorGene<P>>,
+    /// The neighbourhood of every particle.
+    topology: SwarmTopology,
+    /// The inertia weight schedule.
+    inertia: InertiaSchedule,
+    /// The acceleration towards the personal best.
+    cognitive: f64,
+    /// The acceleration towards the neighbourhood best.
+    social: f64,
+    /// If the velocity update uses Clerc's constriction coefficient instead of the inertia weight.
+    constriction: bool,
+    /// The max absolute velocity, relative to the range of every dimension.
+    max_velocity: Option<f64>,
+    /// The fitness value to reach to end the optimiser.
+    fitness_goal: f64,
+    /// The mean pairwise distance under which the swarm is considered converged.
+    convergence_threshold: Option<f64>,
+    /// If the distance metrics of the diversity are saved in the statistics of every generation.
+    diversity_statistics: bool,
+    /// If the optimiser is running or not.
+    running: bool,
+    /// The best gene overall, `None` until the swarm is evaluated.
+    best_gene: Option<RealVectorGene<P>>,
+    /// The stop reason if the optimiser has stopped.
+    stop_criteria: StopCriteria,
+    /// The logger of this execution.
+    logger: Logger,
+    /// Statistics of every iteration.
+    statistics: Vec<GenerationStatistics>,
+    /// The problem instance evaluating the particles, `None` to use `RealVectorProblem::fitness`.
+    problem: Option<Arc<P>>,
+}
+
+impl<P: RealVectorProblem> ParticleSwarm<P> {
+    /// Creates a new `ParticleSwarm` with default values.
+    /// * `swarm_size` = 40
+    /// * `iterations` = 1000
+    /// * `topology` = SwarmTopology::Global
+    /// * `inertia` = InertiaSchedule::Constant { weight: 0.7298 }
+    /// * `cognitive` = 1.49618
+    /// * `social` = 1.49618
+    /// * `constriction` = false
+    /// * `max_velocity` = None
+    /// * `fitness_goal` = f64::MAX
+    pub fn new() -> Self {
+        ParticleSwarm {
+            swarm_size: SWARM_SIZE_DEFAULT,
+            iterations: MAX_ITERATIONS_DEFAULT,
+            current_iteration: 0,
+            particles: (0..SWARM_SIZE_DEFAULT)
+                .map(|_| RealVectorGene::init())
+                .collect(),
+            velocities: Vec::new(),
+            personal_bests: Vec::new(),
+            topology: SwarmTopology::Global,
+            inertia: InertiaSchedule::Constant {
+                weight: INERTIA_DEFAULT,
+            },
+            cognitive: ACCELERATION_DEFAULT,
+            social: ACCELERATION_DEFAULT,
+            constriction: false,
+            max_velocity: None,
+            fitness_goal: f64::MAX,
+            convergence_threshold: None,
+            diversity_statistics: false,
+            running: false,
+            best_gene: None,
+            stop_criteria: StopCriteria::Unknown,
+            logger: Logger::default(),
+            statistics: Vec::new(),
+            problem: None,
+        }
+    }
+
+    /// Initiate the optimiser.
+    ///
+    /// # Errors
+    ///
+    /// Returns the IO error if the logger destination could not be opened.
+    pub fn init(mut self) -> Result<Self, Box<dyn Error>> {
+        self.logger.open()?;
+        self.start();
+        Ok(self)
+    }
+
+    /// Runs the optimiser by itself without user control.
+    ///
+    /// # Notes
+    ///
+    /// Logger IO errors do not stop the execution. Use `ParticleSwarm::try_run` to stop on them.
+    pub fn run(mut self) -> (RealVectorGene<P>, StopCriteria) {
+        if !self.is_running() {
+            self.start();
+        }
+
+        while self.running {
+            self.next_iteration();
+        }
+
+        (
+            self.best_gene.unwrap_or_else(RealVectorGene::init),
+            self.stop_criteria,
+        )
+    }
+
+    /// Runs the optimiser by itself without user control, stopping if the logger fails to save.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first IO error produced by the logger.
+    pub fn try_run(mut self) -> Result<(RealVectorGene<P>, StopCriteria), Box<dyn Error>> {
+        if !self.is_running() {
+            self = self.init()?;
+        }
+
+        while self.running {
+            self.next_iteration();
+            if self.logger.get_error().is_some() {
+                self.logger.flush()?;
+            }
+        }
+
+        self.logger.flush()?;
+        Ok((
+            self.best_gene.unwrap_or_else(RealVectorGene::init),
+            self.stop_criteria,
+        ))
+    }
+
+    /// Marks the optimiser as running, evaluates the swarm and gives every particle a random velocity.
+    fn start(&mut self) {
+        self.running = true;
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!("ParticleSwarm started with values:\n{}", self).as_str(),
+        );
+        let mut rng = rand::thread_rng();
+        for particle in self.particles.iter_mut() {
+            particle.calculate_fitness();
+        }
+        let max_velocity = self.max_velocity.unwrap_or(1.0);
+        self.velocities = (0..self.particles.len())
+            .map(|_| {
+                (0..P::BOUNDS.len())
+                    .map(|_| rng.gen_range(-max_velocity..=max_velocity) * 0.5)
+                    .collect()
+            })
+            .collect();
+        self.personal_bests = self.particles.clone();
+        self.best_gene = Some(self.best_of(&self.personal_bests).clone());
+        self.logger
+            .log(VerbosityLevel::HIGH, "ParticleSwarm initiated properly.");
+    }
+
+    /// Sets the problem instance of the optimiser, if any, to a particle.
+    fn with_problem(&self, particle: RealVectorGene<P>) -> RealVectorGene<P> {
+        match &self.problem {
+            Some(problem) => particle.with_problem(problem.clone()),
+            None => particle,
+        }
+    }
+
+    /// Goes iteration by iteration in case the user wants to have more control over the lifetime of the optimiser.
+    /// If the optimiser was not initiated, the swarm is evaluated and gets its velocities first.
+    ///
+    /// # Returns
+    ///
+    /// `self.particles` - The new positions of the particles.
+    pub fn next_iteration(&mut self) -> &Vec<RealVectorGene<P>> {
+        if !self.running && self.current_iteration == 0 {
+            self.start();
+        }
+        let _generation_span = self.logger.enter_generation(self.current_iteration);
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!(
+                ">>>>>>> Started iteration {} <<<<<<<",
+                self.current_iteration
+            )
+            .as_str(),
+        );
+
+        let mut rng = rand::thread_rng();
+        let (inertia, factor) = if self.constriction {
+            (1.0, self.constriction_coefficient())
+        } else {
+            (
+                self.inertia.weight(self.current_iteration, self.iterations),
+                1.0,
+            )
+        };
+        let neighbourhood_bests: Vec<usize> = (0..self.particles.len())
+            .map(|i| self.neighbourhood_best(i))
+            .collect();
+
+        let mut improvements = 0;
+        for (i, &best_index) in neighbourhood_bests.iter().enumerate() {
+            let neighbourhood_best = &self.personal_bests[best_index];
+            let values = (0..P::BOUNDS.len())
+                .map(|d| {
+                    let (lower, upper) = P::BOUNDS[d];
+                    let range = upper - lower;
+                    let position = self.particles[i].values()[d];
+                    let (personal, social) = if range > 0.0 {
+                        (
+                            (self.personal_bests[i].values()[d] - position) / range,
+                            (neighbourhood_best.values()[d] - position) / range,
+                        )
+                    } else {
+                        (0.0, 0.0)
+                    };
+
+                    let mut velocity = factor
+                        * (inertia * self.velocities[i][d]
+                            + self.cognitive * rng.gen_range(0.0..1.0) * personal
+                            + self.social * rng.gen_range(0.0..1.0) * social);
+                    if let Some(max_velocity) = self.max_velocity {
+                        velocity = velocity.clamp(-max_velocity, max_velocity);
+                    }
+                    self.velocities[i][d] = velocity;
+                    position + velocity * range
+                })
+                .collect();
+
+            let mut particle = self.with_problem(RealVectorGene::from_values(values));
+            particle.calculate_fitness();
+            if particle.get_fitness() > self.personal_bests[i].get_fitness() {
+                self.personal_bests[i] = particle.clone();
+                improvements += 1;
+            }
+            self.particles[i] = particle;
+        }
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!("{} particles improved their personal best.", improvements).as_str(),
+        );
+
+        let best = self.best_of(&self.personal_bests).clone();
+        let best_gene = self.best_gene.get_or_insert_with(|| best.clone());
+        if best.get_fitness() > best_gene.get_fitness() {
+            *best_gene = best;
+        }
+        self.logger.log_best_fitness(best_gene.get_fitness());
+        self.statistics.push(GenerationStatistics::calculate(
+            self.current_iteration,
+            &self.particles,
+            self.diversity_statistics || self.convergence_threshold.is_some(),
+        ));
+
+        self.current_iteration += 1;
+        self.check_stop_criteria();
+
+        &self.particles
+    }
+
+    /// Returns Clerc's constriction coefficient `2 / |2 - φ - sqrt(φ^2 - 4φ)|` with `φ = cognitive + social`. It is 1.0
+    /// if `φ` is not greater than 4.
+    pub fn constriction_coefficient(&self) -> f64 {
+        let phi = self.cognitive + self.social;
+        if phi <= 4.0 {
+            return 1.0;
+        }
+
+        2.0 / (2.0 - phi - (phi * phi - 4.0 * phi).sqrt()).abs()
+    }
+
+    /// Returns the index of the best personal best in the neighbourhood of the particle `i`.
+    fn neighbourhood_best(&self, i: usize) -> usize {
+        let n = self.personal_bests.len();
+        let neighbours = match self.topology {
+            SwarmTopology::Global => return self.best_index(),
+            SwarmTopology::Ring { neighbours } => neighbours.min(n / 2),
+        };
+
+        (0..=2 * neighbours)
+            .map(|offset| (i + n + offset - neighbours) % n)
+            .max_by(|a, b| {
+                self.personal_bests[*a]
+                    .get_fitness()
+                    .total_cmp(&self.personal_bests[*b].get_fitness())
+            })
+            .unwrap_or(i)
+    }
+
+    /// Returns the index of the best personal best of the swarm.
+    fn best_index(&self) -> usize {
+        (0..self.personal_bests.len())
+            .max_by(|a, b| {
+                self.personal_bests[*a]
+                    .get_fitness()
+                    .total_cmp(&self.personal_bests[*b].get_fitness())
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns the best gene of `genes`.
+    fn best_of<'a>(&self, genes: &'a [RealVectorGene<P>]) -> &'a RealVectorGene<P> {
+        genes
+            .iter()
+            .max_by(|a, b| a.get_fitness().total_cmp(&b.get_fitness()))
+            .expect("Empty swarm")
+    }
+
+    /// Checks if the optimiser should stop or not.
+    fn check_stop_criteria(&mut self) {
+        let mean_pairwise_distance = self
+            .statistics
+            .last()
+            .and_then(|statistics| statistics.diversity.mean_pairwise_distance);
+
+        if self
+            .best_gene
+            .as_ref()
+            .is_some_and(|gene| gene.get_fitness() >= self.fitness_goal)
+        {
+            self.running = false;
+            self.stop_criteria = StopCriteria::FitnessAchieved;
+        } else if let (Some(threshold), Some(distance)) =
+            (self.convergence_threshold, mean_pairwise_distance)
+        {
+            if distance <= threshold {
+                self.running = false;
+                self.stop_criteria = StopCriteria::Converged;
+            }
+        }
+
+        if self.running && self.current_iteration >= self.iterations {
+            self.running = false;
+            self.stop_criteria = StopCriteria::MaxIterations;
+        }
+
+        if !self.running {
+            self.logger.log(
+                VerbosityLevel::LOW,
+                format!("Algorithm must stop because of {:?}", self.stop_criteria).as_str(),
+            );
+        }
+    }
+
+    /// Sets the number of particles.
+    ///
+    /// # Panics
+    ///
+    /// If the swarm size is 0.
+    pub fn swarm_size(mut self, swarm_size: usize) -> Self {
+        if swarm_size == 0 {
+            panic!("Swarm size is 0");
+        }
+
+        while self.particles.len() < swarm_size {
+            let particle = self.with_problem(RealVectorGene::init());
+            self.particles.push(particle);
+        }
+        self.particles.truncate(swarm_size);
+        self.swarm_size = swarm_size;
+        self
+    }
+
+    /// Sets the problem instance evaluating the particles with `RealVectorProblem::evaluate`, e.g. a problem with
+    /// runtime data or a `RealVectorFitness` closure.
+    pub fn problem(mut self, problem: Arc<P>) -> Self {
+        self.problem = Some(problem);
+        self.particles = std::mem::take(&mut self.particles)
+            .into_iter()
+            .map(|particle| self.with_problem(particle))
+            .collect();
+        self
+    }
+
+    /// Sets the max number of iterations the optimiser will perform.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        if iterations <= self.current_iteration {
+            panic!("Number of iterations is not greater  than the actual iteration.");
+        }
+
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the topology of the swarm.
+    pub fn topology(mut self, topology: SwarmTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Sets the inertia weight schedule. It is ignored with the constriction coefficient.
+    pub fn inertia(mut self, inertia: InertiaSchedule) -> Self {
+        self.inertia = inertia;
+        self
+    }
+
+    /// Sets the acceleration towards the personal best.
+    pub fn cognitive(mut self, cognitive: f64) -> Self {
+        self.cognitive = cognitive;
+        self
+    }
+
+    /// Sets the acceleration towards the neighbourhood best.
+    pub fn social(mut self, social: f64) -> Self {
+        self.social = social;
+        self
+    }
+
+    /// Sets if the velocity update uses Clerc's constriction coefficient instead of the inertia weight. It needs
+    /// `cognitive + social > 4`, usually 2.05 each.
+    pub fn constriction(mut self, constriction: bool) -> Self {
+        self.constriction = constriction;
+        self
+    }
+
+    /// Sets the max absolute velocity, relative to the range of every dimension.
+    pub fn max_velocity(mut self, max_velocity: f64) -> Self {
+        if max_velocity <= 0.0 {
+            panic!("Max velocity is not greater than 0.0");
+        }
+
+        self.max_velocity = Some(max_velocity);
+        self
+    }
+
+    /// Sets the fitness goal to stop the optimiser.
+    pub fn fitness_goal(mut self, fitness_goal: f64) -> Self {
+        self.fitness_goal = fitness_goal;
+        self
+    }
+
+    /// Sets the mean pairwise distance under which the swarm is considered converged, stopping the optimiser with
+    /// `StopCriteria::Converged`.
+    pub fn convergence_threshold(mut self, convergence_threshold: f64) -> Self {
+        self.convergence_threshold = Some(convergence_threshold);
+        self
+    }
+
+    /// Sets if the mean pairwise distance and the unique genomes are saved in the statistics of every generation.
+    ///
+    /// # Notes
+    ///
+    /// They need O(n²) calls to `Gene::distance` every generation, so they are only calculated by default when the
+    /// convergence threshold uses them.
+    pub fn diversity_statistics(mut self, diversity_statistics: bool) -> Self {
+        self.diversity_statistics = diversity_statistics;
+        self
+    }
+
+    /// Sets the logger of this execution.
+    pub fn logger(mut self, logger: Logger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Returns the number of particles.
+    pub fn get_swarm_size(&self) -> usize {
+        self.swarm_size
+    }
+
+    /// Returns the max iterations.
+    pub fn get_iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns the current iteration.
+    pub fn get_current_iteration(&self) -> u32 {
+        self.current_iteration
+    }
+
+    /// Returns the current position of every particle.
+    pub fn get_particles(&self) -> &[RealVectorGene<P>] {
+        &self.particles
+    }
+
+    /// Returns the velocity of every particle, relative to the range of every dimension.
+    pub fn get_velocities(&self) -> &[Vec<f64>] {
+        &self.velocities
+    }
+
+    /// Returns the best position found by every particle.
+    pub fn get_personal_bests(&self) -> &[RealVectorGene<P>] {
+        &self.personal_bests
+    }
+
+    /// Returns the topology.
+    pub fn get_topology(&self) -> SwarmTopology {
+        self.topology
+    }
+
+    /// Returns the inertia weight schedule.
+    pub fn get_inertia(&self) -> InertiaSchedule {
+        self.inertia
+    }
+
+    /// Returns the acceleration towards the personal best.
+    pub fn get_cognitive(&self) -> f64 {
+        self.cognitive
+    }
+
+    /// Returns the acceleration towards the neighbourhood best.
+    pub fn get_social(&self) -> f64 {
+        self.social
+    }
+
+    /// Returns if the constriction coefficient is used.
+    pub fn get_constriction(&self) -> bool {
+        self.constriction
+    }
+
+    /// Returns the max absolute velocity.
+    pub fn get_max_velocity(&self) -> Option<f64> {
+        self.max_velocity
+    }
+
+    /// Returns the fitness goal.
+    pub fn get_fitness_goal(&self) -> f64 {
+        self.fitness_goal
+    }
+
+    /// Returns the convergence threshold.
+    pub fn get_convergence_threshold(&self) -> Option<f64> {
+        self.convergence_threshold
+    }
+
+    /// Returns if the distance metrics of the diversity are saved in the statistics.
+    pub fn get_diversity_statistics(&self) -> bool {
+        self.diversity_statistics
+    }
+
+    /// Returns the best gene overall, `None` until the swarm is evaluated.
+    pub fn get_best_gene(&self) -> Option<&RealVectorGene<P>> {
+        self.best_gene.as_ref()
+    }
+
+    /// Returns if the optimiser is running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns the stop reason.
+    pub fn get_stop_criteria(&self) -> StopCriteria {
+        self.stop_criteria
+    }
+
+    /// Returns the statistics of every iteration.
+    pub fn get_statistics(&self) -> &[GenerationStatistics] {
+        &self.statistics
+    }
+
+    /// Returns the logger of this execution.
+    pub fn get_logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// Returns the logger of this execution as mutable, for example to flush it.
+    pub fn get_logger_mut(&mut self) -> &mut Logger {
+        &mut self.logger
+    }
+}
+
+/// Default trait implementation for ParticleSwarm.
+impl<P: RealVectorProblem> Default for ParticleSwarm<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Display trait implementation for ParticleSwarm.
+impl<P: RealVectorProblem> fmt::Display for ParticleSwarm<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+             "{{\n\tswarm_size: {},\n\titerations: {},\n\tcurrent_iteration: {},\n\ttopology: {:?},\n\tinertia: {:?},\n\tcognitive: {},\n\tsocial: {},\n\tconstriction: {},\n\tmax_velocity: {:?},\n\tfitness_goal: {:?},\n\tstop_criteria: {:?},\n\tbest_gene_fitness: {:?}\n}}",
+            self.swarm_size,
+            self.iterations,
+            self.current_iteration,
+            self.topology,
+            self.inertia,
+            self.cognitive,
+            self.social,
+            self.constriction,
+            self.max_velocity,
+            self.fitness_goal,
+            self.stop_criteria,
+            self.best_gene.as_ref().map(|gene| gene.get_fitness())
+        )
+    }
+}
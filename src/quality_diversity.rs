@@ -0,0 +1,647 @@
+//! This module contains the quality-diversity tools based on the behaviour descriptor of the genes.
+//!
+//! * `NoveltySearch` makes the `GeneticAlgorithm` select the genes by their novelty instead of their fitness.
+//! * `MapElites` fills a `MapElitesArchive`, a grid over the behaviour space that keeps the best gene of every cell.
+//!
+//! Both rely on `Gene::behaviour`. Genes without a behaviour are never novel and never enter the archive.
+
+#![allow(clippy::manual_range_contains)]
+
+use core::fmt;
+use rand::Rng;
+use std::error::Error;
+
+use crate::genetic_algorithm::StopCriteria;
+use crate::logger::{Logger, VerbosityLevel};
+use crate::operators::{CrossoverOperator, GeneCrossover, GeneMutation, MutationOperator};
+use crate::selection::*;
+use crate::statistics::GenerationStatistics;
+use crate::Gene;
+
+/// Default value for the number of random genes evaluated before the first iteration.
+const INITIAL_POPULATION_DEFAULT: usize = 100;
+/// Default value for the number of children generated every iteration.
+const BATCH_SIZE_DEFAULT: usize = 100;
+/// Default value for the max iterations.
+const MAX_ITERATIONS_DEFAULT: u32 = 1000;
+/// Default value for the crossover probability.
+const CROSSOVER_RATE_DEFAULT: f32 = 0.0;
+/// Default value for the mutation probability.
+const MUTATION_RATE_DEFAULT: f32 = 1.0;
+
+/// Novelty search mode of the `GeneticAlgorithm`. The genes are selected by their novelty, the mean distance to their
+/// `k` nearest neighbours in the behaviour space among the generation and the archive of novel behaviours. The
+/// behaviours with a novelty greater than `archive_threshold` are added to the archive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoveltySearch {
+    /// Number of nearest neighbours.
+    pub k: usize,
+    /// Novelty over which a behaviour is added to the archive.
+    pub archive_threshold: f64,
+}
+
+/// Returns the euclidean distance between two behaviours.
+pub fn behaviour_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Calculates the novelty of every behaviour. The neighbours of a behaviour are the other behaviours and the archive.
+///
+/// # Arguments
+///
+/// * `behaviours` - The behaviours of the generation.
+/// * `archive` - The archive of novel behaviours.
+/// * `k` - Number of nearest neighbours.
+///
+/// # Returns
+///
+/// * `novelties` as `Vec<f64>` - The mean distance of every behaviour to its `k` nearest neighbours.
+pub fn novelty_scores(behaviours: &[Vec<f64>], archive: &[Vec<f64>], k: usize) -> Vec<f64> {
+    behaviours
+        .iter()
+        .enumerate()
+        .map(|(i, behaviour)| {
+            let mut distances: Vec<f64> = behaviours
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| other)
+                .chain(archive.iter())
+                .map(|other| behaviour_distance(behaviour, other))
+                .collect();
+            if distances.is_empty() || k == 0 {
+                return 0.0;
+            }
+
+            let k = k.min(distances.len());
+            distances.select_nth_unstable_by(k - 1, |a, b| a.total_cmp(b));
+            distances[..k].iter().sum::<f64>() / k as f64
+        })
+        .collect()
+}
+
+/// MAP-Elites archive. A grid over the behaviour space keeping the best gene of every cell.
+#[derive(Clone, Debug)]
+pub struct MapElitesArchive<T> {
+    /// The bounds of every dimension of the behaviour space. The behaviours outside them go to the border cells.
+    bounds: Vec<(f64, f64)>,
+    /// Number of cells of every dimension.
+    bins: Vec<usize>,
+    /// The elite of every cell, the first dimension being the most significant.
+    cells: Vec<Option<T>>,
+}
+
+impl<T: Gene + Clone> MapElitesArchive<T> {
+    /// Creates an empty archive.
+    ///
+    /// # Arguments
+    ///
+    /// * `bounds` - The bounds of every dimension of the behaviour space.
+    /// * `bins` - Number of cells of every dimension.
+    ///
+    /// # Panics
+    ///
+    /// If `bounds` and `bins` have different lengths, if they are empty, if any number of bins is 0 or if any lower
+    /// bound is not lower than its upper bound.
+    pub fn new(bounds: &[(f64, f64)], bins: &[usize]) -> Self {
+        if bounds.len() != bins.len() || bounds.is_empty() {
+            panic!("Behaviour bounds and bins have different or empty lengths");
+        }
+        if bins.contains(&0) {
+            panic!("Number of bins is 0");
+        }
+        if bounds.iter().any(|(lower, upper)| lower >= upper) {
+            panic!("Lower behaviour bound not lower than the upper bound");
+        }
+
+        MapElitesArchive {
+            bounds: bounds.to_vec(),
+            bins: bins.to_vec(),
+            cells: vec![None; bins.iter().product()],
+        }
+    }
+
+    /// Returns the cell of a behaviour, or `None` if it has not the dimensions of the archive.
+    pub fn cell(&self, behaviour: &[f64]) -> Option<usize> {
+        if behaviour.len() != self.bins.len() {
+            return None;
+        }
+
+        let cell = behaviour
+            .iter()
+            .zip(self.bounds.iter().zip(self.bins.iter()))
+            .fold(0, |cell, (value, ((lower, upper), bins))| {
+                let position = ((value - lower) / (upper - lower) * *bins as f64).floor();
+                let bin = if position.is_nan() {
+                    0
+                } else {
+                    (position.max(0.0) as usize).min(bins - 1)
+                };
+                cell * bins + bin
+            });
+        Some(cell)
+    }
+
+    /// Inserts a gene in the cell of its behaviour if the cell is empty or the gene is better than its elite.
+    ///
+    /// # Arguments
+    ///
+    /// * `gene` - The gene with its fitness already calculated.
+    ///
+    /// # Returns
+    ///
+    /// * `inserted` as `bool` - If the gene is the new elite of its cell.
+    pub fn insert(&mut self, gene: T) -> bool {
+        let cell = match gene.behaviour().and_then(|behaviour| self.cell(&behaviour)) {
+            Some(cell) => cell,
+            None => return false,
+        };
+
+        let is_better = self.cells[cell]
+            .as_ref()
+            .map_or(true, |elite| gene.get_fitness() > elite.get_fitness());
+        if is_better {
+            self.cells[cell] = Some(gene);
+        }
+        is_better
+    }
+
+    /// Returns the elite of a cell.
+    pub fn get(&self, cell: usize) -> Option<&T> {
+        self.cells.get(cell).and_then(Option::as_ref)
+    }
+
+    /// Returns the elites of the occupied cells.
+    pub fn elites(&self) -> Vec<&T> {
+        self.cells.iter().flatten().collect()
+    }
+
+    /// Returns the best elite.
+    pub fn best(&self) -> Option<&T> {
+        self.cells
+            .iter()
+            .flatten()
+            .max_by(|a, b| a.get_fitness().total_cmp(&b.get_fitness()))
+    }
+
+    /// Returns the number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.cells.iter().flatten().count()
+    }
+
+    /// Returns `true` if no cell is occupied.
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().all(Option::is_none)
+    }
+
+    /// Returns the number of cells.
+    pub fn num_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns the ratio of occupied cells.
+    pub fn coverage(&self) -> f64 {
+        self.len() as f64 / self.num_cells() as f64
+    }
+
+    /// Returns the QD-score, the sum of the fitness of the elites.
+    pub fn qd_score(&self) -> f64 {
+        self.cells
+            .iter()
+            .flatten()
+            .map(|gene| gene.get_fitness())
+            .sum()
+    }
+
+    /// Returns the bounds of the behaviour space.
+    pub fn get_bounds(&self) -> &[(f64, f64)] {
+        &self.bounds
+    }
+
+    /// Returns the number of cells of every dimension.
+    pub fn get_bins(&self) -> &[usize] {
+        &self.bins
+    }
+}
+
+/// MAP-Elites optimiser. Every iteration a batch of children is generated from elites chosen with the selection
+/// algorithm and inserted in the archive.
+pub struct MapElites<T: Gene + Clone> {
+    /// The archive of elites.
+    archive: MapElitesArchive<T>,
+    /// Number of random genes evaluated before the first iteration.
+    initial_population: usize,
+    /// Number of children generated every iteration.
+    batch_size: usize,
+    /// Num of the max iterations the optimiser will perform.
+    iterations: u32,
+    /// The current iteration.
+    current_iteration: u32,
+    /// The selection algorithm choosing the parents among the elites.
+    selection_algorithm: Box<dyn Selection>,
+    /// The probability to cross over two elites. Otherwise the child is a copy of the first one.
+    crossover_rate: f32,
+    /// The probability to mutate every child.
+    mutation_rate: f32,
+    /// The crossover operator to generate the children.
+    crossover_operator: Box<dyn CrossoverOperator<T>>,
+    /// The mutation operator to mutate the children.
+    mutation_operator: Box<dyn MutationOperator<T>>,
+    /// The fitness value to reach to end the optimiser.
+    fitness_goal: f64,
+    /// If the optimiser is running or not.
+    running: bool,
+    /// The best gene overall.
+    best_gene: T,
+    /// The stop reason if the optimiser has stopped.
+    stop_criteria: StopCriteria,
+    /// The logger of this execution.
+    logger: Logger,
+    /// Statistics of the elites after every iteration.
+    statistics: Vec<GenerationStatistics>,
+}
+
+impl<T: Gene + Clone> MapElites<T> {
+    /// Creates a new `MapElites` with an empty archive and default values.
+    /// * `initial_population` = 100
+    /// * `batch_size` = 100
+    /// * `iterations` = 1000
+    /// * `selection_algorithm` = SelectionAlgorithms::Random
+    /// * `crossover_rate` = 0.0
+    /// * `mutation_rate` = 1.0
+    /// * `crossover_operator` = GeneCrossover
+    /// * `mutation_operator` = GeneMutation
+    /// * `fitness_goal` = f64::MAX
+    ///
+    /// # Arguments
+    ///
+    /// * `bounds` - The bounds of every dimension of the behaviour space.
+    /// * `bins` - Number of cells of every dimension.
+    ///
+    /// # Panics
+    ///
+    /// Same as `MapElitesArchive::new`.
+    pub fn new(bounds: &[(f64, f64)], bins: &[usize]) -> Self {
+        MapElites {
+            archive: MapElitesArchive::new(bounds, bins),
+            initial_population: INITIAL_POPULATION_DEFAULT,
+            batch_size: BATCH_SIZE_DEFAULT,
+            iterations: MAX_ITERATIONS_DEFAULT,
+            current_iteration: 0,
+            selection_algorithm: Box::new(SelectionAlgorithms::Random),
+            crossover_rate: CROSSOVER_RATE_DEFAULT,
+            mutation_rate: MUTATION_RATE_DEFAULT,
+            crossover_operator: Box::new(GeneCrossover),
+            mutation_operator: Box::new(GeneMutation),
+            fitness_goal: f64::MAX,
+            running: false,
+            best_gene: T::init(),
+            stop_criteria: StopCriteria::Unknown,
+            logger: Logger::default(),
+            statistics: Vec::new(),
+        }
+    }
+
+    /// Initiate the optimiser.
+    ///
+    /// # Errors
+    ///
+    /// Returns the IO error if the logger destination could not be opened.
+    pub fn init(mut self) -> Result<Self, Box<dyn Error>> {
+        self.logger.open()?;
+        self.start();
+        Ok(self)
+    }
+
+    /// Runs the optimiser by itself without user control.
+    ///
+    /// # Notes
+    ///
+    /// Logger IO errors do not stop the execution. Use `MapElites::try_run` to stop on them.
+    pub fn run(mut self) -> (T, StopCriteria) {
+        if !self.is_running() {
+            self.start();
+        }
+
+        while self.running {
+            self.next_iteration();
+        }
+
+        (self.best_gene, self.stop_criteria)
+    }
+
+    /// Runs the optimiser by itself without user control and returns the archive.
+    pub fn run_archive(mut self) -> (MapElitesArchive<T>, StopCriteria) {
+        if !self.is_running() {
+            self.start();
+        }
+
+        while self.running {
+            self.next_iteration();
+        }
+
+        (self.archive, self.stop_criteria)
+    }
+
+    /// Runs the optimiser by itself without user control, stopping if the logger fails to save.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first IO error produced by the logger.
+    pub fn try_run(mut self) -> Result<(T, StopCriteria), Box<dyn Error>> {
+        if !self.is_running() {
+            self = self.init()?;
+        }
+
+        while self.running {
+            self.next_iteration();
+            if self.logger.get_error().is_some() {
+                self.logger.flush()?;
+            }
+        }
+
+        self.logger.flush()?;
+        Ok((self.best_gene, self.stop_criteria))
+    }
+
+    /// Marks the optimiser as running and fills the archive with the random genes.
+    fn start(&mut self) {
+        self.running = true;
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!("MapElites started with values:\n{}", self).as_str(),
+        );
+        for _ in 0..self.initial_population {
+            let mut gene = T::init();
+            gene.calculate_fitness();
+            self.insert(gene);
+        }
+        self.logger.log(
+            VerbosityLevel::HIGH,
+            format!(
+                "MapElites initiated properly with {} elites.",
+                self.archive.len()
+            )
+            .as_str(),
+        );
+    }
+
+    /// Goes iteration by iteration in case the user wants to have more control over the lifetime of the optimiser.
+    ///
+    /// # Returns
+    ///
+    /// `self.archive` - The archive after inserting the children.
+    pub fn next_iteration(&mut self) -> &MapElitesArchive<T> {
+        let _generation_span = self.logger.enter_generation(self.current_iteration);
+        self.logger.log(
+            VerbosityLevel::LOW,
+            format!(
+                ">>>>>>> Started iteration {} <<<<<<<",
+                self.current_iteration
+            )
+            .as_str(),
+        );
+
+        let mut rng = rand::thread_rng();
+        let elites: Vec<T> = self.archive.elites().into_iter().cloned().collect();
+        let fitnesses: Vec<f64> = elites.iter().map(|gene| gene.get_fitness()).collect();
+        let mut insertions = 0;
+        for _ in 0..self.batch_size {
+            let mut child = if elites.is_empty() {
+                T::init()
+            } else {
+                let parent1 = &elites[self.selection_algorithm.select(&fitnesses)];
+                if rng.gen_range(0.0..1.0) < self.crossover_rate {
+                    let parent2 = &elites[self.selection_algorithm.select(&fitnesses)];
+                    self.crossover_operator.crossover(parent1, parent2)
+                } else {
+                    parent1.clone()
+                }
+            };
+            if rng.gen_range(0.0..1.0) < self.mutation_rate {
+                self.mutation_operator.mutate(&mut child);
+            }
+            child.calculate_fitness();
+            if self.insert(child) {
+                insertions += 1;
+            }
+        }
+        self.logger.log(
+            VerbosityLevel::MID,
+            format!(
+                "{} children inserted, {} elites, coverage = {}, QD-score = {}",
+                insertions,
+                self.archive.len(),
+                self.archive.coverage(),
+                self.archive.qd_score()
+            )
+            .as_str(),
+        );
+        self.logger.log_best_fitness(self.best_gene.get_fitness());
+
+        let elites: Vec<T> = self.archive.elites().into_iter().cloned().collect();
+        self.statistics.push(GenerationStatistics::calculate(
+            self.current_iteration,
+            &elites,
+            true,
+        ));
+
+        self.current_iteration += 1;
+        self.check_stop_criteria();
+
+        &self.archive
+    }
+
+    /// Inserts a gene in the archive, updating the best gene.
+    fn insert(&mut self, gene: T) -> bool {
+        if gene.get_fitness() > self.best_gene.get_fitness() || self.archive.is_empty() {
+            self.best_gene = gene.clone();
+        }
+        self.archive.insert(gene)
+    }
+
+    /// Checks if the optimiser should stop or not.
+    fn check_stop_criteria(&mut self) {
+        if self.best_gene.get_fitness() >= self.fitness_goal {
+            self.running = false;
+            self.stop_criteria = StopCriteria::FitnessAchieved;
+        } else if self.current_iteration >= self.iterations {
+            self.running = false;
+            self.stop_criteria = StopCriteria::MaxIterations;
+        }
+
+        if !self.running {
+            self.logger.log(
+                VerbosityLevel::LOW,
+                format!("Algorithm must stop because of {:?}", self.stop_criteria).as_str(),
+            );
+        }
+    }
+
+    /// Sets the number of random genes evaluated before the first iteration.
+    pub fn initial_population(mut self, initial_population: usize) -> Self {
+        self.initial_population = initial_population;
+        self
+    }
+
+    /// Sets the number of children generated every iteration.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets the max number of iterations the optimiser will perform.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        if iterations <= self.current_iteration {
+            panic!("Number of iterations is not greater  than the actual iteration.");
+        }
+
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the selection algorithm choosing the parents among the elites.
+    pub fn selection_algorithm(mut self, selection_algorithm: Box<dyn Selection>) -> Self {
+        self.selection_algorithm = selection_algorithm;
+        self
+    }
+
+    /// Sets the probability to cross over two elites.
+    pub fn crossover_rate(mut self, crossover_rate: f32) -> Self {
+        if crossover_rate > 1.0 || crossover_rate < 0.0 {
+            panic!("Crossover rate not in rage between 0.0 and 1.0");
+        }
+
+        self.crossover_rate = crossover_rate;
+        self
+    }
+
+    /// Sets the probability to mutate every child.
+    pub fn mutation_rate(mut self, mutation_rate: f32) -> Self {
+        if mutation_rate > 1.0 || mutation_rate < 0.0 {
+            panic!("Mutation rate not in rage between 0.0 and 1.0");
+        }
+
+        self.mutation_rate = mutation_rate;
+        self
+    }
+
+    /// Sets the crossover operator to generate the children.
+    pub fn crossover_operator(mut self, crossover_operator: Box<dyn CrossoverOperator<T>>) -> Self {
+        self.crossover_operator = crossover_operator;
+        self
+    }
+
+    /// Sets the mutation operator to mutate the children.
+    pub fn mutation_operator(mut self, mutation_operator: Box<dyn MutationOperator<T>>) -> Self {
+        self.mutation_operator = mutation_operator;
+        self
+    }
+
+    /// Sets the fitness goal to stop the optimiser.
+    pub fn fitness_goal(mut self, fitness_goal: f64) -> Self {
+        self.fitness_goal = fitness_goal;
+        self
+    }
+
+    /// Sets the logger of this execution.
+    pub fn logger(mut self, logger: Logger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Returns the archive of elites.
+    pub fn get_archive(&self) -> &MapElitesArchive<T> {
+        &self.archive
+    }
+
+    /// Returns the number of random genes evaluated before the first iteration.
+    pub fn get_initial_population(&self) -> usize {
+        self.initial_population
+    }
+
+    /// Returns the number of children generated every iteration.
+    pub fn get_batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Returns the max iterations.
+    pub fn get_iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns the current iteration.
+    pub fn get_current_iteration(&self) -> u32 {
+        self.current_iteration
+    }
+
+    /// Returns the crossover rate.
+    pub fn get_crossover_rate(&self) -> f32 {
+        self.crossover_rate
+    }
+
+    /// Returns the mutation rate.
+    pub fn get_mutation_rate(&self) -> f32 {
+        self.mutation_rate
+    }
+
+    /// Returns the fitness goal.
+    pub fn get_fitness_goal(&self) -> f64 {
+        self.fitness_goal
+    }
+
+    /// Returns the best gene overall.
+    pub fn get_best_gene(&self) -> &T {
+        &self.best_gene
+    }
+
+    /// Returns if the optimiser is running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns the stop reason.
+    pub fn get_stop_criteria(&self) -> StopCriteria {
+        self.stop_criteria
+    }
+
+    /// Returns the statistics of the elites after every iteration.
+    pub fn get_statistics(&self) -> &[GenerationStatistics] {
+        &self.statistics
+    }
+
+    /// Returns the logger of this execution.
+    pub fn get_logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// Returns the logger of this execution as mutable, for example to flush it.
+    pub fn get_logger_mut(&mut self) -> &mut Logger {
+        &mut self.logger
+    }
+}
+
+/// Display trait implementation for MapElites.
+impl<T: Gene + Clone> fmt::Display for MapElites<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+             "{{\n\tbins: {:?},\n\tinitial_population: {},\n\tbatch_size: {},\n\titerations: {},\n\tcurrent_iteration: {},\n\tcrossover_rate: {},\n\tmutation_rate: {},\n\tfitness_goal: {:?},\n\tstop_criteria: {:?},\n\telites: {},\n\tbest_gene_fitness: {}\n}}",
+            self.archive.get_bins(),
+            self.initial_population,
+            self.batch_size,
+            self.iterations,
+            self.current_iteration,
+            self.crossover_rate,
+            self.mutation_rate,
+            self.fitness_goal,
+            self.stop_criteria,
+            self.archive.len(),
+            self.best_gene.get_fitness()
+        )
+    }
+}
@@ -0,0 +1,47 @@
+//! This module contains the restart strategies of the `GeneticAlgorithm` to escape from a stagnated execution.
+//!
+//! A restart re-initialises the generation with `Gene::init`, always keeping the best gene, and can grow the population
+//! size so every restart explores more.
+
+/// The condition that triggers a restart, checked after the fitness calculation of every iteration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RestartTrigger {
+    /// The best fitness has not improved for `iterations` iterations.
+    Stagnation { iterations: u32 },
+    /// The mean pairwise distance of the generation is lower than `threshold`. Without `Gene::distance` it never
+    /// triggers.
+    LowDiversity { threshold: f64 },
+}
+
+/// How the generation is re-initialised in a restart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reinitialisation {
+    /// Every gene but the best one is replaced.
+    Full,
+    /// The worst `fraction` of the genes are replaced. The best gene is always kept.
+    Partial { fraction: f32 },
+}
+
+/// Restart strategy of the `GeneticAlgorithm`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Restart {
+    /// The condition that triggers a restart.
+    pub trigger: RestartTrigger,
+    /// How the generation is re-initialised.
+    pub reinitialisation: Reinitialisation,
+    /// The factor the population size is multiplied by in every restart. 1.0 keeps the population size.
+    pub population_growth: f64,
+    /// The maximum number of restarts, or `None` for no limit.
+    pub max_restarts: Option<u32>,
+}
+
+impl Restart {
+    /// Returns the number of genes kept in a restart of a generation of `len` genes, at least the best one.
+    pub(crate) fn kept(&self, len: usize) -> usize {
+        let kept = match self.reinitialisation {
+            Reinitialisation::Full => 1,
+            Reinitialisation::Partial { fraction } => len - (len as f32 * fraction) as usize,
+        };
+        kept.clamp(1, len.max(1))
+    }
+}
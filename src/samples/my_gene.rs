@@ -38,4 +38,8 @@ impl Gene for MyGene {
     fn get_fitness(&self) -> f64 {
         self.fitness
     }
+
+    fn distance(&self, other: &Self) -> Option<f64> {
+        Some((self.x - other.x).hypot((self.y - other.y) as f64))
+    }
 }
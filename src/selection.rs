@@ -24,6 +24,16 @@ pub trait Selection {
     /// let idx: usize = Selection::select(&v);
     /// println!("Value {} is selected", v[idx]);
     fn select(&self, fitnesses: &Vec<f64>) -> usize;
+
+    /// Returns the number of genes of every tournament if the selection is a tournament. The tournaments can then be
+    /// compared with Deb's feasibility rules instead of the penalized fitness.
+    ///
+    /// # Returns
+    ///
+    /// * `size` as `Option<usize>` - The tournament size, `None` by default.
+    fn tournament_size(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Default selection algorithms supported.
@@ -105,4 +115,11 @@ impl Selection for SelectionAlgorithms {
         }
         winner_idx
     }
+
+    fn tournament_size(&self) -> Option<usize> {
+        match self {
+            SelectionAlgorithms::Tournament(members) => Some(*members),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,208 @@
+//! This module contains the statistics gathered from every generation and the functions to export them.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::Gene;
+
+/// Diversity metrics of a generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Diversity {
+    /// Mean `Gene::distance` between every pair of genes. `None` if the gene does not define a distance or the
+    /// distance metrics are not calculated.
+    pub mean_pairwise_distance: Option<f64>,
+    /// Number of genes at a distance greater than 0.0 of every previous gene. `None` if the gene does not define a
+    /// distance or the distance metrics are not calculated.
+    pub unique_genomes: Option<usize>,
+    /// Shannon entropy of the distinct fitness values. 0.0 means every gene has the same fitness.
+    pub fitness_entropy: f64,
+}
+
+impl Diversity {
+    /// Calculates the diversity metrics of a generation.
+    ///
+    /// # Arguments
+    ///
+    /// * `generation` - The genes with their fitness already calculated.
+    pub fn calculate<T: Gene>(generation: &[T]) -> Self {
+        Diversity {
+            mean_pairwise_distance: mean_pairwise_distance(generation),
+            unique_genomes: unique_genomes(generation),
+            fitness_entropy: fitness_entropy(generation),
+        }
+    }
+
+    /// Calculates only the fitness entropy of a generation, leaving the distance metrics as `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `generation` - The genes with their fitness already calculated.
+    pub fn calculate_without_distances<T: Gene>(generation: &[T]) -> Self {
+        Diversity {
+            mean_pairwise_distance: None,
+            unique_genomes: None,
+            fitness_entropy: fitness_entropy(generation),
+        }
+    }
+}
+
+/// Statistics of a single generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GenerationStatistics {
+    /// The iteration of the generation.
+    pub iteration: u32,
+    /// The best fitness of the generation.
+    pub best_fitness: f64,
+    /// The mean fitness of the generation.
+    pub mean_fitness: f64,
+    /// The worst fitness of the generation.
+    pub worst_fitness: f64,
+    /// The diversity of the generation.
+    pub diversity: Diversity,
+    /// If the generation triggered a restart and was re-initialised after these statistics.
+    pub restarted: bool,
+    /// The mutation rate applied to the generation.
+    pub mutation_rate: f32,
+    /// The mean step size encoded in the genes with `MutationAdaptation::SelfAdaptive`. `None` otherwise or if the
+    /// gene does not define `Gene::step_size`.
+    pub step_size: Option<f64>,
+    /// The number of genes that are not `Gene::is_valid`.
+    pub invalid_genes: usize,
+}
+
+impl GenerationStatistics {
+    /// Calculates the statistics of a generation. The mutation rate is left as 0.0 and the step size as `None`
+    /// to be set by the `GeneticAlgorithm`.
+    ///
+    /// # Arguments
+    ///
+    /// * `iteration` - The iteration of the generation.
+    /// * `generation` - The genes with their fitness already calculated.
+    /// * `distances` - If the distance metrics of the diversity are calculated.
+    ///
+    /// # Notes
+    ///
+    /// The distance metrics need O(n²) calls to `Gene::distance`.
+    pub fn calculate<T: Gene>(iteration: u32, generation: &[T], distances: bool) -> Self {
+        let fitnesses = generation.iter().map(|gene| gene.get_fitness());
+        let best_fitness = fitnesses.clone().fold(f64::MIN, f64::max);
+        let worst_fitness = fitnesses.clone().fold(f64::MAX, f64::min);
+        let mean_fitness = fitnesses.sum::<f64>() / generation.len().max(1) as f64;
+
+        GenerationStatistics {
+            iteration,
+            best_fitness,
+            mean_fitness,
+            worst_fitness,
+            diversity: if distances {
+                Diversity::calculate(generation)
+            } else {
+                Diversity::calculate_without_distances(generation)
+            },
+            restarted: false,
+            mutation_rate: 0.0,
+            step_size: None,
+            invalid_genes: generation.iter().filter(|gene| !gene.is_valid()).count(),
+        }
+    }
+
+    /// Returns the CSV header matching `GenerationStatistics::to_csv`.
+    pub fn csv_header() -> &'static str {
+        "iteration,best_fitness,mean_fitness,worst_fitness,mean_pairwise_distance,unique_genomes,fitness_entropy,restarted,mutation_rate,step_size,invalid_genes"
+    }
+
+    /// Returns the statistics as a CSV row. Missing metrics are left empty.
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            self.iteration,
+            self.best_fitness,
+            self.mean_fitness,
+            self.worst_fitness,
+            self.diversity
+                .mean_pairwise_distance
+                .map_or(String::new(), |distance| distance.to_string()),
+            self.diversity
+                .unique_genomes
+                .map_or(String::new(), |unique| unique.to_string()),
+            self.diversity.fitness_entropy,
+            self.restarted,
+            self.mutation_rate,
+            self.step_size
+                .map_or(String::new(), |step_size| step_size.to_string()),
+            self.invalid_genes
+        )
+    }
+}
+
+/// Writes the statistics as CSV, header included.
+///
+/// # Errors
+///
+/// Returns the IO error produced by the writer.
+pub fn write_csv<W: Write>(statistics: &[GenerationStatistics], mut writer: W) -> io::Result<()> {
+    writeln!(writer, "{}", GenerationStatistics::csv_header())?;
+    for generation_statistics in statistics {
+        writeln!(writer, "{}", generation_statistics.to_csv())?;
+    }
+    writer.flush()
+}
+
+/// Mean distance between every pair of genes.
+fn mean_pairwise_distance<T: Gene>(generation: &[T]) -> Option<f64> {
+    if generation.len() < 2 {
+        return generation
+            .first()
+            .and_then(|gene| gene.distance(gene))
+            .map(|_| 0.0);
+    }
+
+    let mut total = 0.0;
+    let mut pairs = 0;
+    for (i, gene) in generation.iter().enumerate() {
+        for other in &generation[i + 1..] {
+            total += gene.distance(other)?;
+            pairs += 1;
+        }
+    }
+    Some(total / pairs as f64)
+}
+
+/// Number of genes at a distance greater than 0.0 of every previous gene.
+fn unique_genomes<T: Gene>(generation: &[T]) -> Option<usize> {
+    let mut unique: Vec<&T> = Vec::new();
+    for gene in generation {
+        let mut is_unique = true;
+        for other in unique.iter() {
+            if gene.distance(other)? <= 0.0 {
+                is_unique = false;
+                break;
+            }
+        }
+        if is_unique {
+            if unique.is_empty() {
+                // Makes sure the gene defines a distance.
+                gene.distance(gene)?;
+            }
+            unique.push(gene);
+        }
+    }
+    Some(unique.len())
+}
+
+/// Shannon entropy of the distinct fitness values.
+fn fitness_entropy<T: Gene>(generation: &[T]) -> f64 {
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for gene in generation {
+        *counts.entry(gene.get_fitness().to_bits()).or_insert(0) += 1;
+    }
+
+    let total = generation.len() as f64;
+    counts
+        .values()
+        .map(|&count| {
+            let probability = count as f64 / total;
+            -probability * probability.ln()
+        })
+        .sum()
+}
@@ -0,0 +1,21 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+use easy_ga::genotypes::{RealVectorGene, RealVectorProblem};
+
+/// Returns the sum of the squares of the values.
+pub fn sphere(values: &[f64]) -> f64 {
+    values.iter().map(|x| x * x).sum()
+}
+
+/// Sphere of 5 dimensions in `[-5, 5]`, maximised at the origin with fitness 0.0.
+pub struct Sphere;
+
+impl RealVectorProblem for Sphere {
+    const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0); 5];
+
+    fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+        -sphere(gene.values())
+    }
+}
@@ -0,0 +1,211 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod adaptation {
+    use easy_ga::genotypes::{RealVectorGene, RealVectorProblem};
+    use easy_ga::samples::MyGene;
+    use easy_ga::{Gene, GeneticAlgorithm, MutationAdaptation, SelectionAlgorithms};
+
+    struct SelfAdaptiveSphere;
+
+    impl RealVectorProblem for SelfAdaptiveSphere {
+        const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0); 5];
+
+        fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+            -gene.values().iter().map(|x| x * x).sum::<f64>()
+        }
+    }
+
+    fn run<T: Gene + Clone>(genetic_algorithm: GeneticAlgorithm<T>) -> GeneticAlgorithm<T> {
+        let mut genetic_algorithm = genetic_algorithm.init().unwrap();
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+        }
+        genetic_algorithm
+    }
+
+    fn mutation_rates<T: Gene + Clone>(genetic_algorithm: &GeneticAlgorithm<T>) -> Vec<f32> {
+        genetic_algorithm
+            .get_statistics()
+            .iter()
+            .map(|statistics| statistics.mutation_rate)
+            .collect()
+    }
+
+    #[test]
+    fn WhenConstant_ThenMutationRateDoesNotChange() {
+        let genetic_algorithm = run(GeneticAlgorithm::<MyGene>::new()
+            .iterations(20)
+            .mutation_rate(0.3));
+
+        assert_eq!(
+            genetic_algorithm.get_mutation_adaptation(),
+            MutationAdaptation::Constant
+        );
+        assert!(mutation_rates(&genetic_algorithm)
+            .iter()
+            .all(|rate| *rate == 0.3));
+        assert_eq!(genetic_algorithm.get_current_mutation_rate(), 0.3);
+    }
+
+    #[test]
+    fn WhenLinearDecay_ThenMutationRateGoesFromInitialToEnd() {
+        let genetic_algorithm = run(GeneticAlgorithm::<MyGene>::new()
+            .iterations(11)
+            .mutation_rate(0.5)
+            .mutation_adaptation(MutationAdaptation::LinearDecay { end: 0.0 }));
+
+        let rates = mutation_rates(&genetic_algorithm);
+        assert_eq!(rates.len(), 11);
+        for (i, rate) in rates.iter().enumerate() {
+            assert!(
+                (rate - 0.5 * (1.0 - i as f32 / 10.0)).abs() < 1e-6,
+                "{rates:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn WhenExponentialDecay_ThenMutationRateIsMultipliedEveryIteration() {
+        let genetic_algorithm = run(GeneticAlgorithm::<MyGene>::new()
+            .iterations(10)
+            .mutation_rate(0.5)
+            .mutation_adaptation(MutationAdaptation::ExponentialDecay { decay: 0.9 }));
+
+        for (i, rate) in mutation_rates(&genetic_algorithm).iter().enumerate() {
+            assert!((rate - 0.5 * 0.9f32.powi(i as i32)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn WhenMutationsAlwaysImprove_ThenOneFifthRuleIncreasesTheRate() {
+        let genetic_algorithm = run(GeneticAlgorithm::<MyGene>::new()
+            .iterations(10)
+            .mutation_rate(0.1)
+            .mutation_adaptation(MutationAdaptation::OneFifthRule { factor: 1.5 })
+            .mutation_operator(Box::new(|gene: &mut MyGene| gene.x += 1.0)));
+
+        let rates = mutation_rates(&genetic_algorithm);
+        assert_eq!(rates[0], 0.1);
+        assert!(rates.windows(2).all(|pair| pair[1] >= pair[0]), "{rates:?}");
+        assert!(genetic_algorithm.get_current_mutation_rate() > 0.5);
+    }
+
+    #[test]
+    fn WhenMutationsAlwaysWorsen_ThenOneFifthRuleDecreasesTheRate() {
+        let genetic_algorithm = run(GeneticAlgorithm::<MyGene>::new()
+            .iterations(10)
+            .mutation_rate(0.5)
+            .mutation_adaptation(MutationAdaptation::OneFifthRule { factor: 1.5 })
+            .mutation_operator(Box::new(|gene: &mut MyGene| gene.x -= 1.0)));
+
+        let rates = mutation_rates(&genetic_algorithm);
+        assert!(rates.windows(2).all(|pair| pair[1] <= pair[0]), "{rates:?}");
+        assert!(genetic_algorithm.get_current_mutation_rate() < 0.5);
+    }
+
+    #[test]
+    fn WhenDiversityIsLow_ThenHypermutationRateIsUsed() {
+        let genetic_algorithm = run(GeneticAlgorithm::<MyGene>::new()
+            .iterations(5)
+            .mutation_rate(0.1)
+            .mutation_adaptation(MutationAdaptation::Hypermutation {
+                diversity_threshold: f64::MAX,
+                rate: 0.9,
+            }));
+
+        assert!(mutation_rates(&genetic_algorithm)
+            .iter()
+            .all(|rate| *rate == 0.9));
+    }
+
+    #[test]
+    fn WhenDiversityIsHigh_ThenHypermutationKeepsTheInitialRate() {
+        let genetic_algorithm = run(GeneticAlgorithm::<MyGene>::new()
+            .iterations(5)
+            .mutation_rate(0.1)
+            .mutation_adaptation(MutationAdaptation::Hypermutation {
+                diversity_threshold: -1.0,
+                rate: 0.9,
+            }));
+
+        assert!(mutation_rates(&genetic_algorithm)
+            .iter()
+            .all(|rate| *rate == 0.1));
+    }
+
+    #[test]
+    fn WhenSelfAdaptiveWithoutGeneRate_ThenInitialRateIsUsed() {
+        let genetic_algorithm = run(GeneticAlgorithm::<MyGene>::new()
+            .iterations(5)
+            .mutation_rate(0.2)
+            .mutation_adaptation(MutationAdaptation::SelfAdaptive));
+
+        assert!(mutation_rates(&genetic_algorithm)
+            .iter()
+            .all(|rate| (rate - 0.2).abs() < 1e-6));
+    }
+
+    #[test]
+    fn WhenSelfAdaptiveRealVector_ThenStepSizesShrinkNearTheOptimum() {
+        let genetic_algorithm = run(
+            GeneticAlgorithm::<RealVectorGene<SelfAdaptiveSphere>>::new()
+                .iterations(200)
+                .mutation_rate(0.8)
+                .selection_rate(0.5)
+                .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+                .mutation_adaptation(MutationAdaptation::SelfAdaptive),
+        );
+
+        assert!(mutation_rates(&genetic_algorithm)
+            .iter()
+            .all(|rate| (rate - 0.8).abs() < 1e-6));
+        let statistics = genetic_algorithm.get_statistics();
+        let first_step_size = statistics.first().unwrap().step_size.unwrap();
+        let last_step_size = statistics.last().unwrap().step_size.unwrap();
+        assert!(last_step_size < first_step_size, "{last_step_size}");
+        let generation = genetic_algorithm.get_generation();
+        let mean_step_size = generation
+            .iter()
+            .map(|gene| gene.step_sizes().iter().sum::<f64>() / 5.0)
+            .sum::<f64>()
+            / generation.len() as f64;
+        assert!(mean_step_size < 0.01, "{mean_step_size}");
+        let best_fitness = generation
+            .into_iter()
+            .map(|mut gene| gene.calculate_fitness())
+            .fold(f64::MIN, f64::max);
+        assert!(best_fitness > -0.1, "{best_fitness}");
+    }
+
+    #[test]
+    fn WhenNotSelfAdaptive_ThenStepSizesAreNotUsed() {
+        let genetic_algorithm = run(
+            GeneticAlgorithm::<RealVectorGene<SelfAdaptiveSphere>>::new()
+                .iterations(5)
+                .mutation_rate(0.8),
+        );
+
+        assert!(genetic_algorithm
+            .get_statistics()
+            .iter()
+            .all(|statistics| statistics.step_size.is_none()));
+        assert!(genetic_algorithm
+            .get_generation()
+            .iter()
+            .all(|gene| gene.step_sizes() == [SelfAdaptiveSphere::INITIAL_STEP_SIZE; 5]));
+    }
+
+    #[test]
+    fn WhenStatisticsToCsv_ThenMutationRateAndStepSizeAreSaved() {
+        let genetic_algorithm = run(GeneticAlgorithm::<MyGene>::new()
+            .iterations(2)
+            .mutation_rate(0.25));
+
+        assert!(easy_ga::statistics::GenerationStatistics::csv_header()
+            .ends_with(",mutation_rate,step_size,invalid_genes"));
+        assert!(genetic_algorithm.get_statistics()[0]
+            .to_csv()
+            .ends_with(",0.25,,0"));
+    }
+}
@@ -0,0 +1,127 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod alps {
+    use easy_ga::genetic_algorithm::StopCriteria;
+    use easy_ga::genotypes::{BitStringGene, BitStringProblem};
+    use easy_ga::{AgedGene, AgingScheme, Alps, Gene, GeneticAlgorithm, SelectionAlgorithms};
+
+    struct OneMax;
+
+    impl BitStringProblem for OneMax {
+        const LENGTH: usize = 32;
+
+        fn fitness(gene: &BitStringGene<Self>) -> f64 {
+            gene.count_ones() as f64
+        }
+    }
+
+    fn layer(tournament: usize) -> GeneticAlgorithm<AgedGene<BitStringGene<OneMax>>> {
+        GeneticAlgorithm::<AgedGene<BitStringGene<OneMax>>>::new()
+            .population_size(20)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(tournament)))
+    }
+
+    fn layers(n: usize) -> Vec<GeneticAlgorithm<AgedGene<BitStringGene<OneMax>>>> {
+        (0..n).map(|i| layer(2 + i)).collect()
+    }
+
+    #[test]
+    fn WhenAgingScheme_ThenAgeLimitsGrow() {
+        let alps = Alps::new(layers(5)).age_gap(10);
+        let limits: Vec<Option<u32>> = (0..5).map(|i| alps.age_limit(i)).collect();
+        assert_eq!(limits, vec![Some(10), Some(20), Some(40), Some(90), None]);
+
+        let alps = alps.aging_scheme(AgingScheme::Linear);
+        let limits: Vec<Option<u32>> = (0..5).map(|i| alps.age_limit(i)).collect();
+        assert_eq!(limits, vec![Some(10), Some(20), Some(30), Some(40), None]);
+
+        let alps = alps.aging_scheme(AgingScheme::Exponential);
+        let limits: Vec<Option<u32>> = (0..5).map(|i| alps.age_limit(i)).collect();
+        assert_eq!(limits, vec![Some(10), Some(20), Some(40), Some(80), None]);
+    }
+
+    #[test]
+    fn WhenIterating_ThenEveryLayerRespectsItsAgeLimit() {
+        let mut alps = Alps::new(layers(3))
+            .age_gap(3)
+            .iterations(40)
+            .init()
+            .unwrap();
+        while alps.is_running() {
+            alps.next_iteration();
+            for (i, layer) in alps.get_layers().iter().enumerate() {
+                let age_limit = alps.age_limit(i).unwrap_or(u32::MAX);
+                assert!(layer
+                    .get_generation()
+                    .iter()
+                    .all(|gene| gene.get_age() <= age_limit));
+            }
+        }
+
+        let oldest = alps.get_layers()[2]
+            .get_generation()
+            .iter()
+            .map(|gene| gene.get_age())
+            .max()
+            .unwrap();
+        assert!(oldest > alps.age_limit(1).unwrap());
+    }
+
+    #[test]
+    fn WhenAgeGapPasses_ThenTheBottomLayerIsInjected() {
+        let mut alps = Alps::new(layers(2))
+            .age_gap(5)
+            .iterations(20)
+            .init()
+            .unwrap();
+        for _ in 0..5 {
+            alps.next_iteration();
+        }
+        assert!(alps.get_layers()[0]
+            .get_generation()
+            .iter()
+            .any(|gene| gene.get_age() > 0));
+
+        alps.next_iteration();
+        assert!(alps.get_layers()[0]
+            .get_generation()
+            .iter()
+            .all(|gene| gene.get_age() == 0));
+    }
+
+    #[test]
+    fn WhenRun_ThenTheProblemIsSolved() {
+        let (best_gene, stop_criteria) = Alps::new(layers(3))
+            .age_gap(5)
+            .iterations(300)
+            .fitness_goal(32.0)
+            .run();
+
+        assert_eq!(stop_criteria, StopCriteria::FitnessAchieved);
+        assert_eq!(best_gene.count_ones(), 32);
+    }
+
+    #[test]
+    fn WhenCrossedOver_ThenTheChildTakesTheOldestAge() {
+        let mut alps = Alps::new(layers(2)).iterations(20).init().unwrap();
+        for _ in 0..3 {
+            alps.next_iteration();
+        }
+        let generation = alps.get_layers()[1].get_generation();
+
+        let child = generation[0].crossover(&generation[1]);
+        assert_eq!(
+            child.get_age(),
+            generation[0].get_age().max(generation[1].get_age())
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenThereAreNoLayers_ThenPanics() {
+        Alps::<BitStringGene<OneMax>>::new(Vec::new());
+    }
+}
@@ -0,0 +1,192 @@
+#![allow(non_snake_case)]
+
+mod common;
+
+#[cfg(test)]
+mod cma_es {
+    use easy_ga::genetic_algorithm::StopCriteria;
+    use easy_ga::genotypes::{RealVectorGene, RealVectorProblem};
+    use easy_ga::{CmaEs, CmaRestart, Gene};
+    use std::cell::Cell;
+
+    use super::common::{sphere, Sphere};
+
+    thread_local! {
+        static EVALUATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Sphere counting its evaluations.
+    struct CountedSphere;
+
+    impl RealVectorProblem for CountedSphere {
+        const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0); 5];
+
+        fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+            EVALUATIONS.with(|evaluations| evaluations.set(evaluations.get() + 1));
+            -sphere(gene.values())
+        }
+    }
+
+    /// Ill-conditioned and non-separable: the squares of the partial sums weighted up to 10^6.
+    struct RotatedEllipsoid;
+
+    impl RealVectorProblem for RotatedEllipsoid {
+        const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0); 6];
+
+        fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+            let mut partial_sum = 0.0;
+            -gene
+                .values()
+                .iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    partial_sum += x - 1.0;
+                    10f64.powf(6.0 * i as f64 / 5.0) * partial_sum * partial_sum
+                })
+                .sum::<f64>()
+        }
+    }
+
+    struct Rastrigin;
+
+    impl RealVectorProblem for Rastrigin {
+        const BOUNDS: &'static [(f64, f64)] = &[(-5.12, 5.12); 4];
+
+        fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+            -gene
+                .values()
+                .iter()
+                .map(|x| 10.0 + x * x - 10.0 * (2.0 * std::f64::consts::PI * x).cos())
+                .sum::<f64>()
+        }
+    }
+
+    /// The optimum is the upper corner of the bounds.
+    struct Plane;
+
+    impl RealVectorProblem for Plane {
+        const BOUNDS: &'static [(f64, f64)] = &[(-1.0, 2.0); 3];
+
+        fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+            gene.values().iter().sum()
+        }
+    }
+
+    #[test]
+    fn WhenSphere_ThenIsSolved() {
+        let (gene, _) = CmaEs::<Sphere>::new().iterations(300).run();
+
+        assert!(gene.get_fitness() > -1e-10, "{gene:?}");
+    }
+
+    #[test]
+    fn WhenIllConditionedNonSeparableProblem_ThenIsSolved() {
+        let (gene, _) = CmaEs::<RotatedEllipsoid>::new().iterations(1500).run();
+
+        assert!(gene.get_fitness() > -1e-6, "{gene:?}");
+    }
+
+    #[test]
+    fn WhenNoRestarts_ThenStopsWithConverged() {
+        let (gene, stop_criteria) = CmaEs::<Sphere>::new().iterations(5000).run();
+
+        assert_eq!(stop_criteria, StopCriteria::Converged);
+        assert!(gene.get_fitness() > -1e-12);
+    }
+
+    #[test]
+    fn WhenIpop_ThenPopulationSizeIncreasesOnEveryRestart() {
+        let mut cma_es = CmaEs::<Rastrigin>::new()
+            .iterations(3000)
+            .restart_strategy(CmaRestart::Ipop { increase: 2.0 })
+            .max_restarts(3)
+            .init()
+            .unwrap();
+        let initial_population_size = cma_es.get_current_population_size();
+        while cma_es.is_running() {
+            cma_es.next_iteration();
+        }
+
+        assert!(cma_es.get_restarts() > 0);
+        assert_eq!(
+            cma_es.get_current_population_size(),
+            initial_population_size * 2usize.pow(cma_es.get_restarts())
+        );
+    }
+
+    #[test]
+    fn WhenNew_ThenNothingIsEvaluatedUntilTheFirstIteration() {
+        EVALUATIONS.with(|evaluations| evaluations.set(0));
+        let mut cma_es = CmaEs::<CountedSphere>::new()
+            .population_size(8)
+            .init()
+            .unwrap();
+
+        assert!(cma_es.get_best_gene().is_none());
+        assert_eq!(EVALUATIONS.with(|evaluations| evaluations.get()), 0);
+
+        let best_fitness = cma_es
+            .next_iteration()
+            .iter()
+            .map(|gene| gene.get_fitness())
+            .fold(f64::MIN, f64::max);
+
+        assert_eq!(EVALUATIONS.with(|evaluations| evaluations.get()), 8);
+        assert_eq!(cma_es.get_best_gene().unwrap().get_fitness(), best_fitness);
+    }
+
+    #[test]
+    fn WhenBipop_ThenRestartsAndFindsAGoodOptimum() {
+        let mut cma_es = CmaEs::<Rastrigin>::new()
+            .iterations(3000)
+            .restart_strategy(CmaRestart::Bipop)
+            .max_restarts(6)
+            .init()
+            .unwrap();
+        while cma_es.is_running() {
+            cma_es.next_iteration();
+        }
+
+        assert!(cma_es.get_restarts() > 0);
+        assert!(cma_es.get_best_gene().unwrap().get_fitness() > -3.0);
+    }
+
+    #[test]
+    fn WhenOptimumIsOnTheBounds_ThenGenesStayInBounds() {
+        let mut cma_es = CmaEs::<Plane>::new().iterations(100).init().unwrap();
+        while cma_es.is_running() {
+            for gene in cma_es.next_iteration() {
+                assert!(gene.values().iter().all(|x| *x >= -1.0 && *x <= 2.0));
+            }
+        }
+
+        assert!(cma_es.get_best_gene().unwrap().get_fitness() > 5.99);
+    }
+
+    #[test]
+    fn WhenRun_ThenStatisticsOfEveryGenerationAreSaved() {
+        let mut cma_es = CmaEs::<Sphere>::new()
+            .iterations(20)
+            .population_size(12)
+            .init()
+            .unwrap();
+        while cma_es.is_running() {
+            assert_eq!(cma_es.next_iteration().len(), 12);
+        }
+
+        let statistics = cma_es.get_statistics();
+        assert_eq!(statistics.len(), 20);
+        for (i, generation_statistics) in statistics.iter().enumerate() {
+            assert_eq!(generation_statistics.iteration, i as u32);
+            assert!(generation_statistics.best_fitness >= generation_statistics.worst_fitness);
+        }
+    }
+
+    #[test]
+    fn WhenFitnessGoalIsReached_ThenStopsWithFitnessAchieved() {
+        let (gene, stop_criteria) = CmaEs::<Sphere>::new().fitness_goal(-1e-3).run();
+
+        assert_eq!(stop_criteria, StopCriteria::FitnessAchieved);
+        assert!(gene.get_fitness() >= -1e-3);
+    }
+}
@@ -0,0 +1,177 @@
+#![allow(non_snake_case)]
+
+mod common;
+
+#[cfg(test)]
+mod coevolution {
+    use easy_ga::genetic_algorithm::StopCriteria;
+    use easy_ga::genotypes::{BitStringGene, BitStringProblem, RealVectorGene, RealVectorProblem};
+    use easy_ga::{
+        CoEvolution, CoEvolutionMode, CoevolvedGene, Gene, GeneticAlgorithm, SelectionAlgorithms,
+    };
+
+    use super::common::sphere;
+
+    struct Half;
+
+    impl RealVectorProblem for Half {
+        const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0); 2];
+
+        // The fitness is given by the interaction.
+        fn fitness(_gene: &RealVectorGene<Self>) -> f64 {
+            0.0
+        }
+    }
+
+    struct Strategy;
+
+    impl RealVectorProblem for Strategy {
+        const BOUNDS: &'static [(f64, f64)] = &[(0.0, 1.0)];
+
+        fn fitness(_gene: &RealVectorGene<Self>) -> f64 {
+            0.0
+        }
+    }
+
+    struct Bits;
+
+    impl BitStringProblem for Bits {
+        const LENGTH: usize = 16;
+
+        fn fitness(_gene: &BitStringGene<Self>) -> f64 {
+            0.0
+        }
+    }
+
+    fn population<T: Gene + Clone>() -> GeneticAlgorithm<CoevolvedGene<T>> {
+        GeneticAlgorithm::<CoevolvedGene<T>>::new()
+            .population_size(30)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+    }
+
+    #[test]
+    fn WhenCooperative_ThenSubcomponentsAreSolvedTogether() {
+        let ((best_a, best_b), stop_criteria) = CoEvolution::new(
+            population::<RealVectorGene<Half>>(),
+            population::<RealVectorGene<Half>>(),
+            Box::new(|a: &RealVectorGene<Half>, b: &RealVectorGene<Half>| {
+                let fitness = -sphere(a.values()) - sphere(b.values());
+                (fitness, fitness)
+            }),
+        )
+        .iterations(150)
+        .run();
+
+        assert_eq!(stop_criteria, StopCriteria::MaxIterations);
+        assert!(sphere(best_a.values()) < 0.05, "{best_a:?}");
+        assert!(sphere(best_b.values()) < 0.05, "{best_b:?}");
+    }
+
+    #[test]
+    fn WhenCooperativeWithoutInit_ThenTheFirstEvaluationUsesARandomRepresentative() {
+        let mut coevolution = CoEvolution::new(
+            population::<RealVectorGene<Half>>(),
+            population::<RealVectorGene<Half>>(),
+            Box::new(|a: &RealVectorGene<Half>, b: &RealVectorGene<Half>| {
+                let fitness = -1.0 - sphere(a.values()) - sphere(b.values());
+                (fitness, fitness)
+            }),
+        )
+        .iterations(5);
+        coevolution.next_iteration();
+
+        assert!(coevolution.is_running());
+        assert!(coevolution.get_best_gene_a().unwrap().get_fitness() <= -1.0);
+        assert!(coevolution.get_best_gene_b().unwrap().get_fitness() <= -1.0);
+    }
+
+    #[test]
+    fn WhenCompetitive_ThenBothPopulationsRaceUp() {
+        let mut coevolution = CoEvolution::new(
+            population::<RealVectorGene<Strategy>>(),
+            population::<RealVectorGene<Strategy>>(),
+            Box::new(
+                |a: &RealVectorGene<Strategy>, b: &RealVectorGene<Strategy>| {
+                    if a.values()[0] > b.values()[0] {
+                        (1.0, 0.0)
+                    } else {
+                        (0.0, 1.0)
+                    }
+                },
+            ),
+        )
+        .mode(CoEvolutionMode::Competitive {
+            opponents: 5,
+            hall_of_fame_opponents: 2,
+        })
+        .iterations(60)
+        .hall_of_fame_size(5)
+        .init()
+        .unwrap();
+        while coevolution.is_running() {
+            coevolution.next_iteration();
+        }
+
+        let mean = |generation: Vec<CoevolvedGene<RealVectorGene<Strategy>>>| {
+            generation
+                .iter()
+                .map(|gene| gene.gene().values()[0])
+                .sum::<f64>()
+                / generation.len() as f64
+        };
+        assert!(mean(coevolution.get_population_a().get_generation()) > 0.8);
+        assert!(mean(coevolution.get_population_b().get_generation()) > 0.8);
+        assert_eq!(coevolution.get_hall_of_fame_a().len(), 5);
+        assert_eq!(coevolution.get_hall_of_fame_b().len(), 5);
+    }
+
+    #[test]
+    fn WhenPopulationsHaveDifferentGenes_ThenBothAreEvolved() {
+        let mut coevolution = CoEvolution::new(
+            population::<BitStringGene<Bits>>(),
+            population::<RealVectorGene<Half>>(),
+            Box::new(|a: &BitStringGene<Bits>, b: &RealVectorGene<Half>| {
+                (a.count_ones() as f64, -sphere(b.values()))
+            }),
+        )
+        .iterations(50)
+        .init()
+        .unwrap();
+        coevolution.next_iteration();
+
+        let (representative_a, representative_b) = coevolution.get_representatives();
+        assert!(representative_a.is_some() && representative_b.is_some());
+        assert_eq!(coevolution.get_hall_of_fame_a().len(), 1);
+        assert_eq!(
+            coevolution.get_best_gene_a().unwrap().get_fitness(),
+            coevolution.get_best_gene_a().unwrap().gene().count_ones() as f64
+        );
+    }
+
+    #[test]
+    fn WhenFitnessGoalIsReached_ThenStopsWithFitnessAchieved() {
+        let ((best_a, _), stop_criteria) = CoEvolution::new(
+            population::<BitStringGene<Bits>>(),
+            population::<RealVectorGene<Half>>(),
+            Box::new(|a: &BitStringGene<Bits>, b: &RealVectorGene<Half>| {
+                (a.count_ones() as f64, -sphere(b.values()))
+            }),
+        )
+        .iterations(500)
+        .fitness_goal(16.0)
+        .run();
+
+        assert_eq!(stop_criteria, StopCriteria::FitnessAchieved);
+        assert_eq!(best_a.count_ones(), 16);
+    }
+
+    #[test]
+    fn WhenCoevolvedGeneIsCrossedOver_ThenTheFitnessIsAssignedAgain() {
+        let mut gene = CoevolvedGene::<BitStringGene<Bits>>::init();
+
+        assert_eq!(gene.calculate_fitness(), 0.0);
+        assert_eq!(gene.crossover(&gene).get_fitness(), 0.0);
+    }
+}
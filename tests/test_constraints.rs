@@ -0,0 +1,240 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod constraints {
+    use easy_ga::constraints::DEATH_PENALTY;
+    use easy_ga::genotypes::{BitStringGene, BitStringProblem};
+    use easy_ga::samples::MyGene;
+    use easy_ga::selection::Selection;
+    use easy_ga::{ConstraintHandling, Gene, GeneticAlgorithm, SelectionAlgorithms};
+
+    const WEIGHTS: [f64; 20] = [
+        12.0, 7.0, 11.0, 8.0, 9.0, 6.0, 14.0, 5.0, 10.0, 13.0, 4.0, 15.0, 3.0, 9.0, 8.0, 12.0, 6.0,
+        11.0, 7.0, 10.0,
+    ];
+    const VALUES: [f64; 20] = [
+        24.0, 13.0, 23.0, 15.0, 16.0, 11.0, 28.0, 9.0, 20.0, 25.0, 8.0, 30.0, 5.0, 17.0, 16.0,
+        22.0, 12.0, 21.0, 14.0, 19.0,
+    ];
+    const CAPACITY: f64 = 50.0;
+
+    struct Knapsack;
+
+    impl Knapsack {
+        fn weight(gene: &BitStringGene<Self>) -> f64 {
+            gene.iter()
+                .zip(WEIGHTS.iter())
+                .filter(|(bit, _)| *bit)
+                .map(|(_, weight)| weight)
+                .sum()
+        }
+    }
+
+    impl BitStringProblem for Knapsack {
+        const LENGTH: usize = 20;
+
+        fn fitness(gene: &BitStringGene<Self>) -> f64 {
+            gene.iter()
+                .zip(VALUES.iter())
+                .filter(|(bit, _)| *bit)
+                .map(|(_, value)| value)
+                .sum()
+        }
+
+        fn constraint_violation(gene: &BitStringGene<Self>) -> f64 {
+            (Self::weight(gene) - CAPACITY).max(0.0)
+        }
+
+        // Removes the items from the last one until the knapsack is not overweight.
+        fn repair(gene: &mut BitStringGene<Self>) {
+            for i in (0..Self::LENGTH).rev() {
+                if Self::weight(gene) <= CAPACITY {
+                    break;
+                }
+                gene.set(i, false);
+            }
+        }
+    }
+
+    fn knapsack_algorithm(
+        constraint_handling: ConstraintHandling,
+    ) -> GeneticAlgorithm<BitStringGene<Knapsack>> {
+        GeneticAlgorithm::<BitStringGene<Knapsack>>::new()
+            .iterations(100)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .constraint_handling(constraint_handling)
+    }
+
+    fn run<T: Gene + Clone>(genetic_algorithm: GeneticAlgorithm<T>) -> GeneticAlgorithm<T> {
+        let mut genetic_algorithm = genetic_algorithm.init().unwrap();
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+        }
+        genetic_algorithm
+    }
+
+    #[test]
+    fn WhenNoConstraintHandling_ThenFitnessesAreNotChanged() {
+        let fitnesses = ConstraintHandling::None.penalized_fitnesses(&[1.0, 2.0], &[0.0, 5.0], 3);
+
+        assert_eq!(fitnesses, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn WhenStaticPenalty_ThenInfeasibleFitnessIsPenalizedByTheViolation() {
+        let fitnesses = ConstraintHandling::StaticPenalty { coefficient: 2.0 }.penalized_fitnesses(
+            &[10.0, 10.0],
+            &[0.0, 3.0],
+            0,
+        );
+
+        assert_eq!(fitnesses, vec![10.0, 4.0]);
+    }
+
+    #[test]
+    fn WhenDynamicPenalty_ThenPenaltyGrowsWithTheIterations() {
+        let handling = ConstraintHandling::DynamicPenalty {
+            coefficient: 0.5,
+            exponent: 2.0,
+        };
+
+        assert_eq!(handling.penalized_fitnesses(&[10.0], &[1.0], 0), vec![9.75]);
+        assert_eq!(handling.penalized_fitnesses(&[10.0], &[1.0], 3), vec![6.0]);
+        assert_eq!(handling.penalized_fitnesses(&[10.0], &[0.0], 3), vec![10.0]);
+    }
+
+    #[test]
+    fn WhenFeasibilityRules_ThenFeasibleGenesAreBetterAndInfeasibleAreComparedByViolation() {
+        let fitnesses = ConstraintHandling::FeasibilityRules.penalized_fitnesses(
+            &[1.0, 5.0, 100.0, 50.0],
+            &[0.0, 0.0, 2.0, 1.0],
+            0,
+        );
+
+        assert_eq!(fitnesses[0], 1.0);
+        assert_eq!(fitnesses[1], 5.0);
+        assert!(fitnesses[3] < fitnesses[0]);
+        assert!(fitnesses[2] < fitnesses[3]);
+    }
+
+    #[test]
+    fn WhenFeasibilityRulesWithoutFeasibleGenes_ThenLowerViolationIsBetter() {
+        let fitnesses =
+            ConstraintHandling::FeasibilityRules.penalized_fitnesses(&[100.0, 1.0], &[3.0, 1.0], 0);
+
+        assert!(fitnesses[1] > fitnesses[0]);
+    }
+
+    #[test]
+    fn WhenDeathPenalty_ThenInfeasibleGenesGetTheLowestFitness() {
+        let fitnesses =
+            ConstraintHandling::DeathPenalty.penalized_fitnesses(&[1.0, 100.0], &[0.0, 0.1], 0);
+
+        assert_eq!(fitnesses, vec![1.0, 1.0 - DEATH_PENALTY]);
+        assert!(fitnesses.iter().sum::<f64>().is_finite());
+    }
+
+    #[test]
+    fn WhenCompare_ThenFeasibleThenLowerViolationThenHigherFitnessWins() {
+        assert!(ConstraintHandling::compare((1.0, 0.0), (100.0, 0.5)).is_gt());
+        assert!(ConstraintHandling::compare((1.0, 0.5), (100.0, 1.0)).is_gt());
+        assert!(ConstraintHandling::compare((100.0, 1.0), (1.0, 1.0)).is_gt());
+        assert!(ConstraintHandling::compare((1.0, 0.0), (100.0, 0.0)).is_lt());
+        assert!(ConstraintHandling::compare((1.0, -2.0), (1.0, 0.0)).is_eq());
+    }
+
+    #[test]
+    fn WhenTournamentOfEveryGene_ThenWinsTheBestByFeasibilityRules() {
+        let fitnesses = [100.0, 5.0, 1.0, 50.0];
+        let violations = [2.0, 0.0, 0.0, 1.0];
+
+        for _ in 0..10 {
+            assert_eq!(
+                ConstraintHandling::tournament(&fitnesses, &violations, 4),
+                1
+            );
+        }
+        assert_eq!(ConstraintHandling::tournament(&[3.0], &[1.0], 4), 0);
+    }
+
+    #[test]
+    fn WhenFeasibilityRulesWithTournament_ThenTournamentsUseTheViolation() {
+        assert!(ConstraintHandling::FeasibilityRules.uses_feasibility_rules());
+        assert!(ConstraintHandling::Repair.uses_feasibility_rules());
+        assert!(!ConstraintHandling::DeathPenalty.uses_feasibility_rules());
+        assert_eq!(
+            SelectionAlgorithms::Tournament(4).tournament_size(),
+            Some(4)
+        );
+        assert_eq!(SelectionAlgorithms::Roulette.tournament_size(), None);
+    }
+
+    #[test]
+    fn WhenGeneWithoutConstraints_ThenBestFeasibleGeneIsTheBestGene() {
+        let genetic_algorithm = run(GeneticAlgorithm::<MyGene>::new()
+            .iterations(10)
+            .constraint_handling(ConstraintHandling::FeasibilityRules));
+
+        let best_feasible_gene = genetic_algorithm.get_best_feasible_gene().unwrap();
+        assert_eq!(
+            best_feasible_gene.get_fitness(),
+            genetic_algorithm.get_best_gene().unwrap().get_fitness()
+        );
+    }
+
+    #[test]
+    fn WhenConstraintsAreIgnored_ThenBestGeneIsInfeasible() {
+        let genetic_algorithm = run(knapsack_algorithm(ConstraintHandling::None));
+
+        assert!(
+            genetic_algorithm
+                .get_best_gene()
+                .unwrap()
+                .constraint_violation()
+                > 0.0
+        );
+    }
+
+    #[test]
+    fn WhenConstraintHandling_ThenBestFeasibleGeneIsFound() {
+        for handling in [
+            ConstraintHandling::StaticPenalty { coefficient: 10.0 },
+            ConstraintHandling::DynamicPenalty {
+                coefficient: 1.0,
+                exponent: 2.0,
+            },
+            ConstraintHandling::FeasibilityRules,
+            ConstraintHandling::Repair,
+            ConstraintHandling::DeathPenalty,
+        ] {
+            let genetic_algorithm = run(knapsack_algorithm(handling));
+
+            assert_eq!(genetic_algorithm.get_constraint_handling(), handling);
+            let best_feasible_gene = genetic_algorithm.get_best_feasible_gene().unwrap();
+            assert_eq!(best_feasible_gene.constraint_violation(), 0.0);
+            assert!(Knapsack::weight(&best_feasible_gene) <= CAPACITY);
+            assert!(
+                best_feasible_gene.get_fitness() >= 80.0,
+                "{handling:?}: {}",
+                best_feasible_gene.get_fitness()
+            );
+        }
+    }
+
+    #[test]
+    fn WhenRepair_ThenEvaluatedGenesAreRepaired() {
+        let mut genetic_algorithm = knapsack_algorithm(ConstraintHandling::Repair)
+            .mutation_rate(0.0)
+            .crossover_rate(0.0)
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+
+        // Without crossover and mutation the new generation only has copies of the repaired genes.
+        for gene in genetic_algorithm.get_generation() {
+            assert_eq!(gene.constraint_violation(), 0.0);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+#![allow(non_snake_case)]
+
+mod common;
+
+#[cfg(test)]
+mod differential_evolution {
+    use easy_ga::genetic_algorithm::StopCriteria;
+    use easy_ga::genotypes::{RealVectorGene, RealVectorProblem};
+    use easy_ga::{DifferentialAdaptation, DifferentialEvolution, DifferentialStrategy, Gene};
+
+    use super::common::Sphere;
+
+    struct Rosenbrock;
+
+    impl RealVectorProblem for Rosenbrock {
+        const BOUNDS: &'static [(f64, f64)] = &[(-2.0, 2.0); 3];
+
+        fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+            -gene
+                .values()
+                .windows(2)
+                .map(|x| 100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2))
+                .sum::<f64>()
+        }
+    }
+
+    #[test]
+    fn WhenEveryStrategy_ThenSphereIsSolved() {
+        for strategy in [
+            DifferentialStrategy::Rand1Bin,
+            DifferentialStrategy::Best1Bin,
+            DifferentialStrategy::CurrentToBest1Bin,
+        ] {
+            let (gene, stop_criteria) = DifferentialEvolution::<Sphere>::new()
+                .iterations(300)
+                .strategy(strategy)
+                .run();
+
+            assert_eq!(stop_criteria, StopCriteria::MaxIterations);
+            assert!(gene.get_fitness() > -1e-6, "{strategy:?}: {gene:?}");
+        }
+    }
+
+    #[test]
+    fn WhenJade_ThenParametersAdaptAndRosenbrockIsSolved() {
+        let mut differential_evolution = DifferentialEvolution::<Rosenbrock>::new()
+            .iterations(500)
+            .strategy(DifferentialStrategy::CurrentToBest1Bin)
+            .adaptation(DifferentialAdaptation::Jade { c: 0.1, p: 0.1 })
+            .init()
+            .unwrap();
+        while differential_evolution.is_running() {
+            differential_evolution.next_iteration();
+        }
+
+        assert_ne!(differential_evolution.get_parameter_means(), (0.5, 0.9));
+        assert!(
+            differential_evolution
+                .get_best_gene()
+                .unwrap()
+                .get_fitness()
+                > -1e-3,
+            "{:?}",
+            differential_evolution.get_best_gene().unwrap()
+        );
+    }
+
+    #[test]
+    fn WhenConstant_ThenParametersDoNotChange() {
+        let mut differential_evolution = DifferentialEvolution::<Sphere>::new()
+            .iterations(10)
+            .weight(0.7)
+            .crossover_rate(0.3)
+            .init()
+            .unwrap();
+        differential_evolution.next_iteration();
+
+        assert_eq!(differential_evolution.get_parameter_means(), (0.7, 0.3));
+    }
+
+    #[test]
+    fn WhenRun_ThenStatisticsOfEveryGenerationAreSavedAndBestNeverGetsWorse() {
+        let mut differential_evolution = DifferentialEvolution::<Sphere>::new()
+            .iterations(20)
+            .population_size(10)
+            .init()
+            .unwrap();
+        let mut best_fitness = differential_evolution
+            .get_best_gene()
+            .unwrap()
+            .get_fitness();
+        while differential_evolution.is_running() {
+            let population = differential_evolution.next_iteration();
+            assert_eq!(population.len(), 10);
+            let fitness = differential_evolution
+                .get_best_gene()
+                .unwrap()
+                .get_fitness();
+            assert!(fitness >= best_fitness);
+            best_fitness = fitness;
+        }
+
+        assert_eq!(differential_evolution.get_statistics().len(), 20);
+        assert_eq!(differential_evolution.get_current_iteration(), 20);
+    }
+
+    #[test]
+    fn WhenNextIterationWithoutInit_ThenFirstPopulationIsEvaluated() {
+        let mut differential_evolution = DifferentialEvolution::<Sphere>::new().iterations(5);
+        assert!(differential_evolution.get_best_gene().is_none());
+
+        let population = differential_evolution.next_iteration();
+        for gene in population {
+            assert_eq!(gene.get_fitness(), Sphere::fitness(gene));
+        }
+        assert!(differential_evolution.is_running());
+        let best_gene = differential_evolution.get_best_gene().unwrap();
+        assert_eq!(best_gene.get_fitness(), Sphere::fitness(best_gene));
+        assert!(best_gene.get_fitness() < 0.0);
+    }
+
+    #[test]
+    fn WhenFitnessGoalIsReached_ThenStopsWithFitnessAchieved() {
+        let (gene, stop_criteria) = DifferentialEvolution::<Sphere>::new()
+            .fitness_goal(-0.1)
+            .run();
+
+        assert_eq!(stop_criteria, StopCriteria::FitnessAchieved);
+        assert!(gene.get_fitness() >= -0.1);
+    }
+
+    #[test]
+    fn WhenPopulationConverges_ThenStopsWithConverged() {
+        let (_, stop_criteria) = DifferentialEvolution::<Sphere>::new()
+            .convergence_threshold(0.01)
+            .run();
+
+        assert_eq!(stop_criteria, StopCriteria::Converged);
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenPopulationSizeIsTooSmall_ThenPanics() {
+        DifferentialEvolution::<Sphere>::new().population_size(3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenCrossoverRateOverflow_ThenPanics() {
+        DifferentialEvolution::<Sphere>::new().crossover_rate(1.5);
+    }
+}
@@ -1,4 +1,5 @@
 #![allow(non_snake_case)]
+#![allow(clippy::bool_assert_comparison)]
 
 #[cfg(test)]
 mod genetic_algorithm {
@@ -0,0 +1,1288 @@
+#![allow(non_snake_case)]
+
+mod common;
+
+#[cfg(test)]
+mod bit_string {
+    use easy_ga::genetic_algorithm::StopCriteria;
+    use easy_ga::genotypes::{
+        BitStringCrossover, BitStringFitness, BitStringGene, BitStringProblem,
+    };
+    use easy_ga::Gene;
+    use easy_ga::GeneticAlgorithm;
+    use easy_ga::SelectionAlgorithms;
+    use easy_ga::{Reinitialisation, Restart, RestartTrigger};
+    use std::sync::Arc;
+
+    struct OneMax;
+
+    impl BitStringProblem for OneMax {
+        const LENGTH: usize = 100;
+        const CROSSOVER: BitStringCrossover = BitStringCrossover::Uniform;
+
+        fn fitness(gene: &BitStringGene<Self>) -> f64 {
+            gene.count_ones() as f64
+        }
+    }
+
+    /// Matches a target only known at runtime.
+    struct Target(Vec<bool>);
+
+    impl BitStringProblem for Target {
+        const LENGTH: usize = 40;
+
+        fn evaluate(&self, gene: &BitStringGene<Self>) -> f64 {
+            gene.iter()
+                .zip(self.0.iter())
+                .filter(|(a, b)| a == *b)
+                .count() as f64
+        }
+    }
+
+    #[test]
+    fn WhenInit_ThenGeneHasTheProblemLength() {
+        let gene = BitStringGene::<OneMax>::init();
+
+        assert_eq!(gene.len(), 100);
+        assert_eq!(gene.iter().count(), 100);
+        assert!(gene.count_ones() <= 100);
+    }
+
+    #[test]
+    fn WhenSetAndFlip_ThenBitsChange() {
+        let mut gene = BitStringGene::<OneMax>::zeros();
+
+        gene.set(70, true);
+        gene.flip(3);
+        gene.flip(70);
+
+        assert!(gene.get(3));
+        assert!(!gene.get(70));
+        assert_eq!(gene.count_ones(), 1);
+    }
+
+    #[test]
+    fn WhenCrossover_ThenEveryBitComesFromAParent() {
+        let zeros = BitStringGene::<OneMax>::zeros();
+        let ones = BitStringGene::<OneMax>::from_bools(&[true; 100]);
+
+        for child in [
+            zeros.one_point_crossover(&ones),
+            zeros.two_point_crossover(&ones),
+            zeros.uniform_crossover(&ones),
+        ] {
+            assert_eq!(child.len(), 100);
+            assert_eq!(
+                child.hamming_distance(&zeros) + child.hamming_distance(&ones),
+                100
+            );
+        }
+    }
+
+    #[test]
+    fn WhenOnePointCrossover_ThenBitsAreContiguous() {
+        let zeros = BitStringGene::<OneMax>::zeros();
+        let ones = BitStringGene::<OneMax>::from_bools(&[true; 100]);
+
+        let child = zeros.one_point_crossover(&ones);
+        let ones_count = child.count_ones();
+
+        assert!(child.iter().skip(100 - ones_count).all(|bit| bit));
+    }
+
+    #[test]
+    fn WhenBitFlipMutationWithProbabilityOne_ThenEveryBitIsFlipped() {
+        let mut gene = BitStringGene::<OneMax>::zeros();
+
+        gene.bit_flip_mutation(1.0);
+
+        assert_eq!(gene.count_ones(), 100);
+    }
+
+    #[test]
+    fn WhenRunOneMax_ThenReachesNearTheOptimum() {
+        let (gene, _) = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .iterations(200)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .fitness_goal(100.0)
+            .init()
+            .unwrap()
+            .run();
+
+        assert!(gene.get_fitness() > 90.0);
+    }
+
+    #[test]
+    fn WhenProblemInstance_ThenChildrenAndRestartsAreEvaluatedWithIt() {
+        let target = Arc::new(Target((0..40).map(|i| i % 3 == 0).collect()));
+        let problem = target.clone();
+        let mut genetic_algorithm = GeneticAlgorithm::<BitStringGene<Target>>::new()
+            .population_size(50)
+            .iterations(300)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .restart(Restart {
+                trigger: RestartTrigger::Stagnation { iterations: 20 },
+                reinitialisation: Reinitialisation::Partial { fraction: 0.5 },
+                population_growth: 1.0,
+                max_restarts: None,
+            })
+            .gene_init(Box::new(move || {
+                BitStringGene::init().with_problem(problem.clone())
+            }))
+            .fitness_goal(40.0)
+            .init()
+            .unwrap();
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+            assert!(genetic_algorithm
+                .get_generation()
+                .iter()
+                .all(|gene| gene.get_problem().is_some()));
+        }
+
+        let gene = genetic_algorithm.get_best_gene().unwrap();
+        assert_eq!(
+            genetic_algorithm.get_stop_criteria(),
+            StopCriteria::FitnessAchieved
+        );
+        assert!(gene.iter().eq(target.0.iter().copied()));
+    }
+
+    #[test]
+    fn WhenFitnessClosure_ThenGenesAreEvaluatedWithIt() {
+        let target: Vec<bool> = (0..32).map(|i| i % 2 == 0).collect();
+        let expected = target.clone();
+        let (gene, stop_criteria) = GeneticAlgorithm::<BitStringGene<BitStringFitness<32>>>::new()
+            .population_size(50)
+            .iterations(300)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .gene_init(BitStringGene::problem(move |gene| {
+                gene.iter()
+                    .zip(target.iter())
+                    .filter(|(a, b)| a == *b)
+                    .count() as f64
+            }))
+            .fitness_goal(32.0)
+            .init()
+            .unwrap()
+            .run();
+
+        assert_eq!(stop_criteria, StopCriteria::FitnessAchieved);
+        assert_eq!(gene.len(), 32);
+        assert!(gene.iter().eq(expected.iter().copied()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenNoFitnessAndNoProblemInstance_ThenPanics() {
+        BitStringGene::<Target>::init().calculate_fitness();
+    }
+}
+
+#[cfg(test)]
+mod real_vector {
+    use easy_ga::genotypes::{
+        BoundHandling, DifferentialCrossover, RealVectorCrossover, RealVectorGene,
+        RealVectorMutation, RealVectorProblem,
+    };
+    use easy_ga::operators::DiagonalCrossover;
+    use easy_ga::CrossoverOperator;
+    use easy_ga::Gene;
+    use easy_ga::GeneticAlgorithm;
+    use easy_ga::SelectionAlgorithms;
+    use std::sync::Arc;
+
+    use super::common::sphere;
+
+    /// Sphere with different bounds in every dimension.
+    struct MixedBoundsSphere;
+
+    impl RealVectorProblem for MixedBoundsSphere {
+        const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0), (0.0, 1.0), (10.0, 20.0)];
+
+        fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+            -sphere(gene.values())
+        }
+    }
+
+    struct BlendSphere;
+
+    impl RealVectorProblem for BlendSphere {
+        const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0); 5];
+        const CROSSOVER: RealVectorCrossover = RealVectorCrossover::Blend { alpha: 0.5 };
+        const MUTATION: RealVectorMutation = RealVectorMutation::Gaussian { sigma: 0.05 };
+        const BOUND_HANDLING: BoundHandling = BoundHandling::Reflect;
+
+        fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+            -sphere(gene.values())
+        }
+    }
+
+    /// Sphere centred on a point only known at runtime.
+    struct ShiftedSphere(Vec<f64>);
+
+    impl RealVectorProblem for ShiftedSphere {
+        const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0); 3];
+
+        fn evaluate(&self, gene: &RealVectorGene<Self>) -> f64 {
+            -gene
+                .values()
+                .iter()
+                .zip(self.0.iter())
+                .map(|(x, c)| (x - c).powi(2))
+                .sum::<f64>()
+        }
+    }
+
+    fn assert_in_bounds<P: RealVectorProblem>(gene: &RealVectorGene<P>) {
+        for (value, (lower, upper)) in gene.values().iter().zip(P::BOUNDS.iter()) {
+            assert!(
+                value >= lower && value <= upper,
+                "{value} out of [{lower}, {upper}]"
+            );
+        }
+    }
+
+    #[test]
+    fn WhenInit_ThenValuesAreInsideTheBounds() {
+        for _ in 0..100 {
+            let gene = RealVectorGene::<MixedBoundsSphere>::init();
+
+            assert_eq!(gene.dimensions(), 3);
+            assert_in_bounds(&gene);
+        }
+    }
+
+    #[test]
+    fn WhenFromValuesOutOfBounds_ThenValuesAreClamped() {
+        let gene = RealVectorGene::<MixedBoundsSphere>::from_values(vec![-7.0, 0.5, 25.0]);
+
+        assert_eq!(gene.values(), &[-5.0, 0.5, 20.0]);
+    }
+
+    #[test]
+    fn WhenBoundHandling_ThenValueIsInsideTheBounds() {
+        assert_eq!(BoundHandling::Clamp.apply(12.0, 0.0, 10.0), 10.0);
+        assert_eq!(BoundHandling::Reflect.apply(12.0, 0.0, 10.0), 8.0);
+        assert_eq!(BoundHandling::Reflect.apply(-3.0, 0.0, 10.0), 3.0);
+        assert_eq!(BoundHandling::Reflect.apply(25.0, 0.0, 10.0), 5.0);
+        let resampled = BoundHandling::Resample.apply(12.0, 0.0, 10.0);
+        assert!((0.0..=10.0).contains(&resampled));
+        assert_eq!(BoundHandling::Resample.apply(4.0, 0.0, 10.0), 4.0);
+    }
+
+    #[test]
+    fn WhenCrossover_ThenChildIsInsideTheBounds() {
+        let a = RealVectorGene::<MixedBoundsSphere>::from_values(vec![-5.0, 0.0, 10.0]);
+        let b = RealVectorGene::<MixedBoundsSphere>::from_values(vec![5.0, 1.0, 20.0]);
+
+        for _ in 0..100 {
+            assert_in_bounds(&a.blend_crossover(&b, 0.5));
+            assert_in_bounds(&a.arithmetic_crossover(&b));
+            assert_in_bounds(&a.simulated_binary_crossover(&b, 15.0));
+        }
+    }
+
+    #[test]
+    fn WhenArithmeticCrossover_ThenChildIsBetweenItsParents() {
+        let a = RealVectorGene::<MixedBoundsSphere>::from_values(vec![-4.0, 0.2, 12.0]);
+        let b = RealVectorGene::<MixedBoundsSphere>::from_values(vec![2.0, 0.8, 18.0]);
+
+        let child = a.arithmetic_crossover(&b);
+
+        for ((value, x), y) in child.values().iter().zip(a.values()).zip(b.values()) {
+            assert!(value >= &x.min(*y) && value <= &x.max(*y));
+        }
+    }
+
+    #[test]
+    fn WhenMutation_ThenGeneIsInsideTheBounds() {
+        for _ in 0..100 {
+            let mut gene = RealVectorGene::<MixedBoundsSphere>::from_values(vec![5.0, 0.0, 20.0]);
+            gene.gaussian_mutation(1.0, 1.0);
+            assert_in_bounds(&gene);
+
+            let mut gene = RealVectorGene::<MixedBoundsSphere>::from_values(vec![-5.0, 1.0, 10.0]);
+            gene.polynomial_mutation(1.0, 20.0);
+            assert_in_bounds(&gene);
+        }
+    }
+
+    #[test]
+    fn WhenDistance_ThenIsEuclidean() {
+        let a = RealVectorGene::<MixedBoundsSphere>::from_values(vec![0.0, 0.0, 10.0]);
+        let b = RealVectorGene::<MixedBoundsSphere>::from_values(vec![3.0, 0.0, 14.0]);
+
+        assert_eq!(a.distance(&b), Some(5.0));
+    }
+
+    #[test]
+    fn WhenRunSphere_ThenReachesNearTheOptimum() {
+        let (gene, _) = GeneticAlgorithm::<RealVectorGene<BlendSphere>>::new()
+            .iterations(200)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .init()
+            .unwrap()
+            .run();
+
+        let mut evaluated = gene.clone();
+        assert_eq!(evaluated.calculate_fitness(), gene.get_fitness());
+        assert!(gene.get_fitness() < 0.0);
+        assert!(gene.get_fitness() > -1.0, "{:?}", gene.values());
+    }
+
+    #[test]
+    fn WhenSelfAdaptiveMutation_ThenStepSizesChangeAndValuesStayInBounds() {
+        let mut gene = RealVectorGene::<MixedBoundsSphere>::from_values(vec![0.0, 0.5, 15.0]);
+        assert_eq!(gene.step_sizes(), &[0.1, 0.1, 0.1]);
+
+        for _ in 0..100 {
+            gene.self_adaptive_mutation();
+
+            assert_in_bounds(&gene);
+            assert!(gene.step_sizes().iter().all(|step_size| *step_size >= 1e-8));
+        }
+        assert_ne!(gene.step_sizes(), &[0.1, 0.1, 0.1]);
+    }
+
+    #[test]
+    fn WhenCrossover_ThenChildInheritsTheMeanStepSizes() {
+        let mut a = RealVectorGene::<MixedBoundsSphere>::from_values(vec![0.0, 0.5, 15.0]);
+        let b = RealVectorGene::<MixedBoundsSphere>::from_values(vec![1.0, 0.5, 12.0]);
+        a.self_adaptive_mutation();
+
+        let child = a.arithmetic_crossover(&b);
+
+        for ((child, a), b) in child
+            .step_sizes()
+            .iter()
+            .zip(a.step_sizes())
+            .zip(b.step_sizes())
+        {
+            assert!((child - (a + b) / 2.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn WhenProblemInstance_ThenChildrenKeepItAndAreEvaluatedWithIt() {
+        let problem = Arc::new(ShiftedSphere(vec![1.0, -2.0, 3.0]));
+        let a = RealVectorGene::from_values(vec![1.0, -2.0, 3.0]).with_problem(problem.clone());
+        let b = RealVectorGene::from_values(vec![0.0, 0.0, 0.0]).with_problem(problem.clone());
+
+        let children = vec![
+            a.blend_crossover(&b, 0.5),
+            a.arithmetic_crossover(&b),
+            a.simulated_binary_crossover(&b, 15.0),
+            DiagonalCrossover { parents: 2 }.crossover(&a, &b),
+            DifferentialCrossover { weight: 0.5 }.crossover(&a, &b),
+        ];
+        for mut child in children {
+            assert!(Arc::ptr_eq(child.get_problem().unwrap(), &problem));
+            let expected = problem.evaluate(&child);
+            assert_eq!(child.calculate_fitness(), expected);
+        }
+        assert_eq!(a.clone().calculate_fitness(), 0.0);
+    }
+}
+
+#[cfg(test)]
+mod permutation {
+    use easy_ga::genotypes::{
+        PermutationCrossover, PermutationGene, PermutationMutation, PermutationProblem,
+    };
+    use easy_ga::CrossoverOperator;
+    use easy_ga::Gene;
+    use easy_ga::GeneticAlgorithm;
+    use easy_ga::SelectionAlgorithms;
+    use std::sync::Arc;
+
+    struct Sorting;
+
+    impl PermutationProblem for Sorting {
+        const LENGTH: usize = 12;
+
+        fn fitness(gene: &PermutationGene<Self>) -> f64 {
+            gene.order()
+                .iter()
+                .enumerate()
+                .filter(|(i, x)| i == *x)
+                .count() as f64
+        }
+    }
+
+    /// Travelling salesman with the distances only known at runtime.
+    struct Tsp {
+        distances: Vec<Vec<f64>>,
+    }
+
+    impl PermutationProblem for Tsp {
+        const LENGTH: usize = 8;
+
+        fn evaluate(&self, gene: &PermutationGene<Self>) -> f64 {
+            let order = gene.order();
+            -(0..order.len())
+                .map(|i| self.distances[order[i]][order[(i + 1) % order.len()]])
+                .sum::<f64>()
+        }
+    }
+
+    struct Ring;
+
+    impl PermutationProblem for Ring {
+        const LENGTH: usize = 10;
+        const CROSSOVER: PermutationCrossover = PermutationCrossover::EdgeRecombination;
+        const MUTATION: PermutationMutation = PermutationMutation::Inversion;
+
+        // Best when every element is next to its consecutive ones.
+        fn fitness(gene: &PermutationGene<Self>) -> f64 {
+            let order = gene.order();
+            (0..order.len())
+                .filter(|&i| {
+                    let next = order[(i + 1) % order.len()];
+                    (order[i] + 1) % 10 == next || (next + 1) % 10 == order[i]
+                })
+                .count() as f64
+        }
+    }
+
+    #[test]
+    fn WhenInit_ThenGeneIsAValidPermutation() {
+        for _ in 0..100 {
+            let gene = PermutationGene::<Sorting>::init();
+
+            assert_eq!(gene.len(), 12);
+            assert!(gene.is_valid());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenFromOrderWithRepeatedElements_ThenPanics() {
+        PermutationGene::<Sorting>::from_order(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 10]);
+    }
+
+    #[test]
+    fn WhenCrossover_ThenChildIsAValidPermutation() {
+        for _ in 0..200 {
+            let a = PermutationGene::<Sorting>::init();
+            let b = PermutationGene::<Sorting>::init();
+
+            for child in [
+                a.order_crossover(&b),
+                a.partially_mapped_crossover(&b),
+                a.cycle_crossover(&b),
+                a.edge_recombination_crossover(&b),
+            ] {
+                assert!(child.is_valid(), "{child:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn WhenCycleCrossover_ThenEveryElementKeepsAParentPosition() {
+        for _ in 0..100 {
+            let a = PermutationGene::<Sorting>::init();
+            let b = PermutationGene::<Sorting>::init();
+
+            let child = a.cycle_crossover(&b);
+
+            for (i, element) in child.order().iter().enumerate() {
+                assert!(*element == a.order()[i] || *element == b.order()[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn WhenCrossoverOfEqualParents_ThenChildIsTheParent() {
+        let a = PermutationGene::<Sorting>::init();
+
+        assert_eq!(a.order_crossover(&a), a);
+        assert_eq!(a.partially_mapped_crossover(&a), a);
+        assert_eq!(a.cycle_crossover(&a), a);
+    }
+
+    #[test]
+    fn WhenMutation_ThenGeneIsAValidPermutation() {
+        let mut gene = PermutationGene::<Sorting>::init();
+
+        for _ in 0..200 {
+            gene.swap_mutation();
+            assert!(gene.is_valid());
+            gene.insertion_mutation();
+            assert!(gene.is_valid());
+            gene.scramble_mutation();
+            assert!(gene.is_valid());
+            gene.inversion_mutation();
+            assert!(gene.is_valid());
+        }
+    }
+
+    #[test]
+    fn WhenDistance_ThenCountsDifferentPositions() {
+        let identity = PermutationGene::<Sorting>::identity();
+        let mut swapped: Vec<usize> = (0..12).collect();
+        swapped.swap(0, 5);
+
+        let swapped = PermutationGene::<Sorting>::from_order(swapped);
+
+        assert_eq!(identity.distance(&swapped), Some(2.0));
+    }
+
+    #[test]
+    fn WhenRunRing_ThenReachesNearTheOptimum() {
+        let (gene, _) = GeneticAlgorithm::<PermutationGene<Ring>>::new()
+            .iterations(200)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .fitness_goal(10.0)
+            .init()
+            .unwrap()
+            .run();
+
+        assert!(gene.get_fitness() >= 8.0);
+    }
+
+    #[test]
+    fn WhenRunTspWithProblemInstance_ThenFindsTheShortestTour() {
+        // Cities on a circle: the shortest tour visits them in order, with length 8.
+        let distances = (0..8)
+            .map(|i: i64| {
+                (0..8)
+                    .map(|j: i64| {
+                        let steps = (i - j).rem_euclid(8);
+                        steps.min(8 - steps) as f64
+                    })
+                    .collect()
+            })
+            .collect();
+        let problem = Arc::new(Tsp { distances });
+        let tsp = problem.clone();
+        let (mut gene, _) = GeneticAlgorithm::<PermutationGene<Tsp>>::new()
+            .iterations(300)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .gene_init(Box::new(move || {
+                PermutationGene::init().with_problem(tsp.clone())
+            }))
+            .fitness_goal(-8.0)
+            .init()
+            .unwrap()
+            .run();
+
+        assert!(Arc::ptr_eq(gene.get_problem().unwrap(), &problem));
+        assert_eq!(gene.calculate_fitness(), -8.0, "{:?}", gene.order());
+        for crossover in [
+            PermutationCrossover::Order,
+            PermutationCrossover::PartiallyMapped,
+            PermutationCrossover::Cycle,
+            PermutationCrossover::EdgeRecombination,
+        ] {
+            let child = crossover.crossover(&gene, &PermutationGene::identity());
+            assert!(child.get_problem().is_some());
+        }
+    }
+}
+
+#[cfg(test)]
+mod integer_vector {
+    use easy_ga::genotypes::{
+        Domain, IntegerVectorCrossover, IntegerVectorGene, IntegerVectorMutation,
+        IntegerVectorProblem,
+    };
+    use easy_ga::operators::{CrossoverOperator, MutationOperator};
+    use easy_ga::Gene;
+    use easy_ga::GeneticAlgorithm;
+    use easy_ga::SelectionAlgorithms;
+    use std::sync::Arc;
+
+    const ACTIVATIONS: &[&str] = &["relu", "tanh", "sigmoid"];
+
+    struct Hyperparameters;
+
+    impl IntegerVectorProblem for Hyperparameters {
+        const DOMAINS: &'static [Domain] = &[
+            Domain::Integer { min: 1, max: 8 },
+            Domain::Integer { min: -50, max: 50 },
+            Domain::Categorical(ACTIVATIONS),
+        ];
+        const CREEP_STEP: i64 = 5;
+
+        fn fitness(gene: &IntegerVectorGene<Self>) -> f64 {
+            let bonus = if gene.category(2) == Some("tanh") {
+                10.0
+            } else {
+                0.0
+            };
+            bonus - (gene.get(0) - 6).abs() as f64 - (gene.get(1) - 20).abs() as f64
+        }
+    }
+
+    const BATCH_SIZES: &[i64] = &[16, 32, 64, 128];
+
+    struct Batches;
+
+    impl IntegerVectorProblem for Batches {
+        const DOMAINS: &'static [Domain] = &[
+            Domain::Values(BATCH_SIZES),
+            Domain::Integer { min: 0, max: 10 },
+        ];
+
+        fn fitness(gene: &IntegerVectorGene<Self>) -> f64 {
+            -(gene.get(0) - 64).abs() as f64 - gene.get(1) as f64
+        }
+    }
+
+    /// Matches integers only known at runtime.
+    struct Targets(Vec<i64>);
+
+    impl IntegerVectorProblem for Targets {
+        const DOMAINS: &'static [Domain] = &[Domain::Integer { min: -20, max: 20 }; 4];
+        const CREEP_STEP: i64 = 3;
+
+        fn evaluate(&self, gene: &IntegerVectorGene<Self>) -> f64 {
+            -gene
+                .values()
+                .iter()
+                .zip(self.0.iter())
+                .map(|(value, target)| (value - target).abs() as f64)
+                .sum::<f64>()
+        }
+    }
+
+    fn assert_in_domains(gene: &IntegerVectorGene<Hyperparameters>) {
+        for (value, domain) in gene.values().iter().zip(Hyperparameters::DOMAINS.iter()) {
+            assert!(domain.contains(*value), "{value} out of {domain:?}");
+        }
+    }
+
+    #[test]
+    fn WhenInit_ThenValuesAreInsideTheirDomains() {
+        for _ in 0..100 {
+            let gene = IntegerVectorGene::<Hyperparameters>::init();
+
+            assert_eq!(gene.len(), 3);
+            assert_in_domains(&gene);
+            assert!(gene.category(0).is_none());
+            assert!(ACTIVATIONS.contains(&gene.category(2).unwrap()));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenFromValuesOutsideTheDomain_ThenPanics() {
+        IntegerVectorGene::<Hyperparameters>::from_values(vec![1, 0, 3]);
+    }
+
+    #[test]
+    fn WhenUniformCrossover_ThenEveryValueComesFromAParent() {
+        let a = IntegerVectorGene::<Hyperparameters>::from_values(vec![1, -50, 0]);
+        let b = IntegerVectorGene::<Hyperparameters>::from_values(vec![8, 50, 2]);
+
+        for _ in 0..100 {
+            let child = a.uniform_crossover(&b);
+            for (i, value) in child.values().iter().enumerate() {
+                assert!(*value == a.get(i) || *value == b.get(i));
+            }
+        }
+    }
+
+    #[test]
+    fn WhenCreepMutation_ThenIntegersMoveAtMostTheStep() {
+        for _ in 0..100 {
+            let mut gene = IntegerVectorGene::<Hyperparameters>::from_values(vec![8, 0, 1]);
+
+            gene.creep_mutation(1.0, 5);
+
+            assert_in_domains(&gene);
+            assert!((3..=8).contains(&gene.get(0)));
+            assert!((1..=5).contains(&gene.get(1).abs()));
+            assert_eq!(gene.get(2), 1);
+        }
+    }
+
+    #[test]
+    fn WhenRandomResetMutation_ThenOnlyCategoriesChange() {
+        for _ in 0..100 {
+            let mut gene = IntegerVectorGene::<Hyperparameters>::from_values(vec![4, 0, 1]);
+
+            gene.random_reset_mutation(1.0);
+
+            assert_in_domains(&gene);
+            assert_eq!(&gene.values()[..2], &[4, 0]);
+        }
+    }
+
+    #[test]
+    fn WhenDistance_ThenMixesIntegersAndCategories() {
+        let a = IntegerVectorGene::<Hyperparameters>::from_values(vec![1, 10, 0]);
+        let b = IntegerVectorGene::<Hyperparameters>::from_values(vec![4, 5, 2]);
+
+        assert_eq!(a.distance(&b), Some(9.0));
+        assert_eq!(format!("{b}"), "[4, 5, sigmoid]");
+    }
+
+    struct Extremes;
+
+    impl IntegerVectorProblem for Extremes {
+        const DOMAINS: &'static [Domain] = &[Domain::Integer {
+            min: i64::MIN,
+            max: i64::MAX,
+        }];
+
+        fn fitness(_gene: &IntegerVectorGene<Self>) -> f64 {
+            0.0
+        }
+    }
+
+    #[test]
+    fn WhenDistanceBetweenExtremes_ThenDoesNotOverflow() {
+        let a = IntegerVectorGene::<Extremes>::from_values(vec![i64::MIN]);
+        let b = IntegerVectorGene::<Extremes>::from_values(vec![i64::MAX]);
+
+        assert_eq!(a.distance(&b), Some(u64::MAX as f64));
+    }
+
+    #[test]
+    fn WhenOperatorEnums_ThenBehaveAsTheGeneOperators() {
+        let a = IntegerVectorGene::<Hyperparameters>::from_values(vec![1, -50, 0]);
+        let b = IntegerVectorGene::<Hyperparameters>::from_values(vec![8, 50, 2]);
+
+        for _ in 0..100 {
+            let child = IntegerVectorCrossover::Uniform.crossover(&a, &b);
+            for (i, value) in child.values().iter().enumerate() {
+                assert!(*value == a.get(i) || *value == b.get(i));
+            }
+
+            let mut gene = IntegerVectorGene::<Hyperparameters>::from_values(vec![4, 0, 1]);
+            IntegerVectorMutation::RandomReset.mutate(&mut gene);
+            assert_eq!(&gene.values()[..2], &[4, 0]);
+
+            IntegerVectorMutation::Creep { step: 2 }.mutate(&mut gene);
+            assert_in_domains(&gene);
+            assert!((2..=6).contains(&gene.get(0)));
+            assert!(gene.get(1).abs() <= 2);
+        }
+    }
+
+    #[test]
+    fn WhenRunHyperparameters_ThenReachesTheOptimum() {
+        let (gene, _) = GeneticAlgorithm::<IntegerVectorGene<Hyperparameters>>::new()
+            .iterations(200)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .fitness_goal(10.0)
+            .init()
+            .unwrap()
+            .run();
+
+        assert!(gene.get_fitness() >= 8.0);
+    }
+
+    #[test]
+    fn WhenValuesDomain_ThenOnlyTheAllowedValuesAreUsed() {
+        let domain = Domain::Values(BATCH_SIZES);
+        assert!(domain.contains(32));
+        assert!(!domain.contains(33));
+
+        for _ in 0..100 {
+            let mut gene = IntegerVectorGene::<Batches>::init();
+            assert!(BATCH_SIZES.contains(&gene.get(0)));
+            assert!(gene.category(0).is_none());
+
+            let before = gene.get(0);
+            gene.creep_mutation(1.0, 5);
+            assert_eq!(gene.get(0), before);
+
+            gene.random_reset_mutation(1.0);
+            assert!(BATCH_SIZES.contains(&gene.get(0)));
+        }
+    }
+
+    #[test]
+    fn WhenRandomResetMutationOfValues_ThenEveryValueIsReached() {
+        let mut reached = std::collections::HashSet::new();
+        let mut gene = IntegerVectorGene::<Batches>::from_values(vec![16, 0]);
+        for _ in 0..200 {
+            gene.random_reset_mutation(1.0);
+            reached.insert(gene.get(0));
+        }
+
+        assert_eq!(reached.len(), BATCH_SIZES.len());
+        assert_eq!(gene.get(1), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenFromValuesNotAllowed_ThenPanics() {
+        IntegerVectorGene::<Batches>::from_values(vec![20, 0]);
+    }
+
+    #[test]
+    fn WhenDistanceOfValues_ThenIsTheAbsoluteDifference() {
+        let a = IntegerVectorGene::<Batches>::from_values(vec![16, 2]);
+        let b = IntegerVectorGene::<Batches>::from_values(vec![128, 0]);
+
+        assert_eq!(a.distance(&b), Some(114.0));
+        assert_eq!(format!("{b}"), "[128, 0]");
+    }
+
+    #[test]
+    fn WhenRunWithProblemInstance_ThenReachesTheRuntimeTargets() {
+        let problem = Arc::new(Targets(vec![7, -3, 15, 0]));
+        let (mut gene, _) = GeneticAlgorithm::<IntegerVectorGene<Targets>>::new()
+            .iterations(300)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .gene_init(Box::new(move || {
+                IntegerVectorGene::init().with_problem(problem.clone())
+            }))
+            .fitness_goal(0.0)
+            .init()
+            .unwrap()
+            .run();
+
+        assert_eq!(gene.calculate_fitness(), 0.0);
+        assert_eq!(gene.values(), &[7, -3, 15, 0]);
+    }
+}
+
+#[cfg(test)]
+mod tree {
+    use easy_ga::genotypes::tree::{self, Function, Terminal, TreeGene, TreeProblem, Type, Value};
+    use easy_ga::genotypes::{PrimitiveSetError, TreeMutation};
+    use easy_ga::Gene;
+    use easy_ga::GeneticAlgorithm;
+    use easy_ga::SelectionAlgorithms;
+
+    struct Quadratic;
+
+    impl TreeProblem for Quadratic {
+        const FUNCTIONS: &'static [Function] = tree::ARITHMETIC;
+        const TERMINALS: &'static [Terminal] = &[
+            Terminal::Variable {
+                name: "x",
+                index: 0,
+                output: Type::Number,
+            },
+            Terminal::Constant {
+                name: "1",
+                value: Value::Number(1.0),
+            },
+        ];
+        const MAX_DEPTH: usize = 8;
+        const PARSIMONY_COEFFICIENT: f64 = 0.0001;
+
+        fn fitness(gene: &TreeGene<Self>) -> f64 {
+            let samples: Vec<(Vec<f64>, f64)> = (-10..=10)
+                .map(|x| x as f64 / 5.0)
+                .map(|x| (vec![x], x * x + x + 1.0))
+                .collect();
+            -gene.mean_squared_error(&samples)
+        }
+    }
+
+    struct Typed;
+
+    impl TreeProblem for Typed {
+        const FUNCTIONS: &'static [Function] = &[tree::ADD, tree::LESS_THAN, tree::IF_THEN_ELSE];
+        const TERMINALS: &'static [Terminal] = &[
+            Terminal::Variable {
+                name: "x",
+                index: 0,
+                output: Type::Number,
+            },
+            Terminal::Constant {
+                name: "true",
+                value: Value::Boolean(true),
+            },
+            Terminal::RandomConstant {
+                min: -1.0,
+                max: 1.0,
+            },
+        ];
+        const MAX_DEPTH: usize = 6;
+        const MUTATION: TreeMutation = TreeMutation::Point;
+
+        fn fitness(gene: &TreeGene<Self>) -> f64 {
+            gene.evaluate(&[Value::Number(1.0)]).as_number()
+        }
+    }
+
+    struct MissingBoolean;
+
+    impl TreeProblem for MissingBoolean {
+        const FUNCTIONS: &'static [Function] = &[tree::ADD, tree::LESS_THAN, tree::IF_THEN_ELSE];
+        const TERMINALS: &'static [Terminal] = &[Terminal::Variable {
+            name: "x",
+            index: 0,
+            output: Type::Number,
+        }];
+
+        fn fitness(_gene: &TreeGene<Self>) -> f64 {
+            0.0
+        }
+    }
+
+    struct UnreachableBoolean;
+
+    impl TreeProblem for UnreachableBoolean {
+        const FUNCTIONS: &'static [Function] = &[tree::ADD, tree::LESS_THAN];
+        const TERMINALS: &'static [Terminal] = &[Terminal::Variable {
+            name: "x",
+            index: 0,
+            output: Type::Number,
+        }];
+
+        fn fitness(_gene: &TreeGene<Self>) -> f64 {
+            0.0
+        }
+    }
+
+    #[test]
+    fn WhenEveryReachableTypeHasATerminal_ThenPrimitiveSetIsValid() {
+        assert_eq!(tree::validate::<Quadratic>(), Ok(()));
+        assert_eq!(tree::validate::<Typed>(), Ok(()));
+        assert_eq!(tree::validate::<UnreachableBoolean>(), Ok(()));
+    }
+
+    #[test]
+    fn WhenReachableTypeHasNoTerminal_ThenPrimitiveSetIsInvalid() {
+        assert_eq!(
+            tree::validate::<MissingBoolean>(),
+            Err(PrimitiveSetError::MissingTerminal(Type::Boolean))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid primitive set")]
+    fn WhenReachableTypeHasNoTerminal_ThenRandomPanics() {
+        TreeGene::<MissingBoolean>::random(0, true);
+    }
+
+    #[test]
+    fn WhenRandomFull_ThenDepthIsTheMaximum() {
+        for depth in 0..5 {
+            let gene = TreeGene::<Quadratic>::random(depth, true);
+
+            assert_eq!(gene.depth(), depth);
+            assert_eq!(gene.size(), 2usize.pow(depth as u32 + 1) - 1);
+        }
+    }
+
+    #[test]
+    fn WhenInit_ThenDepthIsInsideTheInitialisationRange() {
+        for _ in 0..100 {
+            let gene = TreeGene::<Quadratic>::init();
+
+            assert!(gene.depth() <= Quadratic::MAX_INIT_DEPTH);
+        }
+    }
+
+    #[test]
+    fn WhenEvaluate_ThenTypedTreeReturnsTheRootType() {
+        for _ in 0..100 {
+            let gene = TreeGene::<Typed>::init();
+
+            let value = gene.evaluate(&[Value::Number(2.0)]);
+
+            assert_eq!(value.get_type(), Type::Number, "{gene}");
+        }
+    }
+
+    #[test]
+    fn WhenCrossoverAndMutation_ThenDepthLimitIsKept() {
+        let mut a = TreeGene::<Quadratic>::init();
+        let mut b = TreeGene::<Quadratic>::init();
+
+        for _ in 0..200 {
+            let child = a.subtree_crossover(&b);
+            assert!(child.depth() <= Quadratic::MAX_DEPTH);
+            b = a;
+            a = child;
+            a.mutate();
+            assert!(a.depth() <= Quadratic::MAX_DEPTH);
+        }
+    }
+
+    #[test]
+    fn WhenMutateTypedTree_ThenTreeStaysWellTyped() {
+        let mut gene = TreeGene::<Typed>::init();
+
+        for _ in 0..200 {
+            gene.point_mutation();
+            gene.subtree_mutation();
+            gene = gene.subtree_crossover(&TreeGene::<Typed>::init());
+            assert_eq!(
+                gene.evaluate(&[Value::Number(0.5)]).get_type(),
+                Type::Number
+            );
+        }
+    }
+
+    #[test]
+    fn WhenHoistMutation_ThenTreeDoesNotGrow() {
+        for _ in 0..100 {
+            let mut gene = TreeGene::<Quadratic>::init();
+            let size = gene.size();
+
+            gene.hoist_mutation();
+
+            assert!(gene.size() <= size);
+        }
+    }
+
+    #[test]
+    fn WhenErrorIsNotFinite_ThenMeanSquaredErrorIsTheMaxError() {
+        let gene = TreeGene::<Quadratic>::random(0, true);
+
+        assert_eq!(
+            gene.mean_squared_error(&[(vec![0.0], f64::MAX)]),
+            tree::MAX_ERROR
+        );
+        assert_eq!(
+            gene.mean_squared_error(&[(vec![0.0], f64::NAN)]),
+            tree::MAX_ERROR
+        );
+        assert_eq!(
+            gene.mean_squared_error(&[(vec![0.0], 1.0e9)]),
+            tree::MAX_ERROR
+        );
+    }
+
+    #[test]
+    fn WhenDisplay_ThenPrintsSExpression() {
+        let gene = TreeGene::<Quadratic>::random(0, true);
+
+        assert!(["x", "1"].contains(&gene.to_string().as_str()));
+    }
+
+    #[test]
+    fn WhenRunSymbolicRegression_ThenErrorDecreases() {
+        let mut genetic_algorithm = GeneticAlgorithm::<TreeGene<Quadratic>>::new()
+            .population_size(100)
+            .iterations(100)
+            .mutation_rate(0.3)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .fitness_goal(-0.01)
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+        // The best gene of the initial generation can already be a solution.
+        let initial_fitness = genetic_algorithm.get_statistics()[0].mean_fitness;
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+        }
+
+        let mut gene = genetic_algorithm.get_best_gene().unwrap();
+        let fitness = gene.calculate_fitness();
+        assert!(fitness > initial_fitness, "{fitness} {initial_fitness}");
+        assert!(fitness > -1.0, "{gene}");
+    }
+}
+
+#[cfg(test)]
+mod grammatical {
+    use easy_ga::genotypes::grammatical::invalid_genes;
+    use easy_ga::genotypes::{
+        Grammar, GrammarError, GrammaticalGene, GrammaticalProblem, InvalidReason, Mapping,
+    };
+    use easy_ga::statistics::GenerationStatistics;
+    use easy_ga::Gene;
+    use easy_ga::GeneticAlgorithm;
+    use easy_ga::SelectionAlgorithms;
+    use std::sync::OnceLock;
+
+    const EXPRESSION: &str = "
+        # Arithmetic expressions.
+        <expr> ::= <expr> <op> <expr>
+                 | \"(\" <expr> \")\"
+                 | <var>
+        <op>   ::= + | - | *
+        <var>  ::= x | 1
+    ";
+
+    struct Expression;
+
+    impl GrammaticalProblem for Expression {
+        fn grammar() -> &'static Grammar {
+            static GRAMMAR: OnceLock<Grammar> = OnceLock::new();
+            GRAMMAR.get_or_init(|| Grammar::parse(EXPRESSION).unwrap())
+        }
+
+        // Best when the expression evaluated with x = 3 is 10, e.g. x*x+1.
+        fn fitness(phenotype: &str) -> f64 {
+            -(evaluate(phenotype, 3.0) - 10.0).abs() - phenotype.len() as f64 * 0.001
+        }
+    }
+
+    /// Evaluates the sums and products of the expression from left to right.
+    fn evaluate(phenotype: &str, x: f64) -> f64 {
+        let mut tokens = phenotype.chars().filter(|c| *c != '(' && *c != ')');
+        let value = |c: char| if c == 'x' { x } else { 1.0 };
+        let mut result = value(tokens.next().unwrap());
+        while let (Some(op), Some(operand)) = (tokens.next(), tokens.next()) {
+            match op {
+                '+' => result += value(operand),
+                '-' => result -= value(operand),
+                _ => result *= value(operand),
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn WhenMap_ThenLeftmostNonTerminalIsExpandedWithTheCodon() {
+        let grammar = Grammar::parse(EXPRESSION).unwrap();
+
+        // <expr> -> <expr><op><expr> -> <var><op><expr> -> x<op><expr> -> x*<expr> -> x*<var> -> x*1
+        let mapping = grammar.map(&[0, 2, 0, 2, 2, 1], 0, 10);
+
+        assert_eq!(
+            mapping,
+            Mapping::Valid {
+                phenotype: "x*1".to_string(),
+                used_codons: 6,
+                depth: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn WhenCodonsAreExhausted_ThenWrapsOrIsInvalid() {
+        let grammar = Grammar::parse(EXPRESSION).unwrap();
+
+        assert_eq!(
+            grammar.map(&[0, 2, 0], 0, 10),
+            Mapping::Invalid(InvalidReason::CodonsExhausted)
+        );
+        // [0] wrapped expands <expr> forever.
+        assert_eq!(
+            grammar.map(&[0], 100, 10),
+            Mapping::Invalid(InvalidReason::MaxDepthExceeded)
+        );
+        assert!(matches!(
+            grammar.map(&[2, 1], 1, 10),
+            Mapping::Valid { used_codons: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn WhenParseInvalidGrammar_ThenReturnsTheLine() {
+        assert!(matches!(
+            Grammar::parse("<a> ::= <b>"),
+            Err(GrammarError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            Grammar::parse("<a> ::= x\n<a> ::= y"),
+            Err(GrammarError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            Grammar::parse("<a> ::= \"x"),
+            Err(GrammarError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            Grammar::parse("# Nothing"),
+            Err(GrammarError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn WhenLoadGrammarFromFile_ThenEqualsTheParsedGrammar() {
+        let file = std::env::temp_dir().join(format!("easy_ga-{}-grammar.bnf", std::process::id()));
+        std::fs::write(&file, EXPRESSION).unwrap();
+
+        let grammar = Grammar::from_file(&file);
+
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(grammar.unwrap(), Grammar::parse(EXPRESSION).unwrap());
+        assert!(matches!(Grammar::from_file(file), Err(GrammarError::Io(_))));
+    }
+
+    #[test]
+    fn WhenGeneIsInvalid_ThenGetsTheInvalidFitness() {
+        let mut gene = GrammaticalGene::<Expression>::from_codons(vec![0]);
+
+        assert!(!gene.is_valid());
+        assert_eq!(gene.phenotype(), None);
+        assert_eq!(gene.invalid_reason(), Some(InvalidReason::CodonsExhausted));
+        assert_eq!(gene.calculate_fitness(), Expression::INVALID_FITNESS);
+        assert_eq!(
+            invalid_genes(&[gene, GrammaticalGene::from_codons(vec![2, 1])]),
+            1
+        );
+    }
+
+    #[test]
+    fn WhenCrossover_ThenChildKeepsTheCodonLimit() {
+        let a = GrammaticalGene::<Expression>::init();
+        let b = GrammaticalGene::<Expression>::init();
+
+        for _ in 0..100 {
+            let child = a.one_point_crossover(&b);
+            assert!(child.codons().len() <= Expression::MAX_CODONS);
+            assert!(child
+                .codons()
+                .iter()
+                .all(|codon| *codon <= Expression::CODON_MAX));
+        }
+    }
+
+    #[test]
+    fn WhenRunExpression_ThenFindsTheTarget() {
+        let (gene, _) = GeneticAlgorithm::<GrammaticalGene<Expression>>::new()
+            .population_size(50)
+            .iterations(100)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .init()
+            .unwrap()
+            .run();
+
+        let mut evaluated = gene.clone();
+        assert_eq!(evaluated.calculate_fitness(), gene.get_fitness());
+        let phenotype = gene.phenotype().unwrap();
+        assert_eq!(evaluate(phenotype, 3.0), 10.0, "{phenotype}");
+    }
+
+    #[test]
+    fn WhenGenerationHasInvalidGenes_ThenMeanFitnessIsFinite() {
+        let mut genetic_algorithm = GeneticAlgorithm::<GrammaticalGene<Expression>>::new()
+            .population_size(100)
+            .iterations(1)
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+
+        let statistics = &genetic_algorithm.get_statistics()[0];
+        assert_eq!(statistics.worst_fitness, Expression::INVALID_FITNESS);
+        assert!(statistics.mean_fitness.is_finite());
+        assert!(statistics.invalid_genes > 0);
+    }
+
+    #[test]
+    fn WhenStatisticsAreCalculated_ThenInvalidGenesAreCounted() {
+        let generation: Vec<GrammaticalGene<Expression>> =
+            (0..100).map(|_| GrammaticalGene::init()).collect();
+
+        let statistics = GenerationStatistics::calculate(0, &generation, false);
+
+        assert_eq!(statistics.invalid_genes, invalid_genes(&generation));
+        assert!(statistics
+            .to_csv()
+            .ends_with(&format!(",{}", statistics.invalid_genes)));
+    }
+}
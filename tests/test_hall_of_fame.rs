@@ -0,0 +1,156 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod hall_of_fame {
+    use easy_ga::genetic_algorithm::StopCriteria;
+    use easy_ga::genotypes::{BitStringGene, BitStringProblem};
+    use easy_ga::{Gene, GeneticAlgorithm, HallOfFame, SelectionAlgorithms};
+    use rand::Rng;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    struct LineGene {
+        x: f64,
+        fitness: f64,
+    }
+
+    impl Gene for LineGene {
+        fn init() -> Self {
+            LineGene {
+                x: rand::thread_rng().gen_range(0.0..10.0),
+                fitness: 0.0,
+            }
+        }
+
+        fn calculate_fitness(&mut self) -> f64 {
+            self.fitness = self.x;
+            self.fitness
+        }
+
+        fn crossover(&self, other: &Self) -> Self {
+            LineGene {
+                x: (self.x + other.x) / 2.0,
+                fitness: 0.0,
+            }
+        }
+
+        fn mutate(&mut self) {}
+
+        fn get_fitness(&self) -> f64 {
+            self.fitness
+        }
+
+        fn distance(&self, other: &Self) -> Option<f64> {
+            Some((self.x - other.x).abs())
+        }
+    }
+
+    fn gene(x: f64) -> LineGene {
+        LineGene { x, fitness: x }
+    }
+
+    struct OneMax;
+
+    impl BitStringProblem for OneMax {
+        const LENGTH: usize = 32;
+
+        fn fitness(gene: &BitStringGene<Self>) -> f64 {
+            gene.count_ones() as f64
+        }
+    }
+
+    #[test]
+    fn WhenInserted_ThenTheBestGenesAreKeptSorted() {
+        let mut hall_of_fame = HallOfFame::new(3);
+
+        assert_eq!(
+            hall_of_fame.update(&[gene(2.0), gene(5.0), gene(1.0), gene(4.0)]),
+            4
+        );
+        assert!(!hall_of_fame.insert(&gene(0.5)));
+
+        let xs: Vec<f64> = hall_of_fame.get_genes().iter().map(|g| g.x).collect();
+        assert_eq!(xs, vec![5.0, 4.0, 2.0]);
+        assert_eq!(hall_of_fame.best().unwrap().x, 5.0);
+    }
+
+    #[test]
+    fn WhenDuplicate_ThenOnlyTheBestIsKept() {
+        let mut hall_of_fame = HallOfFame::new(5);
+
+        assert!(hall_of_fame.insert(&gene(3.0)));
+        assert!(!hall_of_fame.insert(&gene(3.0)));
+        assert_eq!(hall_of_fame.len(), 1);
+    }
+
+    #[test]
+    fn WhenMinDistance_ThenCloseGenesAreDuplicates() {
+        let mut hall_of_fame = HallOfFame::new(5).min_distance(1.0);
+
+        assert!(hall_of_fame.insert(&gene(3.0)));
+        assert!(hall_of_fame.insert(&gene(3.5)));
+        assert!(!hall_of_fame.insert(&gene(2.8)));
+        assert!(hall_of_fame.insert(&gene(6.0)));
+
+        let xs: Vec<f64> = hall_of_fame.get_genes().iter().map(|g| g.x).collect();
+        assert_eq!(xs, vec![6.0, 3.5]);
+    }
+
+    #[test]
+    fn WhenEquality_ThenOnlyEqualGenesAreDuplicates() {
+        let mut hall_of_fame = HallOfFame::new(5).min_distance(1.0).equality();
+
+        assert!(hall_of_fame.insert(&gene(3.0)));
+        assert!(hall_of_fame.insert(&gene(3.5)));
+        assert!(!hall_of_fame.insert(&gene(3.5)));
+        assert_eq!(hall_of_fame.len(), 2);
+    }
+
+    #[test]
+    fn WhenRunWithHallOfFame_ThenDistinctBestGenesAreReturned() {
+        let (genes, stop_criteria) = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .population_size(50)
+            .iterations(50)
+            .mutation_rate(0.5)
+            .selection_rate(0.5)
+            .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+            .hall_of_fame(HallOfFame::new(10))
+            .init()
+            .unwrap()
+            .run_hall_of_fame();
+
+        assert_eq!(stop_criteria, StopCriteria::MaxIterations);
+        assert_eq!(genes.len(), 10);
+        for (i, gene) in genes.iter().enumerate() {
+            assert!(genes[i + 1..]
+                .iter()
+                .all(|other| gene.distance(other).unwrap() > 0.0));
+        }
+        assert!(genes
+            .windows(2)
+            .all(|pair| pair[0].get_fitness() >= pair[1].get_fitness()));
+    }
+
+    #[test]
+    fn WhenIterating_ThenTheHallOfFameHasEvaluatedGenes() {
+        let mut genetic_algorithm = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .population_size(30)
+            .iterations(10)
+            .hall_of_fame(HallOfFame::new(5).min_distance(2.0))
+            .init()
+            .unwrap();
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+            let hall_of_fame = genetic_algorithm.get_hall_of_fame().unwrap();
+            assert!(hall_of_fame.len() <= 5);
+            for gene in hall_of_fame.get_genes() {
+                assert_eq!(gene.get_fitness(), gene.count_ones() as f64);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenSizeIsZero_ThenPanics() {
+        HallOfFame::<LineGene>::new(0);
+    }
+}
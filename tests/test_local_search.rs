@@ -0,0 +1,301 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod local_search {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use easy_ga::genotypes::{BitStringGene, BitStringProblem};
+    use easy_ga::{
+        ConstraintHandling, Gene, GeneticAlgorithm, HillClimbing, LocalSearch, LocalSearchScope,
+        LocalSearchWriteBack, Niching, SimulatedAnnealing,
+    };
+
+    struct OneMax;
+
+    impl BitStringProblem for OneMax {
+        const LENGTH: usize = 32;
+
+        fn fitness(gene: &BitStringGene<Self>) -> f64 {
+            gene.count_ones() as f64
+        }
+    }
+
+    thread_local! {
+        static EVALUATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// OneMax counting its evaluations and requiring the first bit set.
+    struct CountedOneMax;
+
+    impl BitStringProblem for CountedOneMax {
+        const LENGTH: usize = 32;
+
+        fn fitness(gene: &BitStringGene<Self>) -> f64 {
+            EVALUATIONS.with(|evaluations| evaluations.set(evaluations.get() + 1));
+            gene.count_ones() as f64
+        }
+
+        fn constraint_violation(gene: &BitStringGene<Self>) -> f64 {
+            if gene.get(0) {
+                0.0
+            } else {
+                1.0
+            }
+        }
+
+        fn repair(gene: &mut BitStringGene<Self>) {
+            gene.set(0, true);
+        }
+    }
+
+    fn ones() -> BitStringGene<OneMax> {
+        let mut gene = BitStringGene::<OneMax>::from_bools(&[true; 32]);
+        gene.calculate_fitness();
+        gene
+    }
+
+    /// Local search returning the optimum and counting its calls.
+    fn to_optimum(calls: &Rc<Cell<usize>>) -> Box<dyn LocalSearch<BitStringGene<OneMax>>> {
+        let calls = Rc::clone(calls);
+        Box::new(move |_: &BitStringGene<OneMax>| {
+            calls.set(calls.get() + 1);
+            ones()
+        })
+    }
+
+    #[test]
+    fn WhenHillClimbing_ThenOneMaxIsSolved() {
+        let mut gene = BitStringGene::<OneMax>::zeros();
+        gene.calculate_fitness();
+
+        let refined = HillClimbing {
+            steps: 1000,
+            neighbours: 10,
+        }
+        .search(&gene);
+
+        assert_eq!(refined.get_fitness(), 32.0);
+        assert_eq!(gene.get_fitness(), 0.0);
+    }
+
+    #[test]
+    fn WhenSimulatedAnnealing_ThenRefinedGeneIsNotWorse() {
+        for _ in 0..10 {
+            let mut gene = BitStringGene::<OneMax>::init();
+            gene.calculate_fitness();
+
+            let refined = SimulatedAnnealing {
+                steps: 200,
+                initial_temperature: 2.0,
+                cooling_rate: 0.95,
+            }
+            .search(&gene);
+
+            assert!(refined.get_fitness() >= gene.get_fitness());
+            assert_eq!(refined.get_fitness(), refined.clone().calculate_fitness());
+        }
+    }
+
+    #[test]
+    fn WhenLamarckian_ThenOffspringAreReplaced() {
+        let calls = Rc::new(Cell::new(0));
+        let mut genetic_algorithm = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .population_size(20)
+            .iterations(10)
+            .selection_rate(0.5)
+            .local_search(to_optimum(&calls))
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+
+        assert_eq!(calls.get(), 10);
+        assert_eq!(
+            genetic_algorithm
+                .get_generation()
+                .iter()
+                .filter(|gene| gene.count_ones() == 32)
+                .count(),
+            10
+        );
+        assert!(genetic_algorithm
+            .get_generation()
+            .iter()
+            .filter(|gene| gene.count_ones() == 32)
+            .all(|gene| gene.get_fitness() == 32.0));
+        assert!(genetic_algorithm
+            .get_learned_fitnesses()
+            .iter()
+            .all(Option::is_none));
+    }
+
+    #[test]
+    fn WhenBaldwinian_ThenOffspringKeepTheirGenomeAndLearnTheFitness() {
+        let calls = Rc::new(Cell::new(0));
+        let mut genetic_algorithm = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .population_size(20)
+            .iterations(10)
+            .selection_rate(0.5)
+            .local_search(to_optimum(&calls))
+            .local_search_write_back(LocalSearchWriteBack::Baldwinian)
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+
+        let learned_fitnesses = genetic_algorithm.get_learned_fitnesses();
+        assert_eq!(calls.get(), 10);
+        assert_eq!(learned_fitnesses.len(), 20);
+        assert!(learned_fitnesses[..10].iter().all(Option::is_none));
+        assert!(learned_fitnesses[10..].iter().all(|f| *f == Some(32.0)));
+        assert!(genetic_algorithm
+            .get_generation()
+            .iter()
+            .all(|gene| gene.count_ones() < 32));
+    }
+
+    #[test]
+    fn WhenBaldwinian_ThenLearnedFitnessesFollowTheirGenes() {
+        let mut genetic_algorithm = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .population_size(20)
+            .iterations(20)
+            .local_search(Box::new(HillClimbing {
+                steps: 5,
+                neighbours: 5,
+            }))
+            .local_search_write_back(LocalSearchWriteBack::Baldwinian)
+            .init()
+            .unwrap();
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+
+            let generation = genetic_algorithm.get_generation();
+            let learned_fitnesses = genetic_algorithm.get_learned_fitnesses();
+            assert!(learned_fitnesses.iter().any(Option::is_some));
+            for (gene, learned) in generation.into_iter().zip(learned_fitnesses) {
+                if let Some(learned) = learned {
+                    assert!(learned >= gene.clone().calculate_fitness());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn WhenMemetic_ThenOffspringAreEvaluatedOnce() {
+        let mut genetic_algorithm = GeneticAlgorithm::<BitStringGene<CountedOneMax>>::new()
+            .population_size(20)
+            .iterations(10)
+            .selection_rate(0.5)
+            .local_search(Box::new(|gene: &BitStringGene<CountedOneMax>| gene.clone()))
+            .init()
+            .unwrap();
+        EVALUATIONS.with(|evaluations| evaluations.set(0));
+
+        genetic_algorithm.next_iteration();
+        genetic_algorithm.next_iteration();
+
+        // 20 genes of the first generation, then 10 survivors, plus 10 offspring in every local search.
+        assert_eq!(EVALUATIONS.with(|evaluations| evaluations.get()), 50);
+    }
+
+    #[test]
+    fn WhenMemeticWithRepair_ThenLocalSearchRefinesRepairedOffspring() {
+        let infeasible = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&infeasible);
+        let mut genetic_algorithm = GeneticAlgorithm::<BitStringGene<CountedOneMax>>::new()
+            .population_size(20)
+            .iterations(10)
+            .selection_rate(0.5)
+            .mutation_rate(1.0)
+            .constraint_handling(ConstraintHandling::Repair)
+            .local_search(Box::new(move |gene: &BitStringGene<CountedOneMax>| {
+                if !gene.get(0) {
+                    counter.set(counter.get() + 1);
+                }
+                gene.clone()
+            }))
+            .init()
+            .unwrap();
+
+        for _ in 0..5 {
+            genetic_algorithm.next_iteration();
+        }
+
+        assert_eq!(infeasible.get(), 0);
+    }
+
+    #[test]
+    fn WhenScopeIsBest_ThenOnlyTheBestOffspringAreRefined() {
+        let calls = Rc::new(Cell::new(0));
+        let mut genetic_algorithm = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .population_size(20)
+            .iterations(10)
+            .selection_rate(0.5)
+            .local_search(to_optimum(&calls))
+            .local_search_scope(LocalSearchScope::Best(3))
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn WhenScopeIsFraction_ThenSomeOffspringAreRefined() {
+        for (fraction, expected) in [(0.0, 0), (1.0, 10)] {
+            let calls = Rc::new(Cell::new(0));
+            let mut genetic_algorithm = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+                .population_size(20)
+                .iterations(10)
+                .selection_rate(0.5)
+                .local_search(to_optimum(&calls))
+                .local_search_scope(LocalSearchScope::Fraction(fraction))
+                .init()
+                .unwrap();
+            genetic_algorithm.next_iteration();
+
+            assert_eq!(calls.get(), expected);
+        }
+    }
+
+    #[test]
+    fn WhenDeterministicCrowding_ThenTheWholeGenerationIsRefined() {
+        let calls = Rc::new(Cell::new(0));
+        let mut genetic_algorithm = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .population_size(20)
+            .iterations(10)
+            .niching(Niching::DeterministicCrowding { niche_radius: 1.0 })
+            .local_search(to_optimum(&calls))
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+
+        assert_eq!(calls.get(), 20);
+        assert!(genetic_algorithm
+            .get_generation()
+            .iter()
+            .all(|gene| gene.count_ones() == 32));
+    }
+
+    #[test]
+    fn WhenMemetic_ThenOneMaxIsSolvedInFewIterations() {
+        let (gene, _) = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .population_size(20)
+            .iterations(30)
+            .fitness_goal(32.0)
+            .local_search(Box::new(HillClimbing {
+                steps: 100,
+                neighbours: 5,
+            }))
+            .local_search_scope(LocalSearchScope::Best(2))
+            .run();
+
+        assert_eq!(gene.get_fitness(), 32.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenLocalSearchFractionOverflow_ThenPanics() {
+        GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .local_search_scope(LocalSearchScope::Fraction(1.5));
+    }
+}
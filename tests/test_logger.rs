@@ -0,0 +1,188 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod logger {
+    use easy_ga::samples::MyGene as MockMyGene;
+    use easy_ga::GeneticAlgorithm;
+    use easy_ga::Logger;
+    use easy_ga::VerbosityLevel;
+    use easy_ga::VerbosityType;
+    use std::thread;
+
+    #[test]
+    fn WhenNewLogger_ThenEveryVariableIsInitializedSuccesfully() {
+        let logger = Logger::new(VerbosityLevel::MID, VerbosityType::LOG);
+
+        assert_eq!(logger.get_level(), VerbosityLevel::MID);
+        assert_eq!(logger.get_verbosity_type(), VerbosityType::LOG);
+    }
+
+    #[test]
+    fn WhenNewLoggers_ThenEachOneHasItsOwnFile() {
+        let logger1 = Logger::new(VerbosityLevel::LOW, VerbosityType::SAVE);
+        let logger2 = Logger::new(VerbosityLevel::LOW, VerbosityType::SAVE);
+
+        assert_ne!(logger1.get_file_path(), logger2.get_file_path());
+    }
+
+    #[test]
+    fn WhenSetLogger_ThenSuccess() {
+        let genetic_algorithm = GeneticAlgorithm::<MockMyGene>::new()
+            .logger(Logger::new(VerbosityLevel::HIGH, VerbosityType::LOG));
+
+        assert_eq!(
+            genetic_algorithm.get_logger().get_level(),
+            VerbosityLevel::HIGH
+        );
+    }
+
+    #[test]
+    fn WhenRunInDifferentThreadsWithDifferentLoggers_ThenSuccess() {
+        let handles: Vec<_> = [VerbosityLevel::DISABLED, VerbosityLevel::LOW]
+            .into_iter()
+            .map(|level| {
+                thread::spawn(move || {
+                    let genetic_algorithm = GeneticAlgorithm::<MockMyGene>::new()
+                        .iterations(10)
+                        .logger(Logger::new(level, VerbosityType::SAVE))
+                        .init()
+                        .unwrap();
+                    assert_eq!(genetic_algorithm.get_logger().get_level(), level);
+                    genetic_algorithm.run()
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "tracing"))]
+mod logger_output {
+    use easy_ga::samples::MyGene as MockMyGene;
+    use easy_ga::GeneticAlgorithm;
+    use easy_ga::LogDestination;
+    use easy_ga::LogFormat;
+    use easy_ga::Logger;
+    use easy_ga::VerbosityLevel;
+    use easy_ga::VerbosityType;
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    /// Writer that keeps the bytes in memory to check them later.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn WhenSaveIntoWriterWithJsonLines_ThenEveryLineIsAJsonObject() {
+        let buffer = SharedBuffer::default();
+        let logger = Logger::new(VerbosityLevel::HIGH, VerbosityType::SAVE)
+            .destination(LogDestination::Writer(Box::new(buffer.clone())))
+            .format(LogFormat::JsonLines);
+
+        let (_, _) = GeneticAlgorithm::<MockMyGene>::new()
+            .iterations(2)
+            .logger(logger)
+            .try_run()
+            .unwrap();
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(!output.is_empty());
+        for line in output.lines() {
+            assert!(line.starts_with("{\"time\":"));
+            assert!(line.contains("\"level\":"));
+            assert!(line.ends_with("\"}"));
+        }
+    }
+
+    #[test]
+    fn WhenLoggerIsSetAfterNew_ThenItGetsTheValuesOfTheAlgorithm() {
+        let buffer = SharedBuffer::default();
+        let logger = Logger::new(VerbosityLevel::LOW, VerbosityType::SAVE)
+            .destination(LogDestination::Writer(Box::new(buffer.clone())));
+
+        let (_, _) = GeneticAlgorithm::<MockMyGene>::new()
+            .iterations(2)
+            .logger(logger)
+            .try_run()
+            .unwrap();
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("GeneticAlgorithm started with values:"));
+        assert!(output.contains("iterations: 2,"));
+    }
+
+    #[test]
+    fn WhenMaxFileSizeIsReached_ThenTheFileIsRotated() {
+        let directory = std::env::temp_dir().join(format!("easy_ga-{}", std::process::id()));
+        let path = directory.join("rotation.log");
+        let _ = std::fs::remove_dir_all(&directory);
+        let logger = Logger::new(VerbosityLevel::HIGH, VerbosityType::SAVE)
+            .destination(LogDestination::File(path.clone()))
+            .max_file_size(1024);
+
+        GeneticAlgorithm::<MockMyGene>::new()
+            .iterations(2)
+            .logger(logger)
+            .try_run()
+            .unwrap();
+
+        assert!(path.exists());
+        assert!(directory.join("rotation.log.1").exists());
+        assert!(std::fs::metadata(&path).unwrap().len() <= 1024);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn WhenRotatedFileAlreadyExists_ThenItIsNotOverwritten() {
+        let directory =
+            std::env::temp_dir().join(format!("easy_ga-{}-existing", std::process::id()));
+        let path = directory.join("rotation.log");
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("rotation.log.1"), "previous execution\n").unwrap();
+        let logger = Logger::new(VerbosityLevel::HIGH, VerbosityType::SAVE)
+            .destination(LogDestination::File(path.clone()))
+            .max_file_size(1024);
+
+        GeneticAlgorithm::<MockMyGene>::new()
+            .iterations(2)
+            .logger(logger)
+            .try_run()
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(directory.join("rotation.log.1")).unwrap(),
+            "previous execution\n"
+        );
+        assert!(directory.join("rotation.log.2").exists());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn WhenDestinationCanNotBeOpened_ThenInitReturnsError() {
+        let file = std::env::temp_dir().join(format!("easy_ga-{}-file", std::process::id()));
+        std::fs::write(&file, "").unwrap();
+        let logger = Logger::new(VerbosityLevel::LOW, VerbosityType::SAVE)
+            .destination(LogDestination::File(file.join("easy_ga.log")));
+
+        let result = GeneticAlgorithm::<MockMyGene>::new().logger(logger).init();
+
+        assert!(result.is_err());
+        std::fs::remove_file(&file).unwrap();
+    }
+}
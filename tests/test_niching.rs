@@ -0,0 +1,241 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod niching {
+    use easy_ga::niching;
+    use easy_ga::Gene;
+    use easy_ga::GeneticAlgorithm;
+    use easy_ga::Niching;
+    use rand::Rng;
+
+    /// Gene with two optima of the same fitness at x = 2 and x = 8.
+    #[derive(Clone, Copy, Default)]
+    struct TwoPeaksGene {
+        x: f64,
+        fitness: f64,
+    }
+
+    impl Gene for TwoPeaksGene {
+        fn init() -> Self {
+            TwoPeaksGene {
+                x: rand::thread_rng().gen_range(0.0..10.0),
+                fitness: 0.0,
+            }
+        }
+
+        fn calculate_fitness(&mut self) -> f64 {
+            self.fitness =
+                1.0 / (1.0 + (self.x - 2.0).powi(2)) + 1.0 / (1.0 + (self.x - 8.0).powi(2));
+            self.fitness
+        }
+
+        fn crossover(&self, other: &Self) -> Self {
+            if rand::thread_rng().gen_bool(0.5) {
+                *self
+            } else {
+                *other
+            }
+        }
+
+        fn mutate(&mut self) {
+            self.x = (self.x + rand::thread_rng().gen_range(-0.5..0.5)).clamp(0.0, 10.0);
+        }
+
+        fn get_fitness(&self) -> f64 {
+            self.fitness
+        }
+
+        fn distance(&self, other: &Self) -> Option<f64> {
+            Some((self.x - other.x).abs())
+        }
+    }
+
+    fn gene(x: f64) -> TwoPeaksGene {
+        let mut gene = TwoPeaksGene { x, fitness: 0.0 };
+        gene.calculate_fitness();
+        gene
+    }
+
+    #[test]
+    fn WhenSharedFitnesses_ThenCrowdedGenesArePenalized() {
+        let generation = [gene(2.0), gene(2.0), gene(8.0), gene(5.0)];
+        let min_fitness = generation[3].get_fitness();
+
+        let fitnesses = niching::shared_fitnesses(&generation, 1.0, 1.0);
+
+        assert_eq!(
+            fitnesses[0],
+            (generation[0].get_fitness() - min_fitness) / 2.0
+        );
+        assert_eq!(
+            fitnesses[1],
+            (generation[1].get_fitness() - min_fitness) / 2.0
+        );
+        assert_eq!(fitnesses[2], generation[2].get_fitness() - min_fitness);
+        assert_eq!(fitnesses[3], 0.0);
+    }
+
+    #[test]
+    fn WhenSharedFitnessesAreNegative_ThenCrowdedGenesArePenalized() {
+        let generation = [
+            TwoPeaksGene {
+                x: 2.0,
+                fitness: -1.0,
+            },
+            TwoPeaksGene {
+                x: 2.0,
+                fitness: -1.0,
+            },
+            TwoPeaksGene {
+                x: 8.0,
+                fitness: -1.0,
+            },
+            TwoPeaksGene {
+                x: 5.0,
+                fitness: -2.0,
+            },
+        ];
+
+        let fitnesses = niching::shared_fitnesses(&generation, 1.0, 1.0);
+
+        assert_eq!(fitnesses, vec![0.5, 0.5, 1.0, 0.0]);
+        assert!(fitnesses[0] < fitnesses[2]);
+    }
+
+    #[test]
+    fn WhenNicheBestGenes_ThenOneGenePerNiche() {
+        let genes = [gene(2.0), gene(2.5), gene(8.0), gene(7.0)];
+
+        let best_genes = niching::niche_best_genes(&genes, 2.0);
+
+        assert_eq!(best_genes.len(), 2);
+        assert!(best_genes.iter().any(|gene| gene.x == 2.0));
+        assert!(best_genes.iter().any(|gene| gene.x == 8.0));
+    }
+
+    #[test]
+    fn WhenRunWithDeterministicCrowding_ThenBothOptimaAreFound() {
+        let (best_genes, _) = GeneticAlgorithm::<TwoPeaksGene>::new()
+            .iterations(50)
+            .mutation_rate(0.5)
+            .niching(Niching::DeterministicCrowding { niche_radius: 2.0 })
+            .init()
+            .unwrap()
+            .run_niches();
+
+        assert!(best_genes.iter().any(|gene| (gene.x - 2.0).abs() < 1.0));
+        assert!(best_genes.iter().any(|gene| (gene.x - 8.0).abs() < 1.0));
+    }
+
+    #[test]
+    fn WhenRunWithFitnessSharing_ThenSeveralNichesAreFound() {
+        let (best_genes, _) = GeneticAlgorithm::<TwoPeaksGene>::new()
+            .iterations(50)
+            .niching(Niching::FitnessSharing {
+                sigma: 2.0,
+                alpha: 1.0,
+            })
+            .init()
+            .unwrap()
+            .run_niches();
+
+        assert!(best_genes.len() >= 2);
+    }
+
+    #[test]
+    fn WhenRunWithoutNiching_ThenOnlyTheBestGeneIsReturned() {
+        let (best_genes, _) = GeneticAlgorithm::<TwoPeaksGene>::new()
+            .iterations(10)
+            .init()
+            .unwrap()
+            .run_niches();
+
+        assert_eq!(best_genes.len(), 1);
+    }
+
+    fn speciation(target_species: usize, stagnation: u32) -> Niching {
+        Niching::Speciation {
+            threshold: 1.0,
+            target_species,
+            threshold_step: 0.1,
+            stagnation,
+        }
+    }
+
+    #[test]
+    fn WhenRunWithSpeciation_ThenBothOptimaAreFound() {
+        let (best_genes, _) = GeneticAlgorithm::<TwoPeaksGene>::new()
+            .iterations(50)
+            .mutation_rate(0.5)
+            .niching(speciation(0, 15))
+            .init()
+            .unwrap()
+            .run_niches();
+
+        assert!(best_genes.iter().any(|gene| (gene.x - 2.0).abs() < 1.0));
+        assert!(best_genes.iter().any(|gene| (gene.x - 8.0).abs() < 1.0));
+    }
+
+    #[test]
+    fn WhenSpeciated_ThenTheOffspringFillTheGeneration() {
+        let mut genetic_algorithm = GeneticAlgorithm::<TwoPeaksGene>::new()
+            .population_size(40)
+            .iterations(20)
+            .niching(speciation(0, 0))
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+
+        let species = genetic_algorithm.get_species();
+        assert!(species.len() > 1);
+        assert_eq!(species.iter().map(|s| s.get_size()).sum::<usize>(), 40);
+        assert_eq!(species.iter().map(|s| s.get_offspring()).sum::<usize>(), 40);
+        assert_eq!(genetic_algorithm.get_generation().len(), 40);
+        assert!(species.iter().all(|s| s.get_best_fitness() > 0.0));
+    }
+
+    #[test]
+    fn WhenTargetSpecies_ThenTheThresholdIsAdjusted() {
+        let mut genetic_algorithm = GeneticAlgorithm::<TwoPeaksGene>::new()
+            .population_size(50)
+            .iterations(30)
+            .niching(Niching::Speciation {
+                threshold: 0.1,
+                target_species: 3,
+                threshold_step: 0.2,
+                stagnation: 0,
+            })
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+
+        assert!(genetic_algorithm.get_species().len() > 3);
+        assert!((genetic_algorithm.get_species_threshold() - 0.3).abs() < 1e-9);
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+        }
+        assert!(genetic_algorithm.get_species().len() <= 6);
+    }
+
+    #[test]
+    fn WhenSpeciesStagnate_ThenTheyGetNoOffspring() {
+        let mut genetic_algorithm = GeneticAlgorithm::<TwoPeaksGene>::new()
+            .population_size(40)
+            .iterations(10)
+            .niching(speciation(0, 1))
+            .init()
+            .unwrap();
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+        }
+
+        // Only the species with the best gene may get offspring without improving.
+        let current_iteration = genetic_algorithm.get_current_iteration() - 1;
+        let stagnant_with_offspring = genetic_algorithm
+            .get_species()
+            .iter()
+            .filter(|s| s.get_last_improvement() < current_iteration && s.get_offspring() > 0)
+            .count();
+        assert!(stagnant_with_offspring <= 1);
+    }
+}
@@ -0,0 +1,330 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod operators {
+    use easy_ga::genotypes::{
+        BitStringCrossover, BitStringGene, BitStringProblem, DifferentialCrossover,
+        PermutationGene, PermutationMutation, PermutationProblem, RealVectorGene,
+        RealVectorProblem,
+    };
+    use easy_ga::operators::{
+        CrossoverMix, DiagonalCrossover, GeneCrossover, MutationChain, MutationMix, TwoChildren,
+    };
+    use easy_ga::samples::MyGene;
+    use easy_ga::{CrossoverOperator, Gene, GeneticAlgorithm, MutationOperator, Selection};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct OneMax;
+
+    impl BitStringProblem for OneMax {
+        const LENGTH: usize = 64;
+
+        fn fitness(gene: &BitStringGene<Self>) -> f64 {
+            gene.count_ones() as f64
+        }
+    }
+
+    struct Sorting;
+
+    impl PermutationProblem for Sorting {
+        const LENGTH: usize = 10;
+
+        fn fitness(gene: &PermutationGene<Self>) -> f64 {
+            gene.order()
+                .iter()
+                .enumerate()
+                .filter(|(i, x)| i == *x)
+                .count() as f64
+        }
+    }
+
+    struct Plane;
+
+    impl RealVectorProblem for Plane {
+        const BOUNDS: &'static [(f64, f64)] = &[(-10.0, 10.0); 4];
+
+        fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+            gene.values().iter().sum()
+        }
+    }
+
+    /// Selection always choosing the first gene.
+    struct First;
+
+    impl Selection for First {
+        fn select(&self, _fitnesses: &Vec<f64>) -> usize {
+            0
+        }
+    }
+
+    /// Crossover with three parents returning a copy of every parent.
+    struct CopyThree(Arc<AtomicUsize>);
+
+    impl CrossoverOperator<MyGene> for CopyThree {
+        fn crossover(&self, parent1: &MyGene, _parent2: &MyGene) -> MyGene {
+            *parent1
+        }
+
+        fn parents(&self) -> usize {
+            3
+        }
+
+        fn crossover_parents(&self, parents: &[&MyGene]) -> Vec<MyGene> {
+            assert_eq!(parents.len(), 3);
+            self.0.fetch_add(1, Ordering::Relaxed);
+            parents.iter().map(|parent| **parent).collect()
+        }
+    }
+
+    #[test]
+    fn WhenDefaultOperators_ThenGeneMethodsAreUsed() {
+        let mut a = MyGene::init();
+        let b = MyGene::init();
+
+        // MyGene::crossover returns `other`.
+        let child = GeneCrossover.crossover(&a, &b);
+        assert_eq!((child.x, child.y), (b.x, b.y));
+
+        easy_ga::operators::GeneMutation.mutate(&mut a);
+        assert!(a.x >= 0.0 && a.x < 100.0);
+    }
+
+    #[test]
+    fn WhenSetClosureOperators_ThenTheAlgorithmUsesThem() {
+        let crossovers = Arc::new(AtomicUsize::new(0));
+        let mutations = Arc::new(AtomicUsize::new(0));
+        let crossovers_counter = Arc::clone(&crossovers);
+        let mutations_counter = Arc::clone(&mutations);
+
+        let _ = GeneticAlgorithm::<MyGene>::new()
+            .population_size(20)
+            .iterations(5)
+            .selection_rate(0.5)
+            .mutation_rate(1.0)
+            .crossover_operator(Box::new(move |a: &MyGene, _: &MyGene| {
+                crossovers_counter.fetch_add(1, Ordering::Relaxed);
+                *a
+            }))
+            .mutation_operator(Box::new(move |gene: &mut MyGene| {
+                mutations_counter.fetch_add(1, Ordering::Relaxed);
+                gene.x = 0.0;
+            }))
+            .init()
+            .unwrap()
+            .run();
+
+        assert_eq!(crossovers.load(Ordering::Relaxed), 5 * 10);
+        assert_eq!(mutations.load(Ordering::Relaxed), 5 * 20);
+    }
+
+    #[test]
+    fn WhenGenotypeOperatorIsSet_ThenOverridesTheProblemOperator() {
+        let zeros = BitStringGene::<OneMax>::zeros();
+        let ones = BitStringGene::<OneMax>::from_bools(&[true; 64]);
+
+        let child = BitStringCrossover::Uniform.crossover(&zeros, &ones);
+
+        assert_eq!(
+            child.hamming_distance(&zeros) + child.hamming_distance(&ones),
+            64
+        );
+
+        let (gene, _) = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .iterations(20)
+            .crossover_operator(Box::new(BitStringCrossover::TwoPoint))
+            .init()
+            .unwrap()
+            .run();
+        assert!(gene.get_fitness() > 0.0);
+    }
+
+    #[test]
+    fn WhenCrossoverMix_ThenOnlyOperatorsWithWeightAreUsed() {
+        let mix = CrossoverMix::new()
+            .operator(Box::new(|a: &i32, _: &i32| *a), 0.0)
+            .operator(Box::new(|_: &i32, b: &i32| *b), 1.0);
+
+        for _ in 0..100 {
+            assert_eq!(mix.crossover(&1, &2), 2);
+        }
+    }
+
+    #[test]
+    fn WhenMutationMix_ThenOneOperatorIsApplied() {
+        let mix = MutationMix::new()
+            .operator(Box::new(|x: &mut i32| *x += 1), 1.0)
+            .operator(Box::new(|x: &mut i32| *x += 10), 1.0);
+
+        let mut values = Vec::new();
+        for _ in 0..100 {
+            let mut x = 0;
+            mix.mutate(&mut x);
+            values.push(x);
+        }
+
+        assert!(values.iter().all(|x| *x == 1 || *x == 10));
+        assert!(values.contains(&1) && values.contains(&10));
+    }
+
+    #[test]
+    fn WhenMutationChain_ThenEveryOperatorIsAppliedInOrder() {
+        let chain = MutationChain::new()
+            .operator(Box::new(|x: &mut i32| *x += 1))
+            .operator(Box::new(|x: &mut i32| *x *= 10));
+
+        let mut x = 1;
+        chain.mutate(&mut x);
+
+        assert_eq!(x, 20);
+    }
+
+    #[test]
+    fn WhenChainPermutationMutations_ThenGeneIsAValidPermutation() {
+        let chain = MutationChain::new()
+            .operator(Box::new(PermutationMutation::Inversion))
+            .operator(Box::new(PermutationMutation::Swap));
+
+        let mut gene = PermutationGene::<Sorting>::init();
+        for _ in 0..100 {
+            chain.mutate(&mut gene);
+            assert!(gene.is_valid());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenMixWithNegativeWeight_ThenPanics() {
+        let _ = CrossoverMix::new().operator(Box::new(|a: &i32, _: &i32| *a), -1.0);
+    }
+
+    #[test]
+    fn WhenTwoChildren_ThenBothParentRolesAreUsed() {
+        let children = TwoChildren(|a: &i32, b: &i32| a * 10 + b).crossover_parents(&[&1, &2]);
+
+        assert_eq!(children, vec![12, 21]);
+    }
+
+    #[test]
+    fn WhenDiagonalCrossover_ThenEveryPositionComesFromADifferentParent() {
+        let a = BitStringGene::<OneMax>::zeros();
+        let b = BitStringGene::<OneMax>::from_bools(&[true; 64]);
+        let c = BitStringGene::<OneMax>::zeros();
+        let operator = DiagonalCrossover { parents: 3 };
+
+        let children = operator.crossover_parents(&[&a, &b, &c]);
+
+        assert_eq!(
+            CrossoverOperator::<BitStringGene<OneMax>>::parents(&operator),
+            3
+        );
+        assert_eq!(children.len(), 3);
+        // Every position of `b` goes to exactly one child.
+        let ones: usize = children.iter().map(|child| child.count_ones()).sum();
+        assert_eq!(ones, 64);
+    }
+
+    #[test]
+    fn WhenDifferentialCrossover_ThenChildIsTheDifferentialVector() {
+        let a = RealVectorGene::<Plane>::from_values(vec![1.0, 1.0, 1.0, 1.0]);
+        let b = RealVectorGene::<Plane>::from_values(vec![3.0, 2.0, 1.0, 0.0]);
+        let c = RealVectorGene::<Plane>::from_values(vec![1.0, 1.0, 1.0, 1.0]);
+
+        let children = DifferentialCrossover { weight: 0.5 }.crossover_parents(&[&a, &b, &c]);
+
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].values(), &[2.0, 1.5, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn WhenMultiParentOperator_ThenTheAlgorithmPassesEveryParent() {
+        let crossovers = Arc::new(AtomicUsize::new(0));
+
+        let genetic_algorithm = GeneticAlgorithm::<MyGene>::new()
+            .population_size(20)
+            .iterations(3)
+            .selection_rate(0.5)
+            .crossover_operator(Box::new(CopyThree(Arc::clone(&crossovers))))
+            .init()
+            .unwrap();
+        let _ = genetic_algorithm.run();
+
+        // 10 children per generation, 3 per crossover.
+        assert_eq!(crossovers.load(Ordering::Relaxed), 3 * 4);
+    }
+
+    #[test]
+    fn WhenCrossoverRateIsZero_ThenChildrenAreCopiesOfTheParents() {
+        let crossovers = Arc::new(AtomicUsize::new(0));
+        let crossovers_counter = Arc::clone(&crossovers);
+
+        let genetic_algorithm = GeneticAlgorithm::<MyGene>::new()
+            .iterations(3)
+            .crossover_rate(0.0)
+            .crossover_operator(Box::new(move |a: &MyGene, _: &MyGene| {
+                crossovers_counter.fetch_add(1, Ordering::Relaxed);
+                *a
+            }))
+            .init()
+            .unwrap();
+        let _ = genetic_algorithm.run();
+
+        assert_eq!(crossovers.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn WhenParentsAreSelected_ThenTheSelectionAlgorithmIsUsed() {
+        let mut genetic_algorithm = GeneticAlgorithm::<MyGene>::new()
+            .population_size(10)
+            .iterations(2)
+            .selection_rate(0.5)
+            .mutation_rate(0.0)
+            .selection_algorithm(Box::new(First))
+            .crossover_operator(Box::new(|a: &MyGene, b: &MyGene| {
+                assert_eq!((a.x, a.y), (b.x, b.y));
+                *a
+            }))
+            .init()
+            .unwrap();
+
+        let first = genetic_algorithm.get_generation()[0];
+        let generation = genetic_algorithm.next_iteration();
+
+        assert!(generation[5..]
+            .iter()
+            .all(|gene| (gene.x, gene.y) == (first.x, first.y)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenSetCrossoverRateWithOverflowValue_ThenPanic() {
+        let _ = GeneticAlgorithm::<MyGene>::new().crossover_rate(1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Crossover operator needs at least 2 parents")]
+    fn WhenSetCrossoverOperatorWithOneParent_ThenPanic() {
+        let _ = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .crossover_operator(Box::new(DiagonalCrossover { parents: 1 }));
+    }
+
+    #[test]
+    #[should_panic(expected = "Crossover operator needs at least 2 parents")]
+    fn WhenMixWithNoParents_ThenPanics() {
+        let _ = CrossoverMix::<BitStringGene<OneMax>>::new()
+            .operator(Box::new(DiagonalCrossover { parents: 0 }), 1.0);
+    }
+
+    #[test]
+    fn WhenRunWithDiagonalCrossover_ThenSuccess() {
+        let (gene, _) = GeneticAlgorithm::<BitStringGene<OneMax>>::new()
+            .iterations(50)
+            .selection_rate(0.5)
+            .crossover_operator(Box::new(DiagonalCrossover { parents: 4 }))
+            .init()
+            .unwrap()
+            .run();
+
+        assert!(gene.get_fitness() > 32.0);
+    }
+}
@@ -0,0 +1,185 @@
+#![allow(non_snake_case)]
+
+mod common;
+
+#[cfg(test)]
+mod particle_swarm {
+    use easy_ga::genetic_algorithm::StopCriteria;
+    use easy_ga::genotypes::{RealVectorFitness, RealVectorGene, RealVectorProblem};
+    use easy_ga::{Gene, GeneticAlgorithm, InertiaSchedule, ParticleSwarm, SwarmTopology};
+    use std::sync::Arc;
+
+    use super::common::Sphere;
+
+    struct Bounds;
+
+    impl RealVectorProblem for Bounds {
+        const BOUNDS: &'static [(f64, f64)] = &[(-5.0, 5.0); 3];
+    }
+
+    /// Sphere centred in a point only known at runtime.
+    fn shifted_sphere(
+        center: Vec<f64>,
+    ) -> impl Fn(&RealVectorGene<RealVectorFitness<Bounds>>) -> f64 + Send + Sync + Clone {
+        move |gene| {
+            -gene
+                .values()
+                .iter()
+                .zip(center.iter())
+                .map(|(x, c)| (x - c).powi(2))
+                .sum::<f64>()
+        }
+    }
+
+    #[test]
+    fn WhenFitnessClosure_ThenGeneticAlgorithmAndSwarmUseIt() {
+        let fitness = shifted_sphere(vec![1.0, -2.0, 3.0]);
+
+        let (gene, _) = ParticleSwarm::<RealVectorFitness<Bounds>>::new()
+            .iterations(300)
+            .problem(Arc::new(RealVectorFitness::new(fitness.clone())))
+            .swarm_size(50)
+            .run();
+        assert!(gene.get_fitness() > -1e-4, "{gene:?}");
+        assert!(gene.get_problem().is_some());
+
+        let (gene, _) = GeneticAlgorithm::<RealVectorGene<RealVectorFitness<Bounds>>>::new()
+            .iterations(10)
+            .gene_init(RealVectorGene::problem(fitness))
+            .run();
+        assert!(gene.get_problem().is_some());
+    }
+
+    #[test]
+    fn WhenEveryTopology_ThenSphereIsSolved() {
+        for topology in [SwarmTopology::Global, SwarmTopology::Ring { neighbours: 1 }] {
+            let (gene, stop_criteria) = ParticleSwarm::<Sphere>::new()
+                .iterations(400)
+                .topology(topology)
+                .run();
+
+            assert_eq!(stop_criteria, StopCriteria::MaxIterations);
+            assert!(gene.get_fitness() > -1e-4, "{topology:?}: {gene:?}");
+        }
+    }
+
+    #[test]
+    fn WhenConstriction_ThenSphereIsSolved() {
+        let (gene, _) = ParticleSwarm::<Sphere>::new()
+            .iterations(400)
+            .constriction(true)
+            .cognitive(2.05)
+            .social(2.05)
+            .run();
+
+        assert!(gene.get_fitness() > -1e-4, "{gene:?}");
+    }
+
+    #[test]
+    fn WhenConstriction_ThenCoefficientIsClercs() {
+        let particle_swarm = ParticleSwarm::<Sphere>::new().cognitive(2.05).social(2.05);
+        assert!((particle_swarm.constriction_coefficient() - 0.7298).abs() < 1e-4);
+
+        let particle_swarm = ParticleSwarm::<Sphere>::new().cognitive(1.0).social(1.0);
+        assert_eq!(particle_swarm.constriction_coefficient(), 1.0);
+    }
+
+    #[test]
+    fn WhenLinearDecay_ThenInertiaGoesFromStartToEnd() {
+        let inertia = InertiaSchedule::LinearDecay {
+            start: 0.9,
+            end: 0.4,
+        };
+
+        assert_eq!(inertia.weight(0, 101), 0.9);
+        assert!((inertia.weight(50, 101) - 0.65).abs() < 1e-12);
+        assert!((inertia.weight(100, 101) - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn WhenMaxVelocity_ThenVelocitiesAreClamped() {
+        let mut particle_swarm = ParticleSwarm::<Sphere>::new()
+            .iterations(20)
+            .inertia(InertiaSchedule::Constant { weight: 1.0 })
+            .max_velocity(0.05)
+            .init()
+            .unwrap();
+        while particle_swarm.is_running() {
+            particle_swarm.next_iteration();
+            assert!(particle_swarm
+                .get_velocities()
+                .iter()
+                .flatten()
+                .all(|velocity| velocity.abs() <= 0.05));
+        }
+    }
+
+    #[test]
+    fn WhenRun_ThenStatisticsOfEveryIterationAreSavedAndBestNeverGetsWorse() {
+        let mut particle_swarm = ParticleSwarm::<Sphere>::new()
+            .iterations(30)
+            .swarm_size(20)
+            .init()
+            .unwrap();
+        let mut best_fitness = particle_swarm.get_best_gene().unwrap().get_fitness();
+        while particle_swarm.is_running() {
+            particle_swarm.next_iteration();
+            assert!(particle_swarm.get_best_gene().unwrap().get_fitness() >= best_fitness);
+            best_fitness = particle_swarm.get_best_gene().unwrap().get_fitness();
+            assert!(particle_swarm
+                .get_personal_bests()
+                .iter()
+                .all(|gene| gene.get_fitness() <= best_fitness));
+        }
+
+        assert_eq!(particle_swarm.get_particles().len(), 20);
+        assert_eq!(particle_swarm.get_statistics().len(), 30);
+        assert_eq!(
+            particle_swarm.get_stop_criteria(),
+            StopCriteria::MaxIterations
+        );
+    }
+
+    #[test]
+    fn WhenNextIterationWithoutInit_ThenSwarmIsEvaluatedAndHasVelocities() {
+        let mut particle_swarm = ParticleSwarm::<Sphere>::new().iterations(5).swarm_size(20);
+        assert!(particle_swarm.get_best_gene().is_none());
+
+        particle_swarm.next_iteration();
+        assert!(particle_swarm.is_running());
+        assert_eq!(particle_swarm.get_velocities().len(), 20);
+        for gene in particle_swarm.get_personal_bests() {
+            assert_eq!(gene.get_fitness(), Sphere::fitness(gene));
+        }
+        let best_gene = particle_swarm.get_best_gene().unwrap();
+        assert_eq!(best_gene.get_fitness(), Sphere::fitness(best_gene));
+        assert!(best_gene.get_fitness() < 0.0);
+    }
+
+    #[test]
+    fn WhenFitnessGoalIsReached_ThenStopsWithFitnessAchieved() {
+        let (gene, stop_criteria) = ParticleSwarm::<Sphere>::new()
+            .iterations(1000)
+            .fitness_goal(-0.01)
+            .run();
+
+        assert_eq!(stop_criteria, StopCriteria::FitnessAchieved);
+        assert!(gene.get_fitness() >= -0.01);
+    }
+
+    #[test]
+    fn WhenSwarmConverges_ThenStopsWithConverged() {
+        let (_, stop_criteria) = ParticleSwarm::<Sphere>::new()
+            .iterations(1000)
+            .convergence_threshold(1e-3)
+            .run();
+
+        assert_eq!(stop_criteria, StopCriteria::Converged);
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenSwarmSizeIsZero_ThenPanics() {
+        ParticleSwarm::<Sphere>::new().swarm_size(0);
+    }
+}
@@ -0,0 +1,162 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod quality_diversity {
+    use easy_ga::genetic_algorithm::StopCriteria;
+    use easy_ga::genotypes::{RealVectorGene, RealVectorProblem};
+    use easy_ga::quality_diversity::novelty_scores;
+    use easy_ga::{
+        Gene, GeneticAlgorithm, MapElites, MapElitesArchive, NoveltySearch, SelectionAlgorithms,
+    };
+
+    /// Deceptive problem whose fitness leads to the corner (1, 1).
+    struct Corner;
+
+    impl RealVectorProblem for Corner {
+        const BOUNDS: &'static [(f64, f64)] = &[(0.0, 1.0); 2];
+
+        fn fitness(gene: &RealVectorGene<Self>) -> f64 {
+            gene.values().iter().sum()
+        }
+
+        fn behaviour(gene: &RealVectorGene<Self>) -> Option<Vec<f64>> {
+            Some(gene.values().to_vec())
+        }
+    }
+
+    fn corner_gene(values: &[f64]) -> RealVectorGene<Corner> {
+        let mut gene = RealVectorGene::<Corner>::from_values(values.to_vec());
+        gene.calculate_fitness();
+        gene
+    }
+
+    #[test]
+    fn WhenNoveltyScores_ThenMeanDistanceToNearestNeighbours() {
+        let behaviours = vec![vec![0.0], vec![1.0], vec![3.0]];
+
+        assert_eq!(novelty_scores(&behaviours, &[], 1), vec![1.0, 1.0, 2.0]);
+        assert_eq!(novelty_scores(&behaviours, &[], 2), vec![2.0, 1.5, 2.5]);
+        assert_eq!(
+            novelty_scores(&behaviours, &[vec![3.5]], 1),
+            vec![1.0, 1.0, 0.5]
+        );
+    }
+
+    #[test]
+    fn WhenBehaviourIsBinned_ThenCellIsInTheGrid() {
+        let archive = MapElitesArchive::<RealVectorGene<Corner>>::new(&[(0.0, 1.0); 2], &[10, 5]);
+
+        assert_eq!(archive.num_cells(), 50);
+        assert_eq!(archive.cell(&[0.0, 0.0]), Some(0));
+        assert_eq!(archive.cell(&[0.05, 0.95]), Some(4));
+        assert_eq!(archive.cell(&[0.95, 0.05]), Some(45));
+        assert_eq!(archive.cell(&[1.0, 1.0]), Some(49));
+        assert_eq!(archive.cell(&[-3.0, 7.0]), Some(4));
+        assert_eq!(archive.cell(&[0.5]), None);
+    }
+
+    #[test]
+    fn WhenInserted_ThenEveryCellKeepsItsBestGene() {
+        let mut archive = MapElitesArchive::new(&[(0.0, 1.0); 2], &[2, 2]);
+
+        assert!(archive.insert(corner_gene(&[0.1, 0.1])));
+        assert!(archive.insert(corner_gene(&[0.4, 0.4])));
+        assert!(!archive.insert(corner_gene(&[0.2, 0.2])));
+        assert!(archive.insert(corner_gene(&[0.9, 0.9])));
+
+        assert_eq!(archive.len(), 2);
+        assert_eq!(archive.coverage(), 0.5);
+        assert_eq!(archive.get(0).unwrap().values(), &[0.4, 0.4]);
+        assert!((archive.qd_score() - 2.6).abs() < 1e-12);
+        assert_eq!(archive.best().unwrap().values(), &[0.9, 0.9]);
+    }
+
+    #[test]
+    fn WhenMapElites_ThenTheGridIsIlluminated() {
+        let mut map_elites = MapElites::<RealVectorGene<Corner>>::new(&[(0.0, 1.0); 2], &[10, 10])
+            .iterations(50)
+            .init()
+            .unwrap();
+        let initial_qd_score = map_elites.get_archive().qd_score();
+        while map_elites.is_running() {
+            map_elites.next_iteration();
+        }
+
+        let archive = map_elites.get_archive();
+        assert!(archive.coverage() > 0.9, "{}", archive.coverage());
+        assert!(archive.qd_score() > initial_qd_score);
+        assert_eq!(map_elites.get_statistics().len(), 50);
+        assert_eq!(map_elites.get_stop_criteria(), StopCriteria::MaxIterations);
+        assert_eq!(
+            map_elites.get_best_gene().get_fitness(),
+            archive.best().unwrap().get_fitness()
+        );
+    }
+
+    #[test]
+    fn WhenFitnessGoalIsReached_ThenMapElitesStopsWithFitnessAchieved() {
+        let (gene, stop_criteria) =
+            MapElites::<RealVectorGene<Corner>>::new(&[(0.0, 1.0); 2], &[10, 10])
+                .fitness_goal(1.9)
+                .run();
+
+        assert_eq!(stop_criteria, StopCriteria::FitnessAchieved);
+        assert!(gene.get_fitness() >= 1.9);
+    }
+
+    #[test]
+    fn WhenNoveltySearch_ThenTheGenerationStaysSpread() {
+        let run = |novelty_search: Option<NoveltySearch>| {
+            let mut genetic_algorithm = GeneticAlgorithm::<RealVectorGene<Corner>>::new()
+                .population_size(50)
+                .iterations(100)
+                .mutation_rate(0.5)
+                .selection_rate(0.5)
+                .selection_algorithm(Box::new(SelectionAlgorithms::Tournament(4)))
+                .diversity_statistics(true);
+            if let Some(novelty_search) = novelty_search {
+                genetic_algorithm = genetic_algorithm.novelty_search(novelty_search);
+            }
+            let mut genetic_algorithm = genetic_algorithm.init().unwrap();
+            while genetic_algorithm.is_running() {
+                genetic_algorithm.next_iteration();
+            }
+            let diversity = genetic_algorithm
+                .get_statistics()
+                .last()
+                .unwrap()
+                .diversity
+                .mean_pairwise_distance
+                .unwrap();
+            (diversity, genetic_algorithm.get_novelty_archive().len())
+        };
+
+        let (fitness_diversity, fitness_archive) = run(None);
+        let (novelty_diversity, novelty_archive) = run(Some(NoveltySearch {
+            k: 5,
+            archive_threshold: 0.05,
+        }));
+
+        assert_eq!(fitness_archive, 0);
+        assert!(novelty_archive > 0);
+        assert!(
+            novelty_diversity > 2.0 * fitness_diversity,
+            "{novelty_diversity} {fitness_diversity}"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenNoveltySearchWithoutNeighbours_ThenPanics() {
+        GeneticAlgorithm::<RealVectorGene<Corner>>::new().novelty_search(NoveltySearch {
+            k: 0,
+            archive_threshold: 0.1,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenArchiveBinsDoNotMatchBounds_ThenPanics() {
+        MapElitesArchive::<RealVectorGene<Corner>>::new(&[(0.0, 1.0); 2], &[10]);
+    }
+}
@@ -0,0 +1,192 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod restart {
+    use easy_ga::statistics::GenerationStatistics;
+    use easy_ga::{Gene, GeneticAlgorithm, Reinitialisation, Restart, RestartTrigger};
+    use rand::Rng;
+
+    /// Gene whose fitness is its value, crossed over as a copy of a parent and never mutated.
+    #[derive(Clone, Copy, Debug, Default)]
+    struct ValueGene {
+        x: f64,
+        fitness: f64,
+    }
+
+    impl Gene for ValueGene {
+        fn init() -> Self {
+            ValueGene {
+                x: rand::thread_rng().gen_range(0.0..10.0),
+                fitness: 0.0,
+            }
+        }
+
+        fn calculate_fitness(&mut self) -> f64 {
+            self.fitness = self.x;
+            self.fitness
+        }
+
+        fn crossover(&self, _other: &Self) -> Self {
+            *self
+        }
+
+        fn mutate(&mut self) {}
+
+        fn get_fitness(&self) -> f64 {
+            self.fitness
+        }
+
+        fn distance(&self, other: &Self) -> Option<f64> {
+            Some((self.x - other.x).abs())
+        }
+    }
+
+    fn restart(trigger: RestartTrigger, reinitialisation: Reinitialisation) -> Restart {
+        Restart {
+            trigger,
+            reinitialisation,
+            population_growth: 1.0,
+            max_restarts: Some(1),
+        }
+    }
+
+    #[test]
+    fn WhenStagnated_ThenRestartsUpToTheMax() {
+        let mut genetic_algorithm = GeneticAlgorithm::<ValueGene>::new()
+            .population_size(10)
+            .iterations(30)
+            .restart(Restart {
+                max_restarts: Some(3),
+                ..restart(
+                    RestartTrigger::Stagnation { iterations: 2 },
+                    Reinitialisation::Full,
+                )
+            })
+            .init()
+            .unwrap();
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+        }
+
+        let statistics = genetic_algorithm.get_statistics();
+        let restarted: Vec<u32> = statistics
+            .iter()
+            .filter(|statistics| statistics.restarted)
+            .map(|statistics| statistics.iteration)
+            .collect();
+        assert_eq!(genetic_algorithm.get_restarts(), 3);
+        assert_eq!(restarted.len(), 3);
+        assert!(restarted.windows(2).all(|pair| pair[1] - pair[0] >= 2));
+    }
+
+    #[test]
+    fn WhenFullRestart_ThenOnlyTheBestGeneIsKept() {
+        let mut genetic_algorithm = GeneticAlgorithm::<ValueGene>::new()
+            .population_size(20)
+            .selection_rate(1.0)
+            .restart(restart(
+                RestartTrigger::LowDiversity { threshold: 100.0 },
+                Reinitialisation::Full,
+            ))
+            .init()
+            .unwrap();
+        let initial_generation = genetic_algorithm.get_generation();
+        genetic_algorithm.next_iteration();
+
+        let best_x = initial_generation
+            .iter()
+            .map(|gene| gene.x)
+            .fold(f64::MIN, f64::max);
+        let generation = genetic_algorithm.get_generation();
+        assert!(genetic_algorithm.get_statistics()[0].restarted);
+        assert_eq!(generation.len(), 20);
+        assert!(generation.iter().any(|gene| gene.x == best_x));
+        assert!(
+            generation
+                .iter()
+                .filter(|gene| initial_generation.iter().any(|initial| initial.x == gene.x))
+                .count()
+                < 5
+        );
+    }
+
+    #[test]
+    fn WhenPartialRestart_ThenTheBestGenesAreKept() {
+        let mut genetic_algorithm = GeneticAlgorithm::<ValueGene>::new()
+            .population_size(10)
+            .selection_rate(1.0)
+            .restart(restart(
+                RestartTrigger::LowDiversity { threshold: 100.0 },
+                Reinitialisation::Partial { fraction: 0.5 },
+            ))
+            .init()
+            .unwrap();
+        let mut initial_xs: Vec<f64> = genetic_algorithm
+            .get_generation()
+            .iter()
+            .map(|gene| gene.x)
+            .collect();
+        genetic_algorithm.next_iteration();
+
+        initial_xs.sort_by(|a, b| b.total_cmp(a));
+        let generation = genetic_algorithm.get_generation();
+        for x in &initial_xs[..5] {
+            assert!(generation.iter().any(|gene| gene.x == *x));
+        }
+    }
+
+    #[test]
+    fn WhenPopulationGrowth_ThenEveryRestartGrowsThePopulation() {
+        let mut genetic_algorithm = GeneticAlgorithm::<ValueGene>::new()
+            .population_size(10)
+            .iterations(10)
+            .restart(Restart {
+                population_growth: 2.0,
+                max_restarts: Some(2),
+                ..restart(
+                    RestartTrigger::LowDiversity { threshold: 100.0 },
+                    Reinitialisation::Full,
+                )
+            })
+            .init()
+            .unwrap();
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+        }
+
+        assert_eq!(genetic_algorithm.get_restarts(), 2);
+        assert_eq!(genetic_algorithm.get_population_size(), 40);
+        assert_eq!(genetic_algorithm.get_generation().len(), 40);
+    }
+
+    #[test]
+    fn WhenStatisticsToCsv_ThenRestartsAreExported() {
+        let mut genetic_algorithm = GeneticAlgorithm::<ValueGene>::new()
+            .population_size(10)
+            .restart(restart(
+                RestartTrigger::LowDiversity { threshold: 100.0 },
+                Reinitialisation::Full,
+            ))
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+        genetic_algorithm.next_iteration();
+
+        let statistics = genetic_algorithm.get_statistics();
+        assert!(GenerationStatistics::csv_header().contains(",restarted,"));
+        assert!(statistics[0].to_csv().contains(",true,"));
+        assert!(statistics[1].to_csv().contains(",false,"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn WhenPopulationShrinks_ThenPanics() {
+        GeneticAlgorithm::<ValueGene>::new().restart(Restart {
+            population_growth: 0.5,
+            ..restart(
+                RestartTrigger::Stagnation { iterations: 5 },
+                Reinitialisation::Full,
+            )
+        });
+    }
+}
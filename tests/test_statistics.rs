@@ -0,0 +1,113 @@
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod statistics {
+    use easy_ga::genetic_algorithm::StopCriteria;
+    use easy_ga::samples::MyGene as MockMyGene;
+    use easy_ga::statistics::{self, Diversity, GenerationStatistics};
+    use easy_ga::Gene;
+    use easy_ga::GeneticAlgorithm;
+
+    fn generation(values: &[(f64, i32)]) -> Vec<MockMyGene> {
+        values
+            .iter()
+            .map(|&(x, y)| {
+                let mut gene = MockMyGene::default();
+                gene.x = x;
+                gene.y = y;
+                gene.calculate_fitness();
+                gene
+            })
+            .collect()
+    }
+
+    #[test]
+    fn WhenIdenticalGenes_ThenDiversityIsZero() {
+        let diversity = Diversity::calculate(&generation(&[(1.0, 1), (1.0, 1), (1.0, 1)]));
+
+        assert_eq!(diversity.mean_pairwise_distance, Some(0.0));
+        assert_eq!(diversity.unique_genomes, Some(1));
+        assert_eq!(diversity.fitness_entropy, 0.0);
+    }
+
+    #[test]
+    fn WhenDifferentGenes_ThenDiversityIsCalculated() {
+        let diversity = Diversity::calculate(&generation(&[(0.0, 0), (3.0, 4), (3.0, 4)]));
+
+        // Distances: 5, 5 and 0.
+        assert_eq!(diversity.mean_pairwise_distance, Some(10.0 / 3.0));
+        assert_eq!(diversity.unique_genomes, Some(2));
+        assert!(diversity.fitness_entropy > 0.0);
+    }
+
+    #[test]
+    fn WhenRun_ThenStatisticsOfEveryGenerationAreSaved() {
+        let iterations = 10;
+        let mut genetic_algorithm = GeneticAlgorithm::<MockMyGene>::new()
+            .iterations(iterations)
+            .diversity_statistics(true)
+            .init()
+            .unwrap();
+        while genetic_algorithm.is_running() {
+            genetic_algorithm.next_iteration();
+        }
+
+        let statistics = genetic_algorithm.get_statistics();
+        assert_eq!(statistics.len(), iterations as usize);
+        for (i, generation_statistics) in statistics.iter().enumerate() {
+            assert_eq!(generation_statistics.iteration, i as u32);
+            assert!(generation_statistics.best_fitness >= generation_statistics.mean_fitness);
+            assert!(generation_statistics.mean_fitness >= generation_statistics.worst_fitness);
+            assert!(generation_statistics
+                .diversity
+                .mean_pairwise_distance
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn WhenDiversityStatisticsAreNotRequested_ThenOnlyTheFitnessEntropyIsCalculated() {
+        let mut genetic_algorithm = GeneticAlgorithm::<MockMyGene>::new()
+            .iterations(1)
+            .init()
+            .unwrap();
+        genetic_algorithm.next_iteration();
+
+        let diversity = genetic_algorithm.get_statistics()[0].diversity;
+        assert_eq!(diversity.mean_pairwise_distance, None);
+        assert_eq!(diversity.unique_genomes, None);
+        assert!(diversity.fitness_entropy > 0.0);
+        let row =
+            GenerationStatistics::calculate(0, &generation(&[(0.0, 0), (3.0, 4)]), false).to_csv();
+        let columns: Vec<&str> = row.split(',').collect();
+        assert_eq!(&columns[4..6], &["", ""]);
+    }
+
+    #[test]
+    fn WhenConvergenceThresholdIsReached_ThenStopBecauseConverged() {
+        let genetic_algorithm = GeneticAlgorithm::<MockMyGene>::new()
+            .convergence_threshold(f64::MAX)
+            .init()
+            .unwrap();
+
+        let (_, stopCriteria) = genetic_algorithm.run();
+        assert_eq!(stopCriteria, StopCriteria::Converged);
+    }
+
+    #[test]
+    fn WhenWriteCsv_ThenOneRowPerGeneration() {
+        let statistics = vec![
+            GenerationStatistics::calculate(0, &generation(&[(0.0, 0), (3.0, 4)]), true),
+            GenerationStatistics::calculate(1, &generation(&[(1.0, 1), (1.0, 1)]), true),
+        ];
+        let mut output = Vec::new();
+
+        statistics::write_csv(&statistics, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], GenerationStatistics::csv_header());
+        assert!(lines[2].starts_with("1,2,2,2,0,1,"));
+    }
+}
@@ -0,0 +1,123 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "tracing")]
+
+#[cfg(test)]
+mod tracing_feature {
+    use easy_ga::samples::MyGene as MockMyGene;
+    use easy_ga::GeneticAlgorithm;
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Subscriber that records the names of the spans, the number of events and the fields of every event.
+    #[derive(Default)]
+    struct RecordingSubscriber {
+        next_id: AtomicU64,
+        spans: Arc<Mutex<Vec<String>>>,
+        events: Arc<AtomicU64>,
+        fields: Arc<Mutex<Vec<HashMap<String, String>>>>,
+    }
+
+    /// Visitor collecting the fields of an event as strings.
+    struct FieldVisitor(HashMap<String, String>);
+
+    impl Visit for FieldVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl Subscriber for RecordingSubscriber {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            self.spans
+                .lock()
+                .unwrap()
+                .push(span.metadata().name().to_string());
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            self.events.fetch_add(1, Ordering::Relaxed);
+            let mut visitor = FieldVisitor(HashMap::new());
+            event.record(&mut visitor);
+            self.fields.lock().unwrap().push(visitor.0);
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[test]
+    fn WhenRunWithTracing_ThenSpansPerGenerationAndPhaseAreEmitted() {
+        let subscriber = RecordingSubscriber::default();
+        let spans = Arc::clone(&subscriber.spans);
+        let events = Arc::clone(&subscriber.events);
+        let iterations = 5;
+
+        tracing::subscriber::with_default(subscriber, || {
+            GeneticAlgorithm::<MockMyGene>::new()
+                .iterations(iterations)
+                .init()
+                .unwrap()
+                .run();
+        });
+
+        let spans = spans.lock().unwrap();
+        let generations = spans.iter().filter(|name| *name == "generation").count();
+        let phases = spans.iter().filter(|name| *name == "phase").count();
+        assert_eq!(generations, iterations as usize);
+        // fitness, selection, crossover, mutation and save per generation plus the save on init.
+        assert_eq!(phases, 5 * iterations as usize + 1);
+        assert!(events.load(Ordering::Relaxed) > 0);
+    }
+
+    #[test]
+    fn WhenRunWithTracing_ThenEventsHaveStructuredFields() {
+        let subscriber = RecordingSubscriber::default();
+        let fields = Arc::clone(&subscriber.fields);
+
+        tracing::subscriber::with_default(subscriber, || {
+            GeneticAlgorithm::<MockMyGene>::new()
+                .iterations(3)
+                .init()
+                .unwrap()
+                .run();
+        });
+
+        let fields = fields.lock().unwrap();
+        assert!(fields
+            .iter()
+            .all(|event| event.contains_key("verbosity") && event.contains_key("message")));
+        let best_genes: Vec<&HashMap<String, String>> = fields
+            .iter()
+            .filter(|event| event["message"] == "Best gene")
+            .collect();
+        assert_eq!(best_genes.len(), 3);
+        for (iteration, event) in best_genes.iter().enumerate() {
+            assert_eq!(event["iteration"], iteration.to_string());
+            assert!(event["best_fitness"].parse::<f64>().is_ok());
+        }
+        // Every event after the first best fitness carries it.
+        let first = fields
+            .iter()
+            .position(|event| event["message"] == "Best gene")
+            .unwrap();
+        assert!(fields[first..]
+            .iter()
+            .all(|event| event.contains_key("iteration") && event.contains_key("best_fitness")));
+    }
+}